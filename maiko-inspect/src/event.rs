@@ -0,0 +1,74 @@
+use std::{borrow::Cow, sync::Arc};
+
+use maiko::{Event, Label, Topic};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An event of any type, kept as the JSON it was recorded as.
+///
+/// The inspector doesn't know the application's event type, so events are
+/// labelled the way `#[derive(Label)]` would label them: by the variant name
+/// of an externally tagged enum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RawEvent(pub Value);
+
+impl Event for RawEvent {}
+
+impl Label for RawEvent {
+    fn label(&self) -> Cow<'static, str> {
+        match &self.0 {
+            // Unit variant: "Tick"
+            Value::String(name) => name.clone().into(),
+            // Newtype, tuple or struct variant: {"Order": ...}
+            Value::Object(map) if map.len() == 1 => {
+                map.keys().next().cloned().unwrap_or_default().into()
+            }
+            Value::Object(_) => "Object".into(),
+            Value::Array(_) => "Array".into(),
+            Value::Number(_) => "Number".into(),
+            Value::Bool(_) => "Bool".into(),
+            Value::Null => "Null".into(),
+        }
+    }
+}
+
+/// A topic known only by the label the recorder stored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordedTopic(Arc<str>);
+
+impl RecordedTopic {
+    pub fn from_record(_: &RawEvent, label: Option<&str>) -> Self {
+        Self(label.unwrap_or("-").into())
+    }
+}
+
+impl Topic<RawEvent> for RecordedTopic {
+    fn from_event(_: &RawEvent) -> Self {
+        Self("-".into())
+    }
+}
+
+impl Label for RecordedTopic {
+    fn label(&self) -> Cow<'static, str> {
+        self.0.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(json: &str) -> Cow<'static, str> {
+        RawEvent(serde_json::from_str(json).unwrap()).label()
+    }
+
+    #[test]
+    fn labels_follow_enum_variants() {
+        assert_eq!(label(r#""Tick""#), "Tick");
+        assert_eq!(label(r#"{"Order":[1,2]}"#), "Order");
+        assert_eq!(label(r#"{"Fill":{"qty":3}}"#), "Fill");
+        assert_eq!(label(r#"{"a":1,"b":2}"#), "Object");
+        assert_eq!(label("42"), "Number");
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use maiko::{Label, testing::EventEntry};
+
+use crate::event::{RawEvent, RecordedTopic};
+
+/// A delivery as drawn on the flowchart: sender, receiver and event label.
+pub type Delivery<'a> = (&'a str, &'a str, String);
+
+pub fn delivery(entry: &EventEntry<RawEvent, RecordedTopic>) -> Delivery<'_> {
+    (
+        entry.sender(),
+        entry.receiver().name(),
+        entry.payload().label().into_owned(),
+    )
+}
+
+/// Mermaid flowchart of who actually sent what to whom, one edge per
+/// sender, receiver and event label, with the number of deliveries.
+pub fn to_mermaid<'a>(deliveries: impl IntoIterator<Item = Delivery<'a>>) -> String {
+    let mut edges: BTreeMap<Delivery, usize> = BTreeMap::new();
+    for delivery in deliveries {
+        *edges.entry(delivery).or_default() += 1;
+    }
+
+    let mut output = String::from("flowchart LR\n");
+    for ((sender, receiver, label), count) in edges {
+        let text = match count {
+            1 => label,
+            n => format!("{label} ({n})"),
+        };
+        output.push_str(&format!(
+            "    {}[\"{sender}\"] -->|\"{text}\"| {}[\"{receiver}\"]\n",
+            sanitize_id(sender),
+            sanitize_id(receiver)
+        ));
+    }
+    output
+}
+
+fn sanitize_id(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_are_aggregated() {
+        let deliveries = [
+            ("trader", "exchange", "Order".to_string()),
+            ("trader", "exchange", "Order".to_string()),
+            ("exchange", "risk-engine", "Fill".to_string()),
+        ];
+        assert_eq!(
+            to_mermaid(deliveries),
+            "flowchart LR\n    \
+             exchange[\"exchange\"] -->|\"Fill\"| risk_engine[\"risk-engine\"]\n    \
+             trader[\"trader\"] -->|\"Order (2)\"| exchange[\"exchange\"]\n"
+        );
+    }
+}
@@ -0,0 +1,426 @@
+//! Command-line inspector for recordings written by Maiko's `Recorder`.
+//!
+//! ```text
+//! maiko-inspect stats events.jsonl
+//! maiko-inspect tree 52385318931349188562396404102254519061 events.jsonl
+//! maiko-inspect flow events.1.jsonl events.jsonl > flow.mmd
+//! ```
+//!
+//! The application's event type isn't known here, so events are read as
+//! JSON and labelled by enum variant (see [`event::RawEvent`]).
+
+mod event;
+mod flow;
+mod stats;
+mod table;
+mod throughput;
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use maiko::{
+    EventId,
+    monitoring::Stage,
+    monitors::Record,
+    testing::{Recording, RecordingReader},
+};
+
+use crate::{
+    event::{RawEvent, RecordedTopic},
+    stats::Stats,
+    throughput::Throughput,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+const USAGE: &str = "\
+Inspect recordings written by maiko's Recorder (JSON Lines).
+
+Usage: maiko-inspect <COMMAND> [OPTIONS] <FILE>...
+
+Rotated files of one recording can be given together, oldest first
+(except for diff, which takes one file per recording).
+
+Commands:
+  stats                        Counts per stage, actor, topic and event label
+  throughput [--interval <D>]  New events and handled deliveries over time (default: 1s)
+  tree <EVENT_ID>              Correlation tree of an event
+  sequence <EVENT_ID>          Mermaid sequence diagram of an event's correlation tree
+  flow [--event <EVENT_ID>]    Mermaid flowchart of the observed event flow
+  diff <EXPECTED> <ACTUAL>     Structural differences between two recordings:
+                               correlation trees, labels received per actor and
+                               deliveries per topic
+
+Options:
+  --stage <STAGE>  Stage deliveries are taken from by tree, sequence, flow and diff
+                   (default: handled)
+  -h, --help       Print this help
+
+Event ids are given as printed in recordings (decimal) or as UUIDs.
+Durations take a unit: ns, us, ms, s or m.
+Exits with 1 when diff finds differences and 2 on errors.
+";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Stats,
+    Throughput(Duration),
+    Tree(EventId),
+    Sequence(EventId),
+    Flow(Option<EventId>),
+    Diff,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    stage: Stage,
+    files: Vec<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(args).and_then(|args| run(&args)) {
+        Ok((output, code)) => match io::stdout().lock().write_all(output.as_bytes()) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+                eprintln!("maiko-inspect: {e}");
+                ExitCode::from(2)
+            }
+            _ => code,
+        },
+        Err(e) => {
+            eprintln!("maiko-inspect: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the command, returning its output and exit code.
+fn run(args: &Args) -> Result<(String, ExitCode)> {
+    if args.command == Command::Diff {
+        let expected = read_recording(&args.files[..1], args.stage)?;
+        let actual = read_recording(&args.files[1..], args.stage)?;
+        let diff = expected.flow_snapshot().diff(&actual.flow_snapshot());
+        let code = if diff.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+        return Ok((diff.to_string(), code));
+    }
+    render(args).map(|output| (output, ExitCode::SUCCESS))
+}
+
+fn render(args: &Args) -> Result<String> {
+    match args.command {
+        Command::Stats => {
+            let mut stats = Stats::default();
+            for_each_record(&args.files, |record| stats.add(record))?;
+            Ok(stats.to_string())
+        }
+        Command::Throughput(interval) => {
+            let mut throughput = Throughput::new(interval);
+            for_each_record(&args.files, |record| throughput.add(record))?;
+            Ok(throughput.to_string())
+        }
+        Command::Tree(id) => {
+            let recording = read_recording(&args.files, args.stage)?;
+            ensure_recorded(&recording, id)?;
+            Ok(recording.chain(id).to_string_tree())
+        }
+        Command::Sequence(id) => {
+            let recording = read_recording(&args.files, args.stage)?;
+            ensure_recorded(&recording, id)?;
+            Ok(recording.chain(id).to_mermaid())
+        }
+        Command::Flow(None) => {
+            let recording = read_recording(&args.files, args.stage)?;
+            let entries = recording.events().all_deliveries();
+            Ok(flow::to_mermaid(entries.iter().map(flow::delivery)))
+        }
+        Command::Flow(Some(id)) => {
+            let recording = read_recording(&args.files, args.stage)?;
+            ensure_recorded(&recording, id)?;
+            let chain = recording.chain(id);
+            let ids: HashSet<EventId> = chain.events().all().iter().map(|e| e.id()).collect();
+            let entries = recording.events().all_deliveries();
+            let deliveries = entries.iter().filter(|e| ids.contains(&e.id()));
+            Ok(flow::to_mermaid(deliveries.map(flow::delivery)))
+        }
+        Command::Diff => unreachable!("diff is handled by run"),
+    }
+}
+
+fn for_each_record(files: &[PathBuf], mut f: impl FnMut(&Record<RawEvent>)) -> Result<()> {
+    for file in files {
+        let mut reader = RecordingReader::<RawEvent, RecordedTopic>::open(file)
+            .map_err(|e| file_error(file, e))?;
+        while let Some(record) = reader.next_record().map_err(|e| file_error(file, e))? {
+            f(&record);
+        }
+    }
+    Ok(())
+}
+
+fn read_recording(files: &[PathBuf], stage: Stage) -> Result<Recording<RawEvent, RecordedTopic>> {
+    let mut entries = Vec::new();
+    for file in files {
+        let reader = RecordingReader::<RawEvent, RecordedTopic>::open(file)
+            .map_err(|e| file_error(file, e))?
+            .with_stage(stage)
+            .with_topics(RecordedTopic::from_record);
+        for entry in reader {
+            entries.push(entry.map_err(|e| file_error(file, e))?);
+        }
+    }
+    if entries.is_empty() && is_per_event(files)? {
+        return Err(
+            "the recording has one record per event (RecordMode::PerEvent), \
+                    without receivers; tree, sequence, flow and diff need \
+                    RecordMode::PerDelivery"
+                .into(),
+        );
+    }
+    Ok(entries.into_iter().collect())
+}
+
+/// Whether the recording was made with `RecordMode::PerEvent`: its event
+/// records carry no receiver, so they can't form deliveries.
+fn is_per_event(files: &[PathBuf]) -> Result<bool> {
+    let mut per_event = false;
+    for_each_record(files, |record| {
+        per_event |= record.envelope().is_some() && record.actor().is_none();
+    })?;
+    Ok(per_event)
+}
+
+fn ensure_recorded(recording: &Recording<RawEvent, RecordedTopic>, id: EventId) -> Result<()> {
+    if recording.event(id).was_delivered() {
+        Ok(())
+    } else {
+        Err(format!("event {id} not found in the recording").into())
+    }
+}
+
+fn file_error(file: &Path, e: io::Error) -> Box<dyn Error> {
+    format!("{}: {e}", file.display()).into()
+}
+
+fn parse_args(args: Vec<String>) -> Result<Args> {
+    let mut args = args.into_iter();
+    let command = args.next().unwrap_or_default();
+
+    let mut stage = Stage::Handled;
+    let mut interval = Duration::from_secs(1);
+    let mut event = None;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--stage" => stage = parse_stage(&value()?)?,
+            "--interval" if command == "throughput" => interval = parse_duration(&value()?)?,
+            "--event" if command == "flow" => event = Some(parse_event_id(&value()?)?),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}").into()),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = match command.as_str() {
+        "stats" => Command::Stats,
+        "throughput" => Command::Throughput(interval),
+        "tree" | "sequence" => {
+            let id = positional
+                .next()
+                .ok_or_else(|| format!("{command} requires an event id"))?;
+            let id = parse_event_id(&id)?;
+            if command == "tree" {
+                Command::Tree(id)
+            } else {
+                Command::Sequence(id)
+            }
+        }
+        "flow" => Command::Flow(event),
+        "diff" => Command::Diff,
+        _ => return Err(format!("unknown command '{command}' (see --help)").into()),
+    };
+
+    let files: Vec<PathBuf> = positional.map(PathBuf::from).collect();
+    if files.is_empty() {
+        return Err("no recording files given".into());
+    }
+    if command == Command::Diff && files.len() != 2 {
+        return Err("diff takes two recording files".into());
+    }
+    Ok(Args {
+        command,
+        stage,
+        files,
+    })
+}
+
+fn parse_stage(s: &str) -> Result<Stage> {
+    Stage::ALL
+        .into_iter()
+        .find(|stage| stage.to_string() == s)
+        .ok_or_else(|| format!("unknown stage '{s}'").into())
+}
+
+/// Parse an event id, either decimal or a UUID (with or without dashes).
+fn parse_event_id(s: &str) -> Result<EventId> {
+    if let Ok(id) = s.parse::<EventId>() {
+        return Ok(id);
+    }
+    let hex: String = s.chars().filter(|c| *c != '-').collect();
+    if hex.len() == 32 {
+        if let Ok(id) = EventId::from_str_radix(&hex, 16) {
+            return Ok(id);
+        }
+    }
+    Err(format!("invalid event id '{s}'").into())
+}
+
+fn parse_duration(s: &str) -> Result<Duration> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("duration '{s}' needs a unit"))?;
+    let (value, unit) = s.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration '{s}'"))?;
+    let duration = match unit {
+        "ns" => Duration::from_nanos(value),
+        "us" => Duration::from_micros(value),
+        "ms" => Duration::from_millis(value),
+        "s" => Duration::from_secs(value),
+        "m" => Duration::from_secs(value * 60),
+        _ => return Err(format!("unknown duration unit '{unit}'").into()),
+    };
+    if duration.is_zero() {
+        return Err("duration must not be zero".into());
+    }
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use maiko::{
+        ActorId, Envelope,
+        monitoring::Monitor,
+        monitors::{RecordMode, Recorder, RecorderConfig},
+    };
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn args(line: &str) -> Result<Args> {
+        parse_args(line.split_whitespace().map(String::from).collect())
+    }
+
+    #[test]
+    fn parses_commands() {
+        let parsed = args("throughput --interval 250ms a.jsonl b.jsonl").unwrap();
+        assert_eq!(
+            parsed.command,
+            Command::Throughput(Duration::from_millis(250))
+        );
+        assert_eq!(
+            parsed.files,
+            [PathBuf::from("a.jsonl"), PathBuf::from("b.jsonl")]
+        );
+
+        let parsed = args("tree 42 --stage dispatched a.jsonl").unwrap();
+        assert_eq!(parsed.command, Command::Tree(42));
+        assert_eq!(parsed.stage, Stage::Dispatched);
+
+        let parsed = args("flow --event 0000000000000000000000000000002a a.jsonl").unwrap();
+        assert_eq!(parsed.command, Command::Flow(Some(42)));
+
+        assert_eq!(args("diff a.jsonl b.jsonl").unwrap().command, Command::Diff);
+        assert!(args("diff a.jsonl").is_err());
+        assert!(args("tree a.jsonl").is_err());
+        assert!(args("stats").is_err());
+        assert!(args("stats --interval 1s a.jsonl").is_err());
+        assert!(args("stats --stage nope a.jsonl").is_err());
+    }
+
+    #[test]
+    fn parses_event_ids() {
+        let uuid = "936da01f-9abd-4d9d-80c7-02af85c822a8";
+        assert_eq!(
+            parse_event_id(uuid).unwrap(),
+            0x936da01f9abd4d9d80c702af85c822a8
+        );
+        assert!(parse_event_id("xyz").is_err());
+    }
+
+    fn record(path: &Path, mode: RecordMode) -> (EventId, EventId) {
+        let recorder = Recorder::open(path, RecorderConfig::default().with_mode(mode)).unwrap();
+        let monitor: &dyn Monitor<RawEvent, RecordedTopic> = &recorder;
+        let topic = RecordedTopic::from_record(&RawEvent(Value::Null), Some("Orders"));
+        let order = Envelope::new(RawEvent(json!({"Order": 1})), ActorId::new("trader".into()));
+        let fill = Envelope::with_correlation(
+            RawEvent(json!("Fill")),
+            ActorId::new("exchange".into()),
+            order.id(),
+        );
+        for (envelope, receiver) in [(&order, "exchange"), (&fill, "trader")] {
+            let receiver = ActorId::new(receiver.into());
+            monitor.on_event_dispatched(envelope, &topic, &receiver);
+            monitor.on_event_handled(envelope, &topic, &receiver);
+        }
+        (order.id(), fill.id())
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("maiko-inspect-{}-{name}.jsonl", std::process::id()))
+    }
+
+    #[test]
+    fn runs_on_a_recorder_file() {
+        let path = temp_file("per-delivery");
+        let (order, _) = record(&path, RecordMode::PerDelivery);
+        let run_on = |line: &str| run(&args(&format!("{line} {}", path.display())).unwrap());
+
+        let (stats, code) = run_on("stats").unwrap();
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert!(stats.contains("trader"), "{stats}");
+
+        let (tree, _) = run_on(&format!("tree {order}")).unwrap();
+        assert!(tree.contains("Order") && tree.contains("Fill"), "{tree}");
+
+        let (flow, _) = run_on("flow").unwrap();
+        assert!(
+            flow.contains("trader") && flow.contains("exchange"),
+            "{flow}"
+        );
+
+        let (_, code) = run(&args(&format!("diff {0} {0}", path.display())).unwrap()).unwrap();
+        assert_eq!(code, ExitCode::SUCCESS);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn per_event_recording_is_reported() {
+        let path = temp_file("per-event");
+        let (order, _) = record(&path, RecordMode::PerEvent);
+        let line = format!("tree {order} {}", path.display());
+        let err = run(&args(&line).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("PerEvent"), "{err}");
+
+        let (stats, _) = run(&args(&format!("stats {}", path.display())).unwrap()).unwrap();
+        assert!(stats.contains("trader"), "{stats}");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    time::Duration,
+};
+
+use maiko::{EventId, Label, monitoring::Stage, monitors::Record};
+
+use crate::{event::RawEvent, table::Table};
+
+/// Counts per stage, actor, topic and event label of a recording.
+#[derive(Default)]
+pub struct Stats {
+    records: u64,
+    missing: u64,
+    last_seq: Option<u64>,
+    span: Option<(u64, u64)>,
+    stages: BTreeMap<Stage, u64>,
+    events: HashSet<EventId>,
+    actors: BTreeMap<String, ActorStats>,
+    topics: BTreeMap<String, TopicStats>,
+    labels: BTreeMap<String, LabelStats>,
+    /// Delivery time of events not yet handled, by event and receiver.
+    delivered_at: HashMap<(EventId, String), u64>,
+}
+
+#[derive(Default)]
+struct ActorStats {
+    sent: u64,
+    received: u64,
+    errors: u64,
+    overflows: u64,
+    handling: Duration,
+    handled: u64,
+}
+
+#[derive(Default)]
+struct TopicStats {
+    deliveries: u64,
+    overflows: u64,
+}
+
+#[derive(Default)]
+struct LabelStats {
+    events: u64,
+    deliveries: u64,
+}
+
+impl Stats {
+    pub fn add(&mut self, record: &Record<RawEvent>) {
+        self.records += 1;
+        if let Some(last) = self.last_seq.filter(|last| record.seq() > last + 1) {
+            self.missing += record.seq() - last - 1;
+        }
+        self.last_seq = Some(record.seq());
+        self.span = Some(match self.span {
+            Some((start, end)) => (start.min(record.timestamp()), end.max(record.timestamp())),
+            None => (record.timestamp(), record.timestamp()),
+        });
+        *self.stages.entry(record.stage()).or_default() += 1;
+
+        if let Some(envelope) = record.envelope() {
+            if self.events.insert(envelope.id()) {
+                self.actor(envelope.meta().actor_name()).sent += 1;
+                self.label(envelope.event()).events += 1;
+            }
+        }
+
+        let actor = record.actor().map(|actor| actor.name());
+        match (record.stage(), actor) {
+            (Stage::Dispatched, _) => {
+                if let Some(topic) = record.topic() {
+                    self.topic(topic).deliveries += 1;
+                }
+            }
+            (Stage::Delivered, Some(actor)) => {
+                if let Some(envelope) = record.envelope() {
+                    self.delivered_at
+                        .insert((envelope.id(), actor.to_string()), record.timestamp());
+                }
+            }
+            (Stage::Handled, Some(actor)) => {
+                let delivered_at = record.envelope().and_then(|envelope| {
+                    if let Some(label) = self.labels.get_mut(envelope.event().label().as_ref()) {
+                        label.deliveries += 1;
+                    }
+                    self.delivered_at
+                        .remove(&(envelope.id(), actor.to_string()))
+                });
+                let stats = self.actor(actor);
+                stats.received += 1;
+                if let Some(delivered_at) = delivered_at {
+                    stats.handling +=
+                        Duration::from_nanos(record.timestamp().saturating_sub(delivered_at));
+                    stats.handled += 1;
+                }
+            }
+            (Stage::Overflow, Some(actor)) => {
+                self.actor(actor).overflows += 1;
+                if let Some(topic) = record.topic() {
+                    self.topic(topic).overflows += 1;
+                }
+            }
+            (Stage::Error, Some(actor)) => self.actor(actor).errors += 1,
+            _ => {}
+        }
+    }
+
+    fn actor(&mut self, name: &str) -> &mut ActorStats {
+        self.actors.entry(name.to_string()).or_default()
+    }
+
+    fn topic(&mut self, label: &str) -> &mut TopicStats {
+        self.topics.entry(label.to_string()).or_default()
+    }
+
+    fn label(&mut self, event: &RawEvent) -> &mut LabelStats {
+        self.labels.entry(event.label().into_owned()).or_default()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Records:  {}", self.records)?;
+        if self.missing > 0 {
+            write!(f, " ({} missing)", self.missing)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Events:   {}", self.events.len())?;
+        if let Some((start, end)) = self.span {
+            writeln!(f, "Duration: {:.3?}", Duration::from_nanos(end - start))?;
+        }
+
+        let mut stages = Table::new(&["STAGE", "RECORDS"]);
+        for (stage, count) in &self.stages {
+            stages.row(vec![stage.to_string(), count.to_string()]);
+        }
+        section(f, "Stages", &stages)?;
+
+        let mut actors = Table::new(&[
+            "ACTOR",
+            "SENT",
+            "RECEIVED",
+            "ERRORS",
+            "OVERFLOWS",
+            "AVG HANDLING",
+        ]);
+        for (name, stats) in &self.actors {
+            let handling = match stats.handled {
+                0 => "-".to_string(),
+                n => {
+                    let avg = Duration::from_nanos((stats.handling.as_nanos() / n as u128) as u64);
+                    format!("{avg:.1?}")
+                }
+            };
+            actors.row(vec![
+                name.clone(),
+                stats.sent.to_string(),
+                stats.received.to_string(),
+                stats.errors.to_string(),
+                stats.overflows.to_string(),
+                handling,
+            ]);
+        }
+        section(f, "Actors", &actors)?;
+
+        let mut topics = Table::new(&["TOPIC", "DELIVERIES", "OVERFLOWS"]);
+        for (name, stats) in &self.topics {
+            topics.row(vec![
+                name.clone(),
+                stats.deliveries.to_string(),
+                stats.overflows.to_string(),
+            ]);
+        }
+        section(f, "Topics", &topics)?;
+
+        let mut labels = Table::new(&["LABEL", "EVENTS", "HANDLED"]);
+        for (name, stats) in &self.labels {
+            labels.row(vec![
+                name.clone(),
+                stats.events.to_string(),
+                stats.deliveries.to_string(),
+            ]);
+        }
+        section(f, "Labels", &labels)
+    }
+}
+
+fn section(f: &mut fmt::Formatter<'_>, title: &str, table: &Table) -> fmt::Result {
+    if table.is_empty() {
+        return Ok(());
+    }
+    write!(f, "\n{title}\n{table}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        seq: u64,
+        stage: &str,
+        timestamp: u64,
+        actor: &str,
+        envelope: &str,
+    ) -> Record<RawEvent> {
+        serde_json::from_str(&format!(
+            r#"{{"seq":{seq},"stage":"{stage}","timestamp":{timestamp},"actor":"{actor}","topic":"default","envelope":{envelope},"detail":null}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn counts_deliveries_and_handling_time() {
+        let order = r#"{"event":{"Order":1},"meta":{"id":1,"timestamp":0,"actor_id":"trader","correlation_id":null}}"#;
+        let mut stats = Stats::default();
+        for record in [
+            record(0, "dispatched", 1_000, "exchange", order),
+            record(1, "dispatched", 1_000, "audit", order),
+            record(2, "delivered", 2_000, "exchange", order),
+            record(3, "handled", 6_000, "exchange", order),
+            // seq 4 was dropped by the recorder
+            record(5, "handled", 7_000, "audit", order),
+        ] {
+            stats.add(&record);
+        }
+
+        assert_eq!(stats.records, 5);
+        assert_eq!(stats.missing, 1);
+        assert_eq!(stats.events.len(), 1);
+        assert_eq!(stats.actors["trader"].sent, 1);
+        assert_eq!(stats.actors["exchange"].received, 1);
+        assert_eq!(stats.actors["exchange"].handling, Duration::from_micros(4));
+        assert_eq!(stats.actors["audit"].handled, 0);
+        assert_eq!(stats.topics["default"].deliveries, 2);
+        assert_eq!(stats.labels["Order"].deliveries, 2);
+
+        let report = stats.to_string();
+        assert!(report.contains("Records:  5 (1 missing)"));
+        assert!(report.contains("4.0µs"));
+    }
+}
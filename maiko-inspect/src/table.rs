@@ -0,0 +1,71 @@
+use std::fmt::{self, Write};
+
+/// A plain text table: the first column left-aligned, the others
+/// right-aligned.
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        debug_assert_eq!(cells.len(), self.headers.len());
+        self.rows.push(cells);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|i| {
+                self.rows
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([self.headers[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let headers = self.headers.iter().map(|h| h.to_string()).collect();
+        for cells in std::iter::once(&headers).chain(&self.rows) {
+            let mut line = String::from(" ");
+            for (i, cell) in cells.iter().enumerate() {
+                if i == 0 {
+                    write!(line, " {cell:<width$}", width = widths[i])?;
+                } else {
+                    write!(line, "  {cell:>width$}", width = widths[i])?;
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_are_aligned() {
+        let mut table = Table::new(&["ACTOR", "SENT"]);
+        table.row(vec!["exchange".into(), "3".into()]);
+        table.row(vec!["risk".into(), "120".into()]);
+        assert_eq!(
+            table.to_string(),
+            "  ACTOR     SENT\n  exchange     3\n  risk       120\n"
+        );
+    }
+}
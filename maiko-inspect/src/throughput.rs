@@ -0,0 +1,119 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    time::Duration,
+};
+
+use maiko::{EventId, monitoring::Stage, monitors::Record};
+
+use crate::{event::RawEvent, table::Table};
+
+const BAR_WIDTH: u64 = 40;
+
+/// New events and handled deliveries per time interval.
+///
+/// Only intervals with activity are kept and printed, so a tiny interval
+/// over a long recording costs no more than the records themselves.
+pub struct Throughput {
+    interval: Duration,
+    start: Option<u64>,
+    events: HashSet<EventId>,
+    /// (events, deliveries) by interval index since `start`.
+    buckets: BTreeMap<u64, (u64, u64)>,
+}
+
+impl Throughput {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            start: None,
+            events: HashSet::new(),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, record: &Record<RawEvent>) {
+        let start = *self.start.get_or_insert(record.timestamp());
+        let Some(envelope) = record.envelope() else {
+            return;
+        };
+        let new_event = record.stage() == Stage::Dispatched && self.events.insert(envelope.id());
+        let handled = record.stage() == Stage::Handled;
+        if !new_event && !handled {
+            return;
+        }
+
+        let elapsed = record.timestamp().saturating_sub(start) as u128;
+        let index = (elapsed / self.interval.as_nanos().max(1)) as u64;
+        let bucket = self.buckets.entry(index).or_default();
+        bucket.0 += new_event as u64;
+        bucket.1 += handled as u64;
+    }
+}
+
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let peak = self
+            .buckets
+            .values()
+            .map(|(events, _)| *events)
+            .max()
+            .unwrap_or(0);
+        let mut table = Table::new(&["TIME", "EVENTS", "HANDLED", ""]);
+        for (i, (events, handled)) in &self.buckets {
+            let bar = match peak {
+                0 => 0,
+                peak => (events * BAR_WIDTH).div_ceil(peak),
+            };
+            table.row(vec![
+                format!("+{:.3}s", self.interval.as_secs_f64() * *i as f64),
+                events.to_string(),
+                handled.to_string(),
+                format!(
+                    "{:<width$}",
+                    "#".repeat(bar as usize),
+                    width = BAR_WIDTH as usize
+                ),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seq: u64, stage: &str, timestamp: u64, id: u128) -> Record<RawEvent> {
+        serde_json::from_str(&format!(
+            r#"{{"seq":{seq},"stage":"{stage}","timestamp":{timestamp},"actor":"a","topic":null,"envelope":{{"event":"Tick","meta":{{"id":{id},"timestamp":0,"actor_id":"b","correlation_id":null}}}},"detail":null}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn buckets_by_interval() {
+        let mut throughput = Throughput::new(Duration::from_millis(1));
+        for record in [
+            record(0, "dispatched", 0, 1),
+            record(1, "dispatched", 10, 1),
+            record(2, "handled", 500_000, 1),
+            record(3, "dispatched", 2_100_000, 2),
+        ] {
+            throughput.add(&record);
+        }
+        assert_eq!(
+            throughput.buckets,
+            BTreeMap::from([(0, (1, 1)), (2, (1, 0))])
+        );
+    }
+
+    #[test]
+    fn tiny_interval_stays_sparse() {
+        let mut throughput = Throughput::new(Duration::from_nanos(1));
+        throughput.add(&record(0, "dispatched", 0, 1));
+        throughput.add(&record(1, "handled", 3_600_000_000_000, 1));
+        assert_eq!(throughput.buckets.len(), 2);
+        assert!(throughput.to_string().contains("+3600.000s"));
+    }
+}
@@ -4,7 +4,7 @@ use tokio::sync::mpsc::Receiver;
 
 use crate::{
     Actor, ActorConfig, ActorId, Context, Envelope, Event, Result, Subscribe, Supervisor, Topic,
-    internal::Subscription,
+    internal::{EventFilter, Routing, Subscription},
 };
 
 /// Builder for registering an actor with custom configuration.
@@ -13,8 +13,8 @@ use crate::{
 /// per-actor settings such as channel capacity or when you want to separate
 /// actor construction from topic subscription.
 ///
-/// Defaults to no topic subscriptions and channel capacity inherited from the
-/// global [`Config`](crate::Config).
+/// Defaults to no topic subscriptions, undeclared publications and channel
+/// capacity inherited from the global [`Config`](crate::Config).
 ///
 /// # Examples
 ///
@@ -25,6 +25,12 @@ use crate::{
 ///     .channel_capacity(512)
 ///     .build()?;
 ///
+/// // Declare what the actor publishes, checked by `Supervisor::validate`
+/// sup.build_actor("parser", |ctx| Parser::new(ctx))
+///     .topics(&[Topic::Raw])
+///     .publishes(&[Topic::Data])
+///     .build()?;
+///
 /// // Replace the entire actor config
 /// sup.build_actor("fast", |ctx| Fast::new(ctx))
 ///     .topics(Subscribe::all())
@@ -39,6 +45,8 @@ pub struct ActorBuilder<'a, E: Event, T: Topic<E>, A: Actor<Event = E>> {
     ctx: Context<A::Event>,
     config: ActorConfig,
     topics: Subscription<T>,
+    publications: Option<Subscription<T>>,
+    filter: Option<EventFilter<E>>,
     receiver: Receiver<Arc<Envelope<E>>>,
 }
 
@@ -56,10 +64,18 @@ impl<'a, E: Event, T: Topic<E>, A: Actor<Event = E>> ActorBuilder<'a, E, T, A> {
             actor,
             config,
             topics: Subscription::None,
+            publications: None,
+            filter: None,
             receiver,
         }
     }
 
+    /// Only dispatch events on the subscribed topics that pass `filter`.
+    pub(crate) fn filter(mut self, filter: EventFilter<E>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Set the topics this actor subscribes to.
     ///
     /// Accepts anything that converts to [`Subscribe`]: a topic slice,
@@ -72,6 +88,22 @@ impl<'a, E: Event, T: Topic<E>, A: Actor<Event = E>> ActorBuilder<'a, E, T, A> {
         self
     }
 
+    /// Declare the topics this actor publishes to.
+    ///
+    /// Accepts the same forms as [`topics()`](Self::topics):
+    /// [`Subscribe::all()`] for an actor that may publish anything and
+    /// [`Subscribe::none()`] for a pure consumer. Declarations feed
+    /// [`Supervisor::validate`], and the broker checks every event the actor
+    /// sends against them, applying the configured
+    /// [`UndeclaredTopicPolicy`](crate::UndeclaredTopicPolicy).
+    pub fn publishes<S>(mut self, topics: S) -> Self
+    where
+        S: Into<Subscribe<E, T>>,
+    {
+        self.publications = Some(topics.into().0);
+        self
+    }
+
     /// Replace the entire [`ActorConfig`] for this actor.
     pub fn config<C>(mut self, config: C) -> Self
     where
@@ -112,12 +144,12 @@ impl<'a, E: Event, T: Topic<E>, A: Actor<Event = E>> ActorBuilder<'a, E, T, A> {
 
     /// Register the actor with the supervisor and return its [`ActorId`].
     pub fn build(self) -> Result<ActorId> {
-        self.supervisor.register_actor(
-            self.ctx,
-            self.actor,
-            self.topics,
-            self.config,
-            self.receiver,
-        )
+        let routing = Routing {
+            topics: self.topics,
+            filter: self.filter,
+            publications: self.publications,
+        };
+        self.supervisor
+            .register_actor(self.ctx, self.actor, routing, self.config, self.receiver)
     }
 }
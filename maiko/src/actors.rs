@@ -0,0 +1,17 @@
+//! Ready-made actors for common integration tasks.
+//!
+//! - [`JsonLinesSource`] - reads JSON Lines from any `AsyncRead` (e.g. stdin)
+//!   and publishes the events.
+//! - [`JsonLinesSink`] - writes received events as JSON Lines to any
+//!   `AsyncWrite` (e.g. stdout).
+//!
+//! Together they make a Maiko system usable in shell pipelines:
+//!
+//! ```rust,ignore
+//! sup.add_actor("stdin", |ctx| JsonLinesSource::new(ctx, tokio::io::stdin()), Subscribe::none())?;
+//! sup.add_actor("stdout", |_| JsonLinesSink::new(tokio::io::stdout()), &[AppTopic::Output])?;
+//! ```
+
+mod json_lines;
+
+pub use json_lines::{JsonLinesSink, JsonLinesSource};
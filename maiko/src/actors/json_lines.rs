@@ -0,0 +1,284 @@
+use std::{fmt, marker::PhantomData};
+
+use futures_util::StreamExt;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
+
+use crate::{Actor, Context, Envelope, Error, Event, Result, StepAction, codec::MAX_RECORD_LEN};
+
+/// Source actor publishing events read as JSON Lines from an `AsyncRead`.
+///
+/// By default every line is a serialized event `E`, published with this
+/// actor as the sender. With [`with_envelopes`](Self::with_envelopes) each
+/// line is a full [`Envelope<E>`] and is published with its original
+/// metadata, e.g. to replay the output of a [`JsonLinesSink`].
+///
+/// Empty lines are skipped. Lines that fail to parse are reported through
+/// `on_error` (and so to monitors) without stopping the actor. At the end
+/// of input the actor stops stepping but keeps running.
+///
+/// Register it with `Subscribe::none()`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sup.add_actor("stdin", |ctx| JsonLinesSource::new(ctx, tokio::io::stdin()), Subscribe::none())?;
+/// ```
+pub struct JsonLinesSource<R, E> {
+    ctx: Context<E>,
+    lines: FramedRead<R, LinesCodec>,
+    envelopes: bool,
+    line_no: usize,
+    pending: Option<Envelope<E>>,
+}
+
+impl<R, E> JsonLinesSource<R, E>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    E: Event + DeserializeOwned,
+{
+    /// Create a source reading events from `reader`.
+    pub fn new(ctx: Context<E>, reader: R) -> Self {
+        Self {
+            ctx,
+            lines: FramedRead::new(reader, LinesCodec::new_with_max_length(MAX_RECORD_LEN)),
+            envelopes: false,
+            line_no: 0,
+            pending: None,
+        }
+    }
+
+    /// Read full envelopes instead of bare events.
+    pub fn with_envelopes(mut self) -> Self {
+        self.envelopes = true;
+        self
+    }
+
+    fn parse(&self, line: &str) -> Result<Envelope<E>> {
+        let parsed = if self.envelopes {
+            serde_json::from_str(line)
+        } else {
+            serde_json::from_str(line)
+                .map(|event| Envelope::new(event, self.ctx.actor_id().clone()))
+        };
+        parsed.map_err(|e| Error::Codec(format!("line {}: {e}", self.line_no).into()))
+    }
+}
+
+impl<R, E> Actor for JsonLinesSource<R, E>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    E: Event + DeserializeOwned,
+{
+    type Event = E;
+
+    async fn step(&mut self) -> Result<StepAction> {
+        if self.pending.is_none() {
+            let line = match self.lines.next().await {
+                Some(Ok(line)) => line,
+                Some(Err(LinesCodecError::Io(e))) => return Err(e.into()),
+                Some(Err(e)) => {
+                    self.line_no += 1;
+                    return Err(Error::Codec(format!("line {}: {e}", self.line_no).into()));
+                }
+                None => return Ok(StepAction::Never),
+            };
+            self.line_no += 1;
+            if line.trim().is_empty() {
+                return Ok(StepAction::Continue);
+            }
+            self.pending = Some(self.parse(&line)?);
+        }
+        // Keep the envelope until the broker accepts it, so a cancelled step doesn't lose it.
+        if let Some(envelope) = &self.pending {
+            self.ctx.send_envelope(envelope.clone()).await?;
+            self.pending = None;
+        }
+        Ok(StepAction::Continue)
+    }
+
+    fn on_error(&self, error: Error) -> Result<()> {
+        match error {
+            Error::Codec(msg) => {
+                tracing::warn!(actor=%self.ctx.actor_name(), error=%msg, "skipping malformed JSON line");
+                Ok(())
+            }
+            e => Err(e),
+        }
+    }
+}
+
+impl<R, E> fmt::Debug for JsonLinesSource<R, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesSource")
+            .field("actor_id", self.ctx.actor_id())
+            .field("envelopes", &self.envelopes)
+            .field("line_no", &self.line_no)
+            .finish()
+    }
+}
+
+/// Sink actor writing received events as JSON Lines to an `AsyncWrite`.
+///
+/// By default only the event is written; with
+/// [`with_envelopes`](Self::with_envelopes) the whole [`Envelope<E>`],
+/// including metadata, is. Each line is flushed right away so the output
+/// can be consumed by pipelines as it's produced.
+///
+/// Events that fail to serialize are reported through `on_error` without
+/// stopping the actor. Write errors (e.g. a closed pipe) stop it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sup.add_actor("stdout", |_| JsonLinesSink::new(tokio::io::stdout()), &[AppTopic::Output])?;
+/// ```
+pub struct JsonLinesSink<W, E> {
+    writer: W,
+    envelopes: bool,
+    buf: Vec<u8>,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<W, E> JsonLinesSink<W, E>
+where
+    W: AsyncWrite + Unpin + Send + 'static,
+    E: Event + Serialize,
+{
+    /// Create a sink writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            envelopes: false,
+            buf: Vec::new(),
+            _event: PhantomData,
+        }
+    }
+
+    /// Write full envelopes instead of bare events.
+    pub fn with_envelopes(mut self) -> Self {
+        self.envelopes = true;
+        self
+    }
+}
+
+impl<W, E> Actor for JsonLinesSink<W, E>
+where
+    W: AsyncWrite + Unpin + Send + 'static,
+    E: Event + Serialize,
+{
+    type Event = E;
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        self.buf.clear();
+        let res = if self.envelopes {
+            serde_json::to_writer(&mut self.buf, envelope)
+        } else {
+            serde_json::to_writer(&mut self.buf, envelope.event())
+        };
+        res.map_err(|e| Error::Codec(format!("event {}: {e}", envelope.id()).into()))?;
+        self.buf.push(b'\n');
+        self.writer.write_all(&self.buf).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    fn on_error(&self, error: Error) -> Result<()> {
+        match error {
+            Error::Codec(msg) => {
+                tracing::warn!(error=%msg, "skipping event that can't be serialized");
+                Ok(())
+            }
+            e => Err(e),
+        }
+    }
+}
+
+impl<W, E> fmt::Debug for JsonLinesSink<W, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesSink")
+            .field("envelopes", &self.envelopes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use serde::Deserialize;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic, Subscribe, Supervisor};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum TestEvent {
+        Data(u32),
+    }
+    impl Event for TestEvent {}
+
+    async fn read_line<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> String {
+        let mut line = String::new();
+        tokio::time::timeout(Duration::from_secs(1), reader.read_line(&mut line))
+            .await
+            .expect("line not written")
+            .unwrap();
+        line
+    }
+
+    #[tokio::test]
+    async fn source_skips_malformed_lines_and_sink_writes_events() {
+        let input: &'static [u8] = b"{\"Data\":1}\nnot json\n\n{\"Data\":2}\n";
+        let (writer, reader) = tokio::io::duplex(1024);
+        let mut reader = BufReader::new(reader);
+
+        let mut sup = Supervisor::<TestEvent>::default();
+        sup.add_actor(
+            "source",
+            |ctx| JsonLinesSource::new(ctx, input),
+            Subscribe::none(),
+        )
+        .unwrap();
+        sup.add_actor("sink", |_| JsonLinesSink::new(writer), &[DefaultTopic])
+            .unwrap();
+        sup.start().await.unwrap();
+
+        assert_eq!(read_line(&mut reader).await, "{\"Data\":1}\n");
+        assert_eq!(read_line(&mut reader).await, "{\"Data\":2}\n");
+        sup.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn envelopes_roundtrip_with_metadata() {
+        let original =
+            Envelope::with_correlation(TestEvent::Data(3), ActorId::new(Arc::from("remote")), 11);
+        let mut input = serde_json::to_vec(&original).unwrap();
+        input.push(b'\n');
+        let (writer, reader) = tokio::io::duplex(1024);
+        let mut reader = BufReader::new(reader);
+
+        let mut sup = Supervisor::<TestEvent>::default();
+        sup.add_actor(
+            "source",
+            |ctx| JsonLinesSource::new(ctx, std::io::Cursor::new(input)).with_envelopes(),
+            Subscribe::none(),
+        )
+        .unwrap();
+        sup.add_actor(
+            "sink",
+            |_| JsonLinesSink::new(writer).with_envelopes(),
+            &[DefaultTopic],
+        )
+        .unwrap();
+        sup.start().await.unwrap();
+
+        let line = read_line(&mut reader).await;
+        sup.stop().await.unwrap();
+
+        let written: Envelope<TestEvent> = serde_json::from_str(&line).unwrap();
+        assert_eq!(written.meta(), original.meta());
+        assert_eq!(written.event(), original.event());
+    }
+}
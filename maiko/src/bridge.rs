@@ -0,0 +1,282 @@
+//! Bridge actors connecting a Maiko system to other processes.
+//!
+//! A bridge is a regular actor: it exports the events it receives to remote
+//! peers and imports events coming from them into the local broker. Envelopes
+//! travel as length-delimited frames (a big-endian `u32` length followed by
+//! the serialized [`Envelope`]), so both sides need the same event type and
+//! [`Codec`] ([`Json`](crate::codec::Json) by default, see `with_codec`).
+//!
+//! Imported envelopes keep their id, timestamp and correlation id, so event
+//! chains can be followed across process boundaries. They are published as
+//! sent by the bridge actor, so a remote actor never passes for a local one
+//! with the same name; the remote sender is kept in
+//! [`Meta::origin`](crate::Meta::origin). A bridge never exports an event it
+//! has imported itself, which prevents echo loops between peers.
+//!
+//! Available transports:
+//!
+//! - [`unix`] - [`UnixServer`] and [`UnixClient`] over Unix domain sockets.
+//! - [`tcp`] - [`TcpBridge`] between processes on different hosts, with a
+//!   versioned handshake, credit-based flow control, heartbeats and replay
+//!   of unacknowledged envelopes after reconnecting.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // daemon
+//! sup.add_actor("ipc", |ctx| UnixServer::new(ctx, "/tmp/app.sock"), &[AppTopic::Status])?;
+//!
+//! // tool
+//! sup.add_actor("ipc", |ctx| UnixClient::new(ctx, "/tmp/app.sock"), &[AppTopic::Command])?;
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    io,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::mpsc::{Receiver, Sender},
+};
+use tokio_util::{
+    bytes::Bytes,
+    codec::{FramedRead, FramedWrite, LengthDelimitedCodec},
+    sync::CancellationToken,
+};
+
+use crate::{
+    Context, Envelope, Event, EventId, Result, StepAction, Topic,
+    codec::{Codec, MAX_RECORD_LEN},
+};
+
+pub mod tcp;
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub mod unix;
+
+pub use tcp::TcpBridge;
+#[cfg(unix)]
+pub use unix::{UnixClient, UnixServer};
+
+/// How many imported event ids a bridge remembers for echo suppression.
+const IMPORTED_IDS_CAPACITY: usize = 4096;
+
+/// Capacity of the channels between connection tasks and the bridge actor.
+pub(crate) const CHANNEL_CAPACITY: usize = 128;
+
+/// Predicate deciding which events a bridge exports.
+pub(crate) type ExportFilter<E> = Box<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// A serialized message, without the length prefix added by [`frame_codec`].
+pub(crate) type Frame = Bytes;
+
+/// Serialize a message (usually an envelope) into a frame.
+pub(crate) fn encode<C: Codec, M: Serialize>(codec: &C, message: &M) -> io::Result<Frame> {
+    let mut buf = Vec::new();
+    codec.encode(message, &mut buf)?;
+    if buf.len() > MAX_RECORD_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds the limit", buf.len()),
+        ));
+    }
+    Ok(buf.into())
+}
+
+/// Framing used on every bridge connection: a big-endian `u32` length
+/// followed by the payload, rejecting frames over [`MAX_RECORD_LEN`].
+pub(crate) fn frame_codec() -> LengthDelimitedCodec {
+    LengthDelimitedCodec::builder()
+        .max_frame_length(MAX_RECORD_LEN)
+        .new_codec()
+}
+
+/// Pump frames in both directions until the connection ends or is cancelled.
+///
+/// Outgoing frames are taken from `outbound`, incoming ones are decoded and
+/// passed to `inbound`. Frames that fail to decode are logged and skipped.
+pub(crate) async fn serve_connection<S, E, C>(
+    codec: &C,
+    stream: S,
+    outbound: &mut Receiver<Frame>,
+    inbound: &Sender<Envelope<E>>,
+    cancel: &CancellationToken,
+) where
+    S: AsyncRead + AsyncWrite,
+    E: DeserializeOwned,
+    C: Codec,
+{
+    let (reader, writer) = tokio::io::split(stream);
+    let mut reader = FramedRead::new(reader, frame_codec());
+    let mut writer = FramedWrite::new(writer, frame_codec());
+
+    let read = async {
+        while let Some(frame) = reader.next().await {
+            match frame {
+                Ok(buf) => match codec.decode::<Envelope<E>>(&buf) {
+                    Ok(envelope) => {
+                        if inbound.send(envelope).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => tracing::warn!(error=%e, "bridge failed to decode frame"),
+                },
+                Err(e) => {
+                    tracing::warn!(error=%e, "bridge read failed");
+                    break;
+                }
+            }
+        }
+    };
+
+    let write = async {
+        while let Some(frame) = outbound.recv().await {
+            if let Err(e) = writer.send(frame).await {
+                tracing::warn!(error=%e, "bridge write failed");
+                break;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = read => {},
+        _ = write => {},
+        _ = cancel.cancelled() => {},
+    }
+}
+
+/// State shared by all bridge actors: export filter, import queue and echo suppression.
+pub(crate) struct BridgeCore<E> {
+    pub ctx: Context<E>,
+    pub filter: Option<ExportFilter<E>>,
+    pub inbound: Receiver<Envelope<E>>,
+    pub inbound_tx: Sender<Envelope<E>>,
+    pub cancel: CancellationToken,
+    pending: Option<Envelope<E>>,
+    imported: HashSet<EventId>,
+    imported_order: VecDeque<EventId>,
+}
+
+impl<E: Event> BridgeCore<E> {
+    pub fn new(ctx: Context<E>) -> Self {
+        let (inbound_tx, inbound) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+        Self {
+            ctx,
+            filter: None,
+            inbound,
+            inbound_tx,
+            cancel: CancellationToken::new(),
+            pending: None,
+            imported: HashSet::new(),
+            imported_order: VecDeque::new(),
+        }
+    }
+
+    /// Replace the import queue with one of the given capacity. Call before start.
+    pub fn set_inbound_capacity(&mut self, capacity: usize) {
+        let (inbound_tx, inbound) = tokio::sync::mpsc::channel(capacity.max(1));
+        self.inbound = inbound;
+        self.inbound_tx = inbound_tx;
+    }
+
+    /// Whether the envelope should be sent to remote peers.
+    pub fn exports(&self, envelope: &Envelope<E>) -> bool {
+        if self.imported.contains(&envelope.id()) {
+            return false;
+        }
+        match &self.filter {
+            Some(filter) => filter(envelope.event()),
+            None => true,
+        }
+    }
+
+    /// Export only events of the given topics.
+    pub fn set_topics<T: Topic<E>>(&mut self, topics: impl IntoIterator<Item = T>) {
+        let topics: Vec<T> = topics.into_iter().collect();
+        self.filter = Some(Box::new(move |event| {
+            topics.contains(&T::from_event(event))
+        }));
+    }
+
+    /// Wait for the next remote envelope and publish it locally.
+    ///
+    /// Cancel-safe: an envelope taken from the queue is kept until the
+    /// broker accepts it.
+    pub async fn import_next(&mut self) -> Result<StepAction> {
+        if self.pending.is_none() {
+            let Some(envelope) = self.inbound.recv().await else {
+                return Ok(StepAction::Never);
+            };
+            self.remember(envelope.id());
+            let (event, meta, _) = envelope.into_parts();
+            let meta = meta.forwarded_by(self.ctx.actor_id().clone());
+            self.pending = Some(Envelope::with_meta(event, meta));
+        }
+        if let Some(envelope) = &self.pending {
+            self.ctx.send_envelope(envelope.clone()).await?;
+            self.pending = None;
+        }
+        Ok(StepAction::Continue)
+    }
+
+    fn remember(&mut self, id: EventId) {
+        if self.imported.insert(id) {
+            self.imported_order.push_back(id);
+        }
+        while self.imported_order.len() > IMPORTED_IDS_CAPACITY {
+            if let Some(old) = self.imported_order.pop_front() {
+                self.imported.remove(&old);
+            }
+        }
+    }
+}
+
+impl<E> Drop for BridgeCore<E> {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{ActorId, codec::Json};
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    struct TestEvent(u32);
+    impl Event for TestEvent {}
+
+    #[tokio::test]
+    async fn frame_roundtrip() {
+        let envelope = Envelope::new(TestEvent(7), ActorId::new(Arc::from("remote")));
+        let mut wire = Vec::new();
+        let mut writer = FramedWrite::new(&mut wire, frame_codec());
+        writer
+            .send(encode(&Json, &envelope).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(wire[..4], (wire.len() as u32 - 4).to_be_bytes());
+
+        let mut reader = FramedRead::new(&wire[..], frame_codec());
+        let buf = reader.next().await.unwrap().unwrap();
+        let decoded: Envelope<TestEvent> = Json.decode(&buf).unwrap();
+        assert_eq!(decoded.id(), envelope.id());
+        assert_eq!(decoded.meta().actor_name(), "remote");
+        assert_eq!(decoded.event().0, 7);
+
+        assert!(reader.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_is_rejected() {
+        let header = ((MAX_RECORD_LEN + 1) as u32).to_be_bytes();
+        let mut reader = FramedRead::new(&header[..], frame_codec());
+        let err = reader.next().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,859 @@
+//! TCP bridge between Maiko processes.
+//!
+//! Both ends run a [`TcpBridge`]: one created with [`TcpBridge::listen`],
+//! the other with [`TcpBridge::connect`]. Once connected the protocol is
+//! symmetric:
+//!
+//! 1. **Handshake** - each side sends a [`Hello`] with the protocol version,
+//!    event schema name, codec and flow-control window. Peers that disagree
+//!    on any of them are disconnected. The hello itself is always JSON; all
+//!    later messages use the bridge's [`Codec`].
+//! 2. **Events** - envelopes are sent with a per-bridge sequence number and
+//!    kept until the remote acknowledges them. At most `window` envelopes
+//!    (as announced by the remote) are in flight; when the window is full
+//!    the bridge stops taking events from the broker, which pushes back on
+//!    local producers instead of buffering without bound.
+//! 3. **Acks** - the receiving side acknowledges an envelope once it has been
+//!    published into its local broker, returning one credit to the sender.
+//! 4. **Heartbeats** - sent periodically; a connection silent for three
+//!    intervals is considered dead.
+//!
+//! After a reconnect all unacknowledged envelopes are sent again. Receivers
+//! skip sequence numbers they have already seen, so each envelope is
+//! published once per peer session.
+
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::{
+    net::{
+        TcpListener, TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::{
+        Notify,
+        mpsc::{self, Receiver, Sender},
+    },
+    task::JoinHandle,
+    time::Instant,
+};
+use tokio_util::{
+    codec::{FramedRead, FramedWrite, LengthDelimitedCodec},
+    sync::CancellationToken,
+};
+
+use crate::{
+    Actor, Context, Envelope, Event, Result, StepAction, Topic,
+    bridge::{BridgeCore, Frame, encode, frame_codec},
+    codec::{Codec, Json},
+};
+
+/// Version of the bridge wire protocol, checked during the handshake.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+const DEFAULT_WINDOW: usize = 64;
+
+type Writer = FramedWrite<OwnedWriteHalf, LengthDelimitedCodec>;
+
+/// Handshake message exchanged when a connection is established.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hello {
+    /// Wire protocol version, see [`PROTOCOL_VERSION`].
+    pub version: u32,
+    /// Name of the event schema; both peers must use the same one.
+    pub schema: String,
+    /// [`Codec::name`] of the codec used after the handshake.
+    pub codec: String,
+    /// Random id of the sending bridge instance, changes on restart.
+    pub session: u128,
+    /// How many unacknowledged envelopes the sender accepts.
+    pub window: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Message<Env> {
+    Hello(Hello),
+    Event { seq: u64, envelope: Env },
+    Ack { seq: u64 },
+    Heartbeat,
+}
+
+enum Endpoint {
+    Listen(String),
+    Listener(std::net::TcpListener),
+    Connect(String),
+}
+
+/// Bridge actor connecting two Maiko processes over TCP.
+///
+/// Exports the events it receives (optionally narrowed with
+/// [`with_topics`](Self::with_topics)) to the remote peer and publishes the
+/// peer's events locally as its own, keeping the remote sender as
+/// [`Meta::origin`](crate::Meta::origin). See the
+/// [module documentation](self) for the protocol.
+///
+/// `handle_event` waits while the remote's window is full, so the bridge's
+/// topics should use [`OverflowPolicy::Block`](crate::OverflowPolicy::Block)
+/// for the backpressure to reach producers.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // host A
+/// sup.add_actor("peer", |ctx| TcpBridge::listen(ctx, "0.0.0.0:7400"), &[AppTopic::Orders])?;
+///
+/// // host B
+/// sup.add_actor("peer", |ctx| {
+///     TcpBridge::connect(ctx, "host-a:7400").with_window(32)
+/// }, &[AppTopic::Shipments])?;
+/// ```
+pub struct TcpBridge<E, C = Json> {
+    endpoint: Option<Endpoint>,
+    core: BridgeCore<E>,
+    outgoing: Sender<Envelope<E>>,
+    outgoing_rx: Option<Receiver<Envelope<E>>>,
+    imported: Arc<AtomicU64>,
+    import_notify: Arc<Notify>,
+    schema: String,
+    window: usize,
+    heartbeat: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    codec: C,
+}
+
+impl<E: Event> TcpBridge<E> {
+    /// Create a bridge that accepts a peer on the given address.
+    ///
+    /// The address is bound in `on_start`. One peer is served at a time.
+    pub fn listen(ctx: Context<E>, addr: impl Into<String>) -> Self {
+        Self::new(ctx, Endpoint::Listen(addr.into()))
+    }
+
+    /// Create a bridge that accepts a peer on an already bound listener.
+    pub fn from_listener(ctx: Context<E>, listener: std::net::TcpListener) -> Self {
+        Self::new(ctx, Endpoint::Listener(listener))
+    }
+
+    /// Create a bridge that connects to a peer, reconnecting with backoff.
+    pub fn connect(ctx: Context<E>, addr: impl Into<String>) -> Self {
+        Self::new(ctx, Endpoint::Connect(addr.into()))
+    }
+
+    fn new(ctx: Context<E>, endpoint: Endpoint) -> Self {
+        let (outgoing, outgoing_rx) = mpsc::channel(DEFAULT_WINDOW);
+        let mut core = BridgeCore::new(ctx);
+        core.set_inbound_capacity(DEFAULT_WINDOW);
+        Self {
+            endpoint: Some(endpoint),
+            core,
+            outgoing,
+            outgoing_rx: Some(outgoing_rx),
+            imported: Arc::new(AtomicU64::new(0)),
+            import_notify: Arc::new(Notify::new()),
+            schema: std::any::type_name::<E>().to_string(),
+            window: DEFAULT_WINDOW,
+            heartbeat: Duration::from_secs(5),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            codec: Json,
+        }
+    }
+}
+
+impl<E: Event, C: Codec> TcpBridge<E, C> {
+    /// Use a different [`Codec`] for messages. The peer must use the same one.
+    pub fn with_codec<C2: Codec>(self, codec: C2) -> TcpBridge<E, C2> {
+        TcpBridge {
+            endpoint: self.endpoint,
+            core: self.core,
+            outgoing: self.outgoing,
+            outgoing_rx: self.outgoing_rx,
+            imported: self.imported,
+            import_notify: self.import_notify,
+            schema: self.schema,
+            window: self.window,
+            heartbeat: self.heartbeat,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            codec,
+        }
+    }
+
+    /// Export only events of the given topics. By default every received event is exported.
+    pub fn with_topics<T: Topic<E>>(mut self, topics: impl IntoIterator<Item = T>) -> Self {
+        self.core.set_topics(topics);
+        self
+    }
+
+    /// Set the schema name announced in the handshake.
+    ///
+    /// Defaults to the Rust type name of `E`, which only matches if both
+    /// processes use the same event type from the same crate.
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = schema.into();
+        self
+    }
+
+    /// Set how many unacknowledged envelopes this side accepts from the remote
+    /// (default 64). Also sizes the local outgoing queue.
+    pub fn with_window(mut self, window: usize) -> Self {
+        let window = window.max(1);
+        let (outgoing, outgoing_rx) = mpsc::channel(window);
+        self.outgoing = outgoing;
+        self.outgoing_rx = Some(outgoing_rx);
+        self.core.set_inbound_capacity(window);
+        self.window = window;
+        self
+    }
+
+    /// Set the heartbeat interval (default 5 s). A connection without any
+    /// traffic for three intervals is dropped and re-established.
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = interval;
+        self
+    }
+
+    /// Set the reconnect delay: it starts at `initial` and doubles up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+}
+
+impl<E, C> Actor for TcpBridge<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    type Event = E;
+
+    async fn on_start(&mut self) -> Result<()> {
+        let (Some(endpoint), Some(outgoing)) = (self.endpoint.take(), self.outgoing_rx.take())
+        else {
+            return Ok(());
+        };
+        let mut link = Link::<E, C> {
+            codec: self.codec.clone(),
+            hello: Hello {
+                version: PROTOCOL_VERSION,
+                schema: self.schema.clone(),
+                codec: self.codec.name().to_string(),
+                session: uuid::Uuid::new_v4().as_u128(),
+                window: self.window as u32,
+            },
+            heartbeat: self.heartbeat,
+            outgoing,
+            inbound: self.core.inbound_tx.clone(),
+            imported: self.imported.clone(),
+            import_notify: self.import_notify.clone(),
+            cancel: self.core.cancel.clone(),
+            next_seq: 1,
+            unacked: VecDeque::new(),
+            remote_session: None,
+            last_received: 0,
+            last_acked: 0,
+            pending_acks: VecDeque::new(),
+            counted_imports: 0,
+        };
+        let cancel = self.core.cancel.clone();
+        let (initial, max) = (self.initial_backoff, self.max_backoff);
+
+        match endpoint {
+            Endpoint::Connect(addr) => {
+                tokio::spawn(async move {
+                    let mut backoff = initial;
+                    while !cancel.is_cancelled() {
+                        match TcpStream::connect(&addr).await {
+                            Ok(stream) => {
+                                if link.run(stream).await {
+                                    backoff = initial;
+                                }
+                            }
+                            Err(e) => {
+                                tracing::debug!(%addr, error=%e, "tcp bridge failed to connect")
+                            }
+                        }
+                        tokio::select! {
+                            _ = cancel.cancelled() => break,
+                            _ = tokio::time::sleep(backoff) => {},
+                        }
+                        backoff = (backoff * 2).min(max);
+                    }
+                });
+            }
+            Endpoint::Listen(addr) => {
+                let listener = TcpListener::bind(&addr).await?;
+                tokio::spawn(async move { link.accept_loop(listener).await });
+            }
+            Endpoint::Listener(listener) => {
+                listener.set_nonblocking(true)?;
+                let listener = TcpListener::from_std(listener)?;
+                tokio::spawn(async move { link.accept_loop(listener).await });
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        if !self.core.exports(envelope) {
+            return Ok(());
+        }
+        tokio::select! {
+            res = self.outgoing.send(envelope.clone()) => {
+                if res.is_err() {
+                    tracing::warn!(event_id=%envelope.id(), "tcp bridge link is closed, event dropped");
+                }
+            }
+            _ = self.core.ctx.shutdown_requested() => {
+                tracing::debug!(event_id=%envelope.id(), "tcp bridge shutting down, event dropped");
+            }
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self) -> Result<StepAction> {
+        let action = self.core.import_next().await?;
+        if action == StepAction::Continue {
+            self.imported.fetch_add(1, Ordering::Release);
+            self.import_notify.notify_one();
+        }
+        Ok(action)
+    }
+
+    async fn on_shutdown(&mut self) -> Result<()> {
+        self.core.cancel.cancel();
+        Ok(())
+    }
+}
+
+/// Connection state that survives reconnects.
+struct Link<E, C> {
+    codec: C,
+    hello: Hello,
+    heartbeat: Duration,
+    outgoing: Receiver<Envelope<E>>,
+    inbound: Sender<Envelope<E>>,
+    imported: Arc<AtomicU64>,
+    import_notify: Arc<Notify>,
+    cancel: CancellationToken,
+
+    next_seq: u64,
+    unacked: VecDeque<(u64, Frame)>,
+
+    remote_session: Option<u128>,
+    last_received: u64,
+    last_acked: u64,
+    /// Sequence numbers handed to the actor, tagged with the remote session.
+    pending_acks: VecDeque<(u128, u64)>,
+    /// How many of the actor's imports have been matched with `pending_acks`.
+    counted_imports: u64,
+}
+
+impl<E, C> Link<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    async fn accept_loop(&mut self, listener: TcpListener) {
+        loop {
+            let stream = tokio::select! {
+                _ = self.cancel.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!(error=%e, "tcp bridge failed to accept connection");
+                        continue;
+                    }
+                },
+            };
+            self.run(stream).await;
+        }
+    }
+
+    /// Serve one connection. Returns whether the handshake succeeded.
+    async fn run(&mut self, stream: TcpStream) -> bool {
+        let _ = stream.set_nodelay(true);
+        let (reader, writer) = stream.into_split();
+        let mut writer = FramedWrite::new(writer, frame_codec());
+        let (messages_tx, mut messages) = mpsc::channel(self.hello.window as usize + 16);
+        let reader = spawn_reader::<E, C>(self.codec.clone(), reader, messages_tx);
+
+        let res = async {
+            let remote = self.handshake(&mut writer, &mut messages).await?;
+            self.exchange(&mut writer, &mut messages, remote.window as usize)
+                .await
+        }
+        .await;
+        reader.abort();
+
+        match res {
+            Ok(()) => true,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                tracing::warn!(error=%e, "tcp bridge protocol error");
+                false
+            }
+            Err(e) => {
+                tracing::debug!(error=%e, "tcp bridge connection closed");
+                true
+            }
+        }
+    }
+
+    async fn handshake(
+        &mut self,
+        writer: &mut Writer,
+        messages: &mut Receiver<Message<Envelope<E>>>,
+    ) -> io::Result<Hello> {
+        writer
+            .send(encode(
+                &Json,
+                &Message::<&Envelope<E>>::Hello(self.hello.clone()),
+            )?)
+            .await?;
+        let remote = match tokio::time::timeout(self.heartbeat * 3, messages.recv()).await {
+            Ok(Some(Message::Hello(hello))) => hello,
+            Ok(Some(_)) => return Err(invalid("expected hello")),
+            Ok(None) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Err(_) => return Err(io::ErrorKind::TimedOut.into()),
+        };
+        if remote.version != self.hello.version {
+            return Err(invalid(format!(
+                "protocol version {} != {}",
+                remote.version, self.hello.version
+            )));
+        }
+        if remote.schema != self.hello.schema {
+            return Err(invalid(format!(
+                "schema '{}' != '{}'",
+                remote.schema, self.hello.schema
+            )));
+        }
+        if remote.codec != self.hello.codec {
+            return Err(invalid(format!(
+                "codec '{}' != '{}'",
+                remote.codec, self.hello.codec
+            )));
+        }
+        if self.remote_session != Some(remote.session) {
+            self.remote_session = Some(remote.session);
+            self.last_received = 0;
+            self.last_acked = 0;
+        }
+        if self.last_acked > 0 {
+            writer
+                .send(encode(
+                    &self.codec,
+                    &Message::<&Envelope<E>>::Ack {
+                        seq: self.last_acked,
+                    },
+                )?)
+                .await?;
+        }
+        for (_, frame) in &self.unacked {
+            writer.feed(frame.clone()).await?;
+        }
+        writer.flush().await?;
+        Ok(remote)
+    }
+
+    async fn exchange(
+        &mut self,
+        writer: &mut Writer,
+        messages: &mut Receiver<Message<Envelope<E>>>,
+        remote_window: usize,
+    ) -> io::Result<()> {
+        let silence = self.heartbeat * 3;
+        let mut last_seen = Instant::now();
+        let mut heartbeat = tokio::time::interval(self.heartbeat);
+        heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => return Ok(()),
+
+                message = messages.recv() => {
+                    let Some(message) = message else {
+                        return Err(io::ErrorKind::UnexpectedEof.into());
+                    };
+                    last_seen = Instant::now();
+                    self.receive(message)?;
+                }
+
+                Some(envelope) = self.outgoing.recv(), if self.unacked.len() < remote_window => {
+                    let seq = self.next_seq;
+                    self.next_seq += 1;
+                    let frame = match encode(&self.codec, &Message::Event { seq, envelope: &envelope }) {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            tracing::warn!(event_id=%envelope.id(), error=%e, "tcp bridge failed to encode event");
+                            continue;
+                        }
+                    };
+                    self.unacked.push_back((seq, frame.clone()));
+                    writer.send(frame).await?;
+                }
+
+                _ = self.import_notify.notified() => {
+                    if let Some(seq) = self.take_ack() {
+                        writer.send(encode(&self.codec, &Message::<&Envelope<E>>::Ack { seq })?).await?;
+                    }
+                }
+
+                _ = heartbeat.tick() => {
+                    writer.send(encode(&self.codec, &Message::<&Envelope<E>>::Heartbeat)?).await?;
+                }
+
+                _ = tokio::time::sleep_until(last_seen + silence) => {
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "peer went silent"));
+                }
+            }
+        }
+    }
+
+    fn receive(&mut self, message: Message<Envelope<E>>) -> io::Result<()> {
+        match message {
+            Message::Event { seq, envelope } => {
+                if seq <= self.last_received {
+                    return Ok(());
+                }
+                let session = self.remote_session.unwrap_or_default();
+                if self.inbound.try_send(envelope).is_err() {
+                    return Err(invalid("peer exceeded the flow-control window"));
+                }
+                self.last_received = seq;
+                self.pending_acks.push_back((session, seq));
+            }
+            Message::Ack { seq } => {
+                while self.unacked.front().is_some_and(|(s, _)| *s <= seq) {
+                    self.unacked.pop_front();
+                }
+            }
+            Message::Hello(_) | Message::Heartbeat => {}
+        }
+        Ok(())
+    }
+
+    /// Match the actor's completed imports with received sequence numbers and
+    /// return the highest one that can be acknowledged to the current peer.
+    fn take_ack(&mut self) -> Option<u64> {
+        let total = self.imported.load(Ordering::Acquire);
+        let mut ack = None;
+        while self.counted_imports < total {
+            let Some((session, seq)) = self.pending_acks.pop_front() else {
+                break;
+            };
+            self.counted_imports += 1;
+            if Some(session) == self.remote_session {
+                ack = Some(seq);
+            }
+        }
+        if let Some(seq) = ack {
+            self.last_acked = seq;
+        }
+        ack
+    }
+}
+
+/// Decode incoming messages: the first one (the hello) as JSON, the rest with `codec`.
+fn spawn_reader<E, C>(
+    codec: C,
+    reader: OwnedReadHalf,
+    messages: Sender<Message<Envelope<E>>>,
+) -> JoinHandle<()>
+where
+    E: Event + DeserializeOwned,
+    C: Codec,
+{
+    tokio::spawn(async move {
+        let mut reader = FramedRead::new(reader, frame_codec());
+        let mut handshake = true;
+        while let Some(frame) = reader.next().await {
+            match frame {
+                Ok(buf) => match decode_message(&codec, &buf, std::mem::take(&mut handshake)) {
+                    Ok(message) => {
+                        if messages.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error=%e, "tcp bridge failed to decode message");
+                        break;
+                    }
+                },
+                Err(e) => {
+                    tracing::debug!(error=%e, "tcp bridge read failed");
+                    break;
+                }
+            }
+        }
+    })
+}
+
+fn decode_message<E, C>(codec: &C, buf: &[u8], handshake: bool) -> io::Result<Message<Envelope<E>>>
+where
+    E: DeserializeOwned,
+    C: Codec,
+{
+    if handshake {
+        Json.decode(buf)
+    } else {
+        codec.decode(buf)
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+    use tokio_util::codec::Framed;
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic, Supervisor};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct TestEvent(u32);
+    impl Event for TestEvent {}
+
+    struct Collector(UnboundedSender<Envelope<TestEvent>>);
+
+    impl Actor for Collector {
+        type Event = TestEvent;
+        async fn handle_event(&mut self, envelope: &Envelope<TestEvent>) -> Result<()> {
+            let _ = self.0.send(envelope.clone());
+            Ok(())
+        }
+    }
+
+    type Peer = Framed<TcpStream, LengthDelimitedCodec>;
+
+    async fn read_message(stream: &mut Peer) -> Message<Envelope<TestEvent>> {
+        loop {
+            let buf = stream.next().await.unwrap().unwrap();
+            let message = Json.decode(&buf).unwrap();
+            if !matches!(message, Message::Heartbeat) {
+                return message;
+            }
+        }
+    }
+
+    async fn write_message(stream: &mut Peer, message: Message<&Envelope<TestEvent>>) {
+        stream.send(encode(&Json, &message).unwrap()).await.unwrap();
+    }
+
+    async fn expect_event(stream: &mut Peer) -> u64 {
+        match tokio::time::timeout(Duration::from_secs(2), read_message(stream)).await {
+            Ok(Message::Event { seq, .. }) => seq,
+            other => panic!("expected event, got {other:?}"),
+        }
+    }
+
+    /// Accept a connection and complete the handshake with the given window.
+    async fn accept_peer(listener: &TcpListener, window: u32) -> Peer {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = Framed::new(stream, frame_codec());
+        let Message::Hello(hello) = read_message(&mut stream).await else {
+            panic!("expected hello");
+        };
+        assert_eq!(hello.version, PROTOCOL_VERSION);
+        assert_eq!(hello.codec, "json");
+        let reply = Hello {
+            session: 1,
+            window,
+            ..hello
+        };
+        write_message(&mut stream, Message::Hello(reply)).await;
+        stream
+    }
+
+    /// Read until the bridge closes the connection, failing on any event.
+    async fn expect_closed(stream: &mut Peer) {
+        let closed = tokio::time::timeout(Duration::from_secs(2), async {
+            while let Some(Ok(buf)) = stream.next().await {
+                let message: Message<Envelope<TestEvent>> = Json.decode(&buf).unwrap();
+                assert!(
+                    !matches!(message, Message::Event { .. }),
+                    "event sent on a rejected connection"
+                );
+            }
+        });
+        closed.await.expect("connection not closed");
+    }
+
+    /// Start a connecting bridge with one event queued for export.
+    async fn connecting_bridge(
+        listener: &TcpListener,
+        heartbeat: Duration,
+    ) -> Supervisor<TestEvent> {
+        let addr = listener.local_addr().unwrap().to_string();
+        let mut sup = Supervisor::<TestEvent>::default();
+        sup.add_actor(
+            "peer",
+            |ctx| {
+                TcpBridge::connect(ctx, addr)
+                    .with_heartbeat(heartbeat)
+                    .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+            },
+            &[DefaultTopic],
+        )
+        .unwrap();
+        sup.start().await.unwrap();
+        sup.send(TestEvent(1)).await.unwrap();
+        sup
+    }
+
+    /// Answer the bridge's hello with a modified one and expect a hang-up.
+    async fn assert_hello_rejected(modify: impl FnOnce(&mut Hello)) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut sup = connecting_bridge(&listener, Duration::from_secs(5)).await;
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = Framed::new(stream, frame_codec());
+        let Message::Hello(mut reply) = read_message(&mut stream).await else {
+            panic!("expected hello");
+        };
+        reply.session = 1;
+        modify(&mut reply);
+        write_message(&mut stream, Message::Hello(reply)).await;
+        expect_closed(&mut stream).await;
+
+        sup.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn hello_with_other_version_is_rejected() {
+        assert_hello_rejected(|hello| hello.version = PROTOCOL_VERSION + 1).await;
+    }
+
+    #[tokio::test]
+    async fn hello_with_other_schema_is_rejected() {
+        assert_hello_rejected(|hello| hello.schema = "other-schema".to_string()).await;
+    }
+
+    #[tokio::test]
+    async fn hello_with_other_codec_is_rejected() {
+        assert_hello_rejected(|hello| hello.codec = "cbor".to_string()).await;
+    }
+
+    #[tokio::test]
+    async fn silent_peer_is_disconnected() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut sup = connecting_bridge(&listener, Duration::from_millis(20)).await;
+
+        let mut stream = accept_peer(&listener, 0).await;
+        let closed = tokio::time::timeout(Duration::from_secs(2), async {
+            while let Some(Ok(buf)) = stream.next().await {
+                let message: Message<Envelope<TestEvent>> = Json.decode(&buf).unwrap();
+                assert!(matches!(message, Message::Heartbeat));
+            }
+        });
+        closed.await.expect("silent peer not disconnected");
+
+        let _reconnected = accept_peer(&listener, 0).await;
+        sup.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn events_cross_between_processes() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let (tx, mut rx) = unbounded_channel();
+        let mut server = Supervisor::<TestEvent>::default();
+        server
+            .add_actor(
+                "peer",
+                |ctx| TcpBridge::from_listener(ctx, listener),
+                &[DefaultTopic],
+            )
+            .unwrap();
+        // Named like the remote sender: a name collision must not hide
+        // imported events from it.
+        server
+            .add_actor("sensor", |_| Collector(tx), &[DefaultTopic])
+            .unwrap();
+
+        let mut client = Supervisor::<TestEvent>::default();
+        client
+            .add_actor(
+                "peer",
+                |ctx| {
+                    TcpBridge::connect(ctx, addr)
+                        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+                },
+                &[DefaultTopic],
+            )
+            .unwrap();
+
+        server.start().await.unwrap();
+        client.start().await.unwrap();
+
+        let original =
+            Envelope::with_correlation(TestEvent(5), ActorId::new(Arc::from("sensor")), 77);
+        let original_id = original.id();
+        client.sender.send(Arc::new(original)).await.unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("event not received")
+            .unwrap();
+        client.stop().await.unwrap();
+        server.stop().await.unwrap();
+
+        assert_eq!(received.event().0, 5);
+        assert_eq!(received.id(), original_id);
+        assert_eq!(received.meta().actor_name(), "peer");
+        assert_eq!(received.meta().origin().map(ActorId::name), Some("sensor"));
+        assert_eq!(received.meta().correlation_id(), Some(77));
+    }
+
+    #[tokio::test]
+    async fn window_limits_in_flight_and_unacked_are_replayed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let mut sup = Supervisor::<TestEvent>::default();
+        sup.add_actor(
+            "peer",
+            |ctx| {
+                TcpBridge::connect(ctx, addr)
+                    .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+            },
+            &[DefaultTopic],
+        )
+        .unwrap();
+        sup.start().await.unwrap();
+
+        let mut stream = accept_peer(&listener, 2).await;
+        for i in 0..3 {
+            sup.send(TestEvent(i)).await.unwrap();
+        }
+        assert_eq!(expect_event(&mut stream).await, 1);
+        assert_eq!(expect_event(&mut stream).await, 2);
+        let third = tokio::time::timeout(Duration::from_millis(100), read_message(&mut stream));
+        assert!(third.await.is_err(), "window of 2 exceeded");
+        drop(stream);
+
+        let mut stream = accept_peer(&listener, 2).await;
+        assert_eq!(expect_event(&mut stream).await, 1);
+        assert_eq!(expect_event(&mut stream).await, 2);
+        write_message(&mut stream, Message::Ack { seq: 2 }).await;
+        assert_eq!(expect_event(&mut stream).await, 3);
+
+        sup.stop().await.unwrap();
+    }
+}
@@ -0,0 +1,401 @@
+//! Unix domain socket bridge.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::mpsc::{self, Receiver, Sender, error::TrySendError},
+};
+
+use crate::{
+    Actor, Context, Envelope, Event, Result, StepAction, Topic,
+    bridge::{BridgeCore, CHANNEL_CAPACITY, Frame, encode, serve_connection},
+    codec::{Codec, Json},
+};
+
+/// Bridge actor accepting connections on a Unix domain socket.
+///
+/// Every event the actor receives (optionally narrowed with
+/// [`with_topics`](Self::with_topics)) is sent to all connected clients.
+/// Events arriving from any client are published into the local broker as
+/// sent by the server, keeping the remote sender as
+/// [`Meta::origin`](crate::Meta::origin). Clients that can't keep up have
+/// frames dropped.
+///
+/// The socket is bound in `on_start`; a stale socket file at the same path
+/// is removed first. The file is removed again on shutdown.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sup.add_actor("ipc", |ctx| UnixServer::new(ctx, "/tmp/app.sock"), &[AppTopic::Status])?;
+/// ```
+pub struct UnixServer<E, C = Json> {
+    path: PathBuf,
+    core: BridgeCore<E>,
+    connections: Arc<Mutex<Vec<Sender<Frame>>>>,
+    codec: C,
+}
+
+impl<E: Event> UnixServer<E> {
+    /// Create a server that will listen on `path`.
+    pub fn new(ctx: Context<E>, path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            core: BridgeCore::new(ctx),
+            connections: Arc::new(Mutex::new(Vec::new())),
+            codec: Json,
+        }
+    }
+}
+
+impl<E: Event, C: Codec> UnixServer<E, C> {
+    /// Use a different [`Codec`] for frames. Clients must use the same one.
+    pub fn with_codec<C2: Codec>(self, codec: C2) -> UnixServer<E, C2> {
+        UnixServer {
+            path: self.path,
+            core: self.core,
+            connections: self.connections,
+            codec,
+        }
+    }
+
+    /// Export only events of the given topics. By default every received event is exported.
+    pub fn with_topics<T: Topic<E>>(mut self, topics: impl IntoIterator<Item = T>) -> Self {
+        self.core.set_topics(topics);
+        self
+    }
+
+    /// Path of the socket file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of currently connected clients.
+    pub fn connection_count(&self) -> usize {
+        self.connections.lock().unwrap().len()
+    }
+}
+
+impl<E, C> Actor for UnixServer<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    type Event = E;
+
+    async fn on_start(&mut self) -> Result<()> {
+        remove_stale_socket(&self.path)?;
+        let listener = UnixListener::bind(&self.path)?;
+        let connections = self.connections.clone();
+        let inbound = self.core.inbound_tx.clone();
+        let cancel = self.core.cancel.clone();
+        let codec = self.codec.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            tracing::warn!(error=%e, "unix bridge failed to accept connection");
+                            continue;
+                        }
+                    },
+                };
+                let (tx, mut rx) = mpsc::channel(CHANNEL_CAPACITY);
+                connections.lock().unwrap().push(tx);
+                let inbound = inbound.clone();
+                let cancel = cancel.clone();
+                let codec = codec.clone();
+                tokio::spawn(async move {
+                    serve_connection(&codec, stream, &mut rx, &inbound, &cancel).await;
+                });
+            }
+        });
+        Ok(())
+    }
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        if !self.core.exports(envelope) {
+            return Ok(());
+        }
+        let frame = match encode(&self.codec, envelope) {
+            Ok(frame) => frame,
+            Err(e) => {
+                tracing::warn!(event_id=%envelope.id(), error=%e, "unix bridge failed to encode event");
+                return Ok(());
+            }
+        };
+        self.connections
+            .lock()
+            .unwrap()
+            .retain(|tx| match tx.try_send(frame.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    tracing::warn!(event_id=%envelope.id(), "unix bridge client is lagging, frame dropped");
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            });
+        Ok(())
+    }
+
+    async fn step(&mut self) -> Result<StepAction> {
+        self.core.import_next().await
+    }
+
+    async fn on_shutdown(&mut self) -> Result<()> {
+        self.core.cancel.cancel();
+        let _ = std::fs::remove_file(&self.path);
+        Ok(())
+    }
+}
+
+/// Bridge actor connecting to a [`UnixServer`].
+///
+/// Exports the events it receives (optionally narrowed with
+/// [`with_topics`](Self::with_topics)) and publishes events coming from the
+/// server locally as its own, keeping the remote sender as
+/// [`Meta::origin`](crate::Meta::origin). When the connection can't be
+/// established or is lost, the client reconnects with exponential backoff.
+/// Events exported while disconnected are buffered up to a small limit and
+/// dropped beyond it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sup.add_actor("ipc", |ctx| {
+///     UnixClient::new(ctx, "/tmp/app.sock").with_backoff(Duration::from_millis(50), Duration::from_secs(2))
+/// }, &[AppTopic::Command])?;
+/// ```
+pub struct UnixClient<E, C = Json> {
+    path: PathBuf,
+    core: BridgeCore<E>,
+    outbound: Sender<Frame>,
+    outbound_rx: Option<Receiver<Frame>>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    codec: C,
+}
+
+impl<E: Event> UnixClient<E> {
+    /// Create a client that will connect to the server listening on `path`.
+    pub fn new(ctx: Context<E>, path: impl AsRef<Path>) -> Self {
+        let (outbound, outbound_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        Self {
+            path: path.as_ref().to_path_buf(),
+            core: BridgeCore::new(ctx),
+            outbound,
+            outbound_rx: Some(outbound_rx),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            codec: Json,
+        }
+    }
+}
+
+impl<E: Event, C: Codec> UnixClient<E, C> {
+    /// Use a different [`Codec`] for frames. The server must use the same one.
+    pub fn with_codec<C2: Codec>(self, codec: C2) -> UnixClient<E, C2> {
+        UnixClient {
+            path: self.path,
+            core: self.core,
+            outbound: self.outbound,
+            outbound_rx: self.outbound_rx,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            codec,
+        }
+    }
+
+    /// Export only events of the given topics. By default every received event is exported.
+    pub fn with_topics<T: Topic<E>>(mut self, topics: impl IntoIterator<Item = T>) -> Self {
+        self.core.set_topics(topics);
+        self
+    }
+
+    /// Set the reconnect delay: it starts at `initial` and doubles up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Path of the server's socket file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<E, C> Actor for UnixClient<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    type Event = E;
+
+    async fn on_start(&mut self) -> Result<()> {
+        let Some(mut outbound) = self.outbound_rx.take() else {
+            return Ok(());
+        };
+        let path = self.path.clone();
+        let inbound = self.core.inbound_tx.clone();
+        let cancel = self.core.cancel.clone();
+        let (initial, max) = (self.initial_backoff, self.max_backoff);
+        let codec = self.codec.clone();
+
+        tokio::spawn(async move {
+            let mut backoff = initial;
+            while !cancel.is_cancelled() {
+                match UnixStream::connect(&path).await {
+                    Ok(stream) => {
+                        tracing::debug!(path=%path.display(), "unix bridge connected");
+                        backoff = initial;
+                        serve_connection(&codec, stream, &mut outbound, &inbound, &cancel).await;
+                        tracing::debug!(path=%path.display(), "unix bridge disconnected");
+                    }
+                    Err(e) => {
+                        tracing::debug!(path=%path.display(), error=%e, "unix bridge failed to connect");
+                    }
+                }
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(backoff) => {},
+                }
+                backoff = (backoff * 2).min(max);
+            }
+        });
+        Ok(())
+    }
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        if !self.core.exports(envelope) {
+            return Ok(());
+        }
+        match encode(&self.codec, envelope) {
+            Ok(frame) => {
+                if self.outbound.try_send(frame).is_err() {
+                    tracing::warn!(event_id=%envelope.id(), "unix bridge outbound buffer is full, frame dropped");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(event_id=%envelope.id(), error=%e, "unix bridge failed to encode event")
+            }
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self) -> Result<StepAction> {
+        self.core.import_next().await
+    }
+
+    async fn on_shutdown(&mut self) -> Result<()> {
+        self.core.cancel.cancel();
+        Ok(())
+    }
+}
+
+fn remove_stale_socket(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic, Supervisor};
+
+    #[derive(Clone, Debug, Serialize, serde::Deserialize)]
+    enum TestEvent {
+        Data(u32),
+        Marker,
+    }
+    impl Event for TestEvent {}
+
+    struct Collector(UnboundedSender<Envelope<TestEvent>>);
+
+    impl Actor for Collector {
+        type Event = TestEvent;
+        async fn handle_event(&mut self, envelope: &Envelope<TestEvent>) -> Result<()> {
+            let _ = self.0.send(envelope.clone());
+            Ok(())
+        }
+    }
+
+    async fn next(rx: &mut UnboundedReceiver<Envelope<TestEvent>>) -> Envelope<TestEvent> {
+        tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("event not received")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn events_cross_the_socket_with_metadata_and_no_echo() {
+        let path = std::env::temp_dir().join(format!("maiko-{}.sock", uuid::Uuid::new_v4()));
+
+        let (server_tx, mut server_rx) = unbounded_channel();
+        let mut server_sup = Supervisor::<TestEvent>::default();
+        server_sup
+            .add_actor("server", |ctx| UnixServer::new(ctx, &path), &[DefaultTopic])
+            .unwrap();
+        // Named like the remote sender: a name collision must not hide
+        // imported events from it.
+        server_sup
+            .add_actor("sensor", |_| Collector(server_tx), &[DefaultTopic])
+            .unwrap();
+
+        let (client_tx, mut client_rx) = unbounded_channel();
+        let mut client_sup = Supervisor::<TestEvent>::default();
+        client_sup
+            .add_actor(
+                "client",
+                |ctx| {
+                    UnixClient::new(ctx, &path)
+                        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+                },
+                &[DefaultTopic],
+            )
+            .unwrap();
+        client_sup
+            .add_actor("collector", |_| Collector(client_tx), &[DefaultTopic])
+            .unwrap();
+
+        server_sup.start().await.unwrap();
+        client_sup.start().await.unwrap();
+
+        let original =
+            Envelope::with_correlation(TestEvent::Data(1), ActorId::new(Arc::from("sensor")), 99);
+        let original_id = original.id();
+        client_sup.sender.send(Arc::new(original)).await.unwrap();
+        assert_eq!(next(&mut client_rx).await.id(), original_id);
+
+        let imported = next(&mut server_rx).await;
+        assert_eq!(imported.id(), original_id);
+        assert_eq!(imported.meta().actor_name(), "server");
+        assert_eq!(imported.meta().origin().map(ActorId::name), Some("sensor"));
+        assert_eq!(imported.meta().correlation_id(), Some(99));
+
+        server_sup.send(TestEvent::Marker).await.unwrap();
+        let received = next(&mut client_rx).await;
+        assert!(matches!(received.event(), TestEvent::Marker));
+
+        client_sup.stop().await.unwrap();
+        server_sup.stop().await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,274 @@
+//! Pluggable serialization for envelopes.
+//!
+//! Components that persist or transmit events - the
+//! [`Recorder`](crate::monitors::Recorder) and the [`bridge`](crate::bridge)
+//! actors - are generic over a [`Codec`], with [`Json`] as the default.
+//!
+//! | Codec | Feature | Notes |
+//! |-------|---------|-------|
+//! | [`Json`] | `serde` | Human-readable, line-delimited in recordings |
+//! | [`Postcard`] | `postcard` | Compact binary, smallest and fastest |
+//! | [`MessagePack`] | `msgpack` | Binary, self-describing, widely supported |
+//!
+//! Binary codecs are length-prefixed (a big-endian `u32` before each
+//! record); JSON recordings keep the JSON Lines format.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let recorder = Recorder::with_codec("events.bin", Postcard)?;
+//! sup.add_actor("peer", |ctx| TcpBridge::connect(ctx, addr).with_codec(Postcard), &[Topic::Orders])?;
+//! ```
+
+use std::io::{self, Write};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Encodes and decodes values (usually [`Envelope`](crate::Envelope)s).
+///
+/// Both ends of a bridge, and the writer and reader of a recording, must
+/// use the same codec. Bridges check this during the handshake using
+/// [`name`](Self::name).
+pub trait Codec: Clone + Send + Sync + 'static {
+    /// Short identifier of the format, e.g. `"json"`.
+    fn name(&self) -> &'static str;
+
+    /// Whether encoded values never contain a newline byte, so records can
+    /// be separated by lines instead of length prefixes.
+    fn is_line_delimited(&self) -> bool {
+        false
+    }
+
+    /// Append the encoded value to `buf`.
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()>;
+
+    /// Decode a value from a complete record.
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T>;
+}
+
+/// JSON codec based on `serde_json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Json;
+
+impl Codec for Json {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        true
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()> {
+        serde_json::to_writer(buf, value)?;
+        Ok(())
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Compact binary codec based on [`postcard`](https://docs.rs/postcard).
+///
+/// Not self-describing: both sides must use exactly the same event types.
+#[cfg(feature = "postcard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl Codec for Postcard {
+    fn name(&self) -> &'static str {
+        "postcard"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()> {
+        let bytes = postcard::to_allocvec(value).map_err(invalid_data)?;
+        buf.extend_from_slice(&bytes);
+        Ok(())
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        postcard::from_bytes(bytes).map_err(invalid_data)
+    }
+}
+
+/// MessagePack codec based on [`rmp-serde`](https://docs.rs/rmp-serde).
+///
+/// Structs are encoded as maps, so records stay readable by other
+/// MessagePack tools.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MessagePack {
+    fn name(&self) -> &'static str {
+        "msgpack"
+    }
+
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()> {
+        rmp_serde::encode::write_named(buf, value).map_err(invalid_data)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        rmp_serde::from_slice(bytes).map_err(invalid_data)
+    }
+}
+
+/// Largest record accepted or produced with length-prefixed framing (16 MiB).
+pub const MAX_RECORD_LEN: usize = 16 * 1024 * 1024;
+
+/// Encode a value as a single record, framed according to the codec.
+///
+/// Line-delimited codecs get a trailing newline, others a `u32` length prefix.
+#[cfg_attr(not(feature = "recorder"), allow(dead_code))]
+pub(crate) fn encode_record<C: Codec, T: Serialize + ?Sized>(
+    codec: &C,
+    value: &T,
+) -> io::Result<Vec<u8>> {
+    if codec.is_line_delimited() {
+        let mut buf = Vec::new();
+        codec.encode(value, &mut buf)?;
+        buf.push(b'\n');
+        Ok(buf)
+    } else {
+        encode_frame(codec, value)
+    }
+}
+
+/// Encode a value with a `u32` length prefix, regardless of the codec.
+pub(crate) fn encode_frame<C: Codec, T: Serialize + ?Sized>(
+    codec: &C,
+    value: &T,
+) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; 4];
+    codec.encode(value, &mut buf)?;
+    let len = buf.len() - 4;
+    if len > MAX_RECORD_LEN {
+        return Err(invalid_data(format!(
+            "record of {len} bytes exceeds the limit"
+        )));
+    }
+    buf[..4].copy_from_slice(&(len as u32).to_be_bytes());
+    Ok(buf)
+}
+
+/// Write a single record, framed according to the codec.
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) fn write_record<C: Codec, T: Serialize + ?Sized, W: Write>(
+    codec: &C,
+    value: &T,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_all(&encode_record(codec, value)?)
+}
+
+/// Read the next record written by [`write_record`] into `buf`.
+/// Returns `false` at the end of the input.
+#[cfg_attr(not(feature = "recorder"), allow(dead_code))]
+pub(crate) fn read_record<C: Codec, R: io::BufRead>(
+    codec: &C,
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<bool> {
+    buf.clear();
+    if codec.is_line_delimited() {
+        let n = reader.read_until(b'\n', buf)?;
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        }
+        return Ok(n > 0);
+    }
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_RECORD_LEN {
+        return Err(invalid_data(format!(
+            "record of {len} bytes exceeds the limit"
+        )));
+    }
+    buf.resize(len, 0);
+    reader.read_exact(buf)?;
+    Ok(true)
+}
+
+fn invalid_data(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{ActorId, Envelope, Event};
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum TestEvent {
+        Reading { sensor: String, value: f64 },
+        Tick(u64),
+    }
+    impl Event for TestEvent {}
+
+    fn roundtrip<C: Codec>(codec: C) {
+        let events = [
+            Envelope::with_correlation(
+                TestEvent::Reading {
+                    sensor: "t1".into(),
+                    value: 21.5,
+                },
+                ActorId::new(Arc::from("sensor")),
+                7,
+            ),
+            Envelope::new(TestEvent::Tick(3), ActorId::new(Arc::from("clock"))),
+        ];
+
+        let mut out = Vec::new();
+        for envelope in &events {
+            write_record(&codec, envelope, &mut out).unwrap();
+        }
+
+        let mut reader = io::BufReader::new(&out[..]);
+        let mut buf = Vec::new();
+        for envelope in &events {
+            assert!(read_record(&codec, &mut reader, &mut buf).unwrap());
+            let decoded: Envelope<TestEvent> = codec.decode(&buf).unwrap();
+            assert_eq!(decoded.meta(), envelope.meta());
+            assert_eq!(decoded.event(), envelope.event());
+        }
+        assert!(!read_record(&codec, &mut reader, &mut buf).unwrap());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        roundtrip(Json);
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn postcard_roundtrip() {
+        roundtrip(Postcard);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_roundtrip() {
+        roundtrip(MessagePack);
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn postcard_is_smaller_than_json() {
+        let envelope = Envelope::new(TestEvent::Tick(3), ActorId::new(Arc::from("clock")));
+        let json = encode_record(&Json, &envelope).unwrap();
+        let binary = encode_record(&Postcard, &envelope).unwrap();
+        assert!(binary.len() * 2 < json.len());
+    }
+}
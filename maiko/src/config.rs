@@ -1,3 +1,5 @@
+use crate::UndeclaredTopicPolicy;
+
 /// Runtime configuration for the supervisor and actors.
 ///
 /// Controls channel buffer sizes and event batching behavior. Use the builder
@@ -42,6 +44,37 @@ pub struct Config {
     /// Default: 1024
     // TODO rename to monitoring_channel_capacity in 0.3.0 and make private
     pub monitoring_channel_size: usize,
+
+    /// Whether actor `step()` calls are reported to monitors.
+    /// Off by default, as stepping actors can produce far more monitoring
+    /// events than event deliveries.
+    /// Default: false
+    #[cfg_attr(feature = "serde", serde(default))]
+    monitor_steps: bool,
+
+    /// How often the broker samples the queue depth of every actor's
+    /// channels and reports it to monitors. `None` disables sampling.
+    /// Default: None
+    #[cfg_attr(feature = "serde", serde(default))]
+    queue_depth_interval: Option<tokio::time::Duration>,
+
+    /// Whether event producers wait for room in a full monitoring channel
+    /// instead of dropping monitoring events.
+    /// Default: false
+    #[cfg_attr(feature = "serde", serde(default))]
+    blocking_monitoring: bool,
+
+    /// What the broker does with events an actor sends to a topic it
+    /// didn't declare as published.
+    /// Default: Warn
+    #[cfg_attr(feature = "serde", serde(default))]
+    undeclared_topic_policy: UndeclaredTopicPolicy,
+
+    /// Whether [`Supervisor::start`](crate::Supervisor::start) fails when
+    /// [`Supervisor::validate`](crate::Supervisor::validate) finds problems.
+    /// Default: false
+    #[cfg_attr(feature = "serde", serde(default))]
+    validate_on_start: bool,
 }
 
 impl Default for Config {
@@ -52,6 +85,11 @@ impl Default for Config {
             max_events_per_tick: 10,
             maintenance_interval: tokio::time::Duration::from_secs(10),
             monitoring_channel_size: 1024,
+            monitor_steps: false,
+            queue_depth_interval: None,
+            blocking_monitoring: false,
+            undeclared_topic_policy: UndeclaredTopicPolicy::Warn,
+            validate_on_start: false,
         }
     }
 }
@@ -135,6 +173,80 @@ impl Config {
         self.monitoring_channel_size
     }
 
+    /// Report step entry and exit to monitors
+    /// ([`on_step_enter`](crate::monitoring::Monitor::on_step_enter),
+    /// [`on_step_exit_timed`](crate::monitoring::Monitor::on_step_exit_timed),
+    /// [`on_step_error`](crate::monitoring::Monitor::on_step_error) and
+    /// [`on_step_cancel`](crate::monitoring::Monitor::on_step_cancel)).
+    ///
+    /// Leave disabled for actors stepping in a tight loop
+    /// ([`StepAction::Continue`](crate::StepAction::Continue)): every step
+    /// then competes with event deliveries for the monitoring channel.
+    pub fn with_step_monitoring(mut self, enabled: bool) -> Self {
+        self.monitor_steps = enabled;
+        self
+    }
+
+    pub fn step_monitoring(&self) -> bool {
+        self.monitor_steps
+    }
+
+    /// Sample the queue depth of every actor's channels at the given interval
+    /// and report it to monitors
+    /// ([`on_queue_depth`](crate::monitoring::Monitor::on_queue_depth)).
+    ///
+    /// Pass [`maintenance_interval`](Self::maintenance_interval) to sample
+    /// along with the broker's maintenance.
+    pub fn with_queue_depth_sampling(mut self, interval: tokio::time::Duration) -> Self {
+        self.queue_depth_interval = Some(interval);
+        self
+    }
+
+    pub fn queue_depth_interval(&self) -> Option<tokio::time::Duration> {
+        self.queue_depth_interval
+    }
+
+    /// Make actors and the broker wait for room in a full monitoring channel
+    /// instead of dropping monitoring events.
+    ///
+    /// By default monitoring never slows the system down: when monitors fall
+    /// behind, events are dropped and reported with
+    /// [`on_lagged`](crate::monitoring::Monitor::on_lagged). Blocking suits
+    /// tests, where a complete record matters more than latency. Everything
+    /// reported through the channel waits, including actor registration and
+    /// the replay of retained events to actors added at runtime.
+    pub fn with_blocking_monitoring(mut self, enabled: bool) -> Self {
+        self.blocking_monitoring = enabled;
+        self
+    }
+
+    pub fn blocking_monitoring(&self) -> bool {
+        self.blocking_monitoring
+    }
+
+    /// Set what the broker does when an actor sends an event to a topic it
+    /// didn't declare with [`ActorBuilder::publishes`](crate::ActorBuilder::publishes).
+    pub fn with_undeclared_topic_policy(mut self, policy: UndeclaredTopicPolicy) -> Self {
+        self.undeclared_topic_policy = policy;
+        self
+    }
+
+    pub fn undeclared_topic_policy(&self) -> UndeclaredTopicPolicy {
+        self.undeclared_topic_policy
+    }
+
+    /// Make [`Supervisor::start`](crate::Supervisor::start) return
+    /// [`Error::InvalidTopology`](crate::Error::InvalidTopology) when the
+    /// declared topology has problems.
+    pub fn with_validation_on_start(mut self, enabled: bool) -> Self {
+        self.validate_on_start = enabled;
+        self
+    }
+
+    pub fn validate_on_start(&self) -> bool {
+        self.validate_on_start
+    }
+
     pub fn with_default_actor_channel_capacity(mut self, capacity: usize) -> Self {
         self.default_actor_channel_capacity = capacity;
         self
@@ -7,8 +7,13 @@ use std::{
 };
 
 use tokio::sync::mpsc::Sender;
+use tokio_util::sync::CancellationToken;
+use tracing::Span;
 
-use crate::{ActorId, Envelope, EventId, Meta, Result};
+use crate::{
+    ActorId, Envelope, Event, EventId, Meta, Result,
+    internal::{ContextSender, Widening},
+};
 
 /// Runtime-provided context for an actor to interact with the system.
 ///
@@ -22,12 +27,20 @@ use crate::{ActorId, Envelope, EventId, Meta, Result};
 /// - `send_with_correlation(event, id)`: emit an event linked to a specific correlation id.
 /// - `send_child_event(event, meta)`: convenience to set correlation id to the parent `meta.id()`.
 ///
+/// Correlated sends also carry the current `tracing` span, so the span in which
+/// the receiving actor handles the event is a child of the sender's span.
+///
+/// Narrowing:
+/// - `narrow()`: derive a context for an actor with a narrower event type
+///   (see [`NarrowActor`](crate::NarrowActor)).
+///
 /// See also: [`Envelope`], [`Meta`], [`crate::Supervisor`].
 #[derive(Clone)]
 pub struct Context<E> {
     pub(crate) actor_id: ActorId,
-    pub(crate) sender: Sender<Arc<Envelope<E>>>,
+    pub(crate) sender: ContextSender<E>,
     pub(crate) alive: Arc<AtomicBool>,
+    pub(crate) shutdown: Option<Arc<CancellationToken>>,
 }
 
 impl<E> Context<E> {
@@ -38,8 +51,27 @@ impl<E> Context<E> {
     ) -> Self {
         Self {
             actor_id,
-            sender,
+            sender: ContextSender::Direct(sender),
             alive,
+            shutdown: None,
+        }
+    }
+
+    /// Attach the supervisor's shutdown token.
+    pub(crate) fn with_shutdown(mut self, token: Arc<CancellationToken>) -> Self {
+        self.shutdown = Some(token);
+        self
+    }
+
+    /// Completes when the supervisor begins shutting down actors.
+    ///
+    /// Lets long waits inside `handle_event` give up instead of blocking
+    /// shutdown. Never completes for contexts created outside a supervisor.
+    #[cfg_attr(not(feature = "bridge"), allow(dead_code))]
+    pub(crate) async fn shutdown_requested(&self) {
+        match &self.shutdown {
+            Some(token) => token.cancelled().await,
+            None => std::future::pending().await,
         }
     }
 
@@ -56,28 +88,22 @@ impl<E> Context<E> {
         T: Into<E>,
         ID: Into<EventId>,
     {
-        self.send_envelope(Envelope::with_correlation(
-            event.into(),
-            self.actor_id.clone(),
-            correlation_id.into(),
-        ))
-        .await
+        let envelope =
+            Envelope::with_correlation(event.into(), self.actor_id.clone(), correlation_id.into());
+        self.send_envelope(envelope.with_span(Span::current()))
+            .await
     }
 
     /// Emit a child event correlated to the given parent `Meta`.
     pub async fn send_child_event<T: Into<E>>(&self, event: T, meta: &Meta) -> Result<()> {
-        self.send_envelope(Envelope::with_correlation(
-            event.into(),
-            self.actor_id.clone(),
-            meta.id(),
-        ))
-        .await
+        let envelope = Envelope::with_correlation(event.into(), self.actor_id.clone(), meta.id());
+        self.send_envelope(envelope.with_span(Span::current()))
+            .await
     }
 
     #[inline]
     pub async fn send_envelope<T: Into<Envelope<E>>>(&self, envelope: T) -> Result<()> {
-        self.sender.send(Arc::new(envelope.into())).await?;
-        Ok(())
+        self.sender.send(envelope.into()).await
     }
 
     /// Signal this actor to stop
@@ -141,7 +167,28 @@ impl<E> Context<E> {
     /// global system pressure or subscriber-side congestion (stage 2).
     #[inline]
     pub fn is_sender_full(&self) -> bool {
-        self.sender.capacity() == 0
+        self.sender.is_full()
+    }
+}
+
+impl<E: Event> Context<E> {
+    /// Derive a context for an actor whose event type `S` is a subset of `E`.
+    ///
+    /// The returned context shares this actor's identity and liveness flag.
+    /// Events sent through it are converted into `E` (keeping their metadata)
+    /// before they reach the broker. Normally you don't call this directly -
+    /// [`Supervisor::add_narrow_actor`](crate::Supervisor::add_narrow_actor)
+    /// does it for you.
+    pub fn narrow<S>(&self) -> Context<S>
+    where
+        S: Event + Into<E>,
+    {
+        Context {
+            actor_id: self.actor_id.clone(),
+            sender: ContextSender::Widened(Arc::new(Widening(self.sender.clone()))),
+            alive: self.alive.clone(),
+            shutdown: self.shutdown.clone(),
+        }
     }
 }
 
@@ -1,5 +1,7 @@
 use std::{fmt, hash, ops};
 
+use tracing::Span;
+
 use crate::{ActorId, Event, EventId, Meta};
 
 /// Event plus metadata used by the broker for routing and observability.
@@ -7,6 +9,10 @@ use crate::{ActorId, Event, EventId, Meta};
 /// - `event`: the user-defined payload implementing `Event`.
 /// - `meta`: `Meta` describing who emitted the event and when.
 ///   Includes `actor_name` and optional `correlation_id` for linking related events.
+///
+/// Child events also carry the `tracing` span they were sent from, so the
+/// span of the actor handling them nests under it (see [`Envelope::span`]).
+/// The span stays in the process: it is not serialized, compared or hashed.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -19,6 +25,8 @@ use crate::{ActorId, Event, EventId, Meta};
 pub struct Envelope<E> {
     meta: Meta,
     event: E,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Span::none"))]
+    span: Span,
 }
 
 impl<E> Envelope<E> {
@@ -27,6 +35,7 @@ impl<E> Envelope<E> {
         Self {
             meta: Meta::new(actor_id, None),
             event,
+            span: Span::none(),
         }
     }
 
@@ -37,9 +46,39 @@ impl<E> Envelope<E> {
         Self {
             meta: Meta::new(actor_id, Some(correlation_id)),
             event,
+            span: Span::none(),
         }
     }
 
+    /// Create an envelope carrying existing metadata.
+    pub(crate) fn with_meta(event: E, meta: Meta) -> Self {
+        Self {
+            meta,
+            event,
+            span: Span::none(),
+        }
+    }
+
+    /// Attach the span the envelope is sent from.
+    pub(crate) fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Copy of the envelope without its span, for envelopes kept after
+    /// handling: a stored span would stay open as long as the copy lives.
+    pub(crate) fn without_span(&self) -> Self
+    where
+        E: Clone,
+    {
+        Self::with_meta(self.event.clone(), self.meta.clone())
+    }
+
+    /// Split the envelope into its payload, metadata and span.
+    pub(crate) fn into_parts(self) -> (E, Meta, Span) {
+        (self.event, self.meta, self.span)
+    }
+
     /// Returns a reference to the event payload.
     ///
     /// This is a convenience method for pattern matching. For method calls,
@@ -67,6 +106,17 @@ impl<E> Envelope<E> {
     pub fn id(&self) -> EventId {
         self.meta.id()
     }
+
+    /// The `tracing` span the event was sent from, parent of the spans in
+    /// which actors handle it.
+    ///
+    /// Set by [`Context::send_child_event`](crate::Context::send_child_event) and
+    /// [`Context::send_with_correlation`](crate::Context::send_with_correlation);
+    /// [`Span::none`] for other events, whose handling spans are roots.
+    #[inline]
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
 }
 
 impl<E: Event> From<(&E, &Meta)> for Envelope<E> {
@@ -74,6 +124,7 @@ impl<E: Event> From<(&E, &Meta)> for Envelope<E> {
         Envelope::<E> {
             meta: meta.clone(),
             event: event.clone(),
+            span: Span::none(),
         }
     }
 }
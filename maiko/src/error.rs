@@ -18,6 +18,9 @@ pub enum Error {
     #[error("Broker has already started.")]
     BrokerAlreadyStarted,
 
+    #[error("Supervisor has been stopped.")]
+    SupervisorStopped,
+
     #[error("The message channel has reached its capacity.")]
     ChannelIsFull,
 
@@ -30,6 +33,12 @@ pub enum Error {
     #[error("IO Error: {0}")]
     IOError(#[from] std::io::Error),
 
+    #[error("Couldn't encode or decode an event: {0}")]
+    Codec(Arc<str>),
+
+    #[error("Topology validation failed: {0}")]
+    InvalidTopology(Arc<str>),
+
     #[cfg(feature = "test-harness")]
     #[error("settle_on condition not met within {0:?}: {1} events recorded")]
     SettleTimeout(std::time::Duration, usize),
@@ -0,0 +1,494 @@
+//! Append-only persistent log of envelopes.
+//!
+//! An [`EventLog`] stores envelopes in a directory of segment files. Every
+//! record gets a sequential offset, starting at 0, that stays valid for the
+//! lifetime of the log, so a consumer can remember the last offset it
+//! processed and resume after it with [`EventLog::read_from`]. Records can
+//! also be located by [`EventId`] and by timestamp.
+//!
+//! On disk, each segment is a pair of files named after the offset of its
+//! first record:
+//!
+//! - `<offset>.log` - records: a `u32` length and a CRC32 of the payload
+//!   (both big-endian), followed by the envelope encoded with the log's
+//!   [`Codec`].
+//! - `<offset>.idx` - a fixed-size entry (offset, file position, timestamp
+//!   and event id) per record.
+//!
+//! When a log is opened, the newest segment is verified record by record and
+//! truncated after the last intact one, so a crash in the middle of a write
+//! never leaves a corrupted tail. Durability beyond a process crash is
+//! controlled by the [`FsyncPolicy`], disk usage by retention limits in
+//! [`LogConfig`].
+//!
+//! To persist selected topics, register a [`LogWriter`] actor for them.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let log = EventLog::open("data/orders", LogConfig::default().with_fsync(FsyncPolicy::Always))?;
+//! sup.add_actor("order-log", |_| LogWriter::new(log), &[Topic::Orders])?;
+//!
+//! // after a restart
+//! let log = EventLog::<Event>::open("data/orders", LogConfig::default())?;
+//! for record in log.read_from(last_processed + 1)? {
+//!     let (offset, envelope) = record?;
+//!     // ...
+//! }
+//! ```
+
+mod config;
+mod reader;
+mod segment;
+mod writer;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, File},
+    io::{self, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+pub use config::{FsyncPolicy, LogConfig};
+pub use reader::LogReader;
+pub use writer::LogWriter;
+
+use reader::Span;
+use segment::{IndexEntry, Segment, encode_record};
+
+use crate::{
+    Envelope, EventId,
+    codec::{Codec, Json},
+};
+
+/// Segmented, append-only log of envelopes with an index by offset,
+/// [`EventId`] and timestamp. See the [module documentation](self).
+///
+/// Only one `EventLog` may be open on a directory at a time.
+pub struct EventLog<E, C = Json> {
+    dir: PathBuf,
+    config: LogConfig,
+    codec: C,
+    segments: Vec<Segment>,
+    log_file: File,
+    index_file: File,
+    ids: HashMap<EventId, u64>,
+    unsynced: u32,
+    last_sync: Instant,
+    buf: Vec<u8>,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E> EventLog<E>
+where
+    E: Serialize + DeserializeOwned,
+{
+    /// Open (or create) a JSON-encoded log in `dir`.
+    pub fn open<P: AsRef<Path>>(dir: P, config: LogConfig) -> io::Result<Self> {
+        Self::open_with_codec(dir, config, Json)
+    }
+}
+
+impl<E, C> EventLog<E, C>
+where
+    E: Serialize + DeserializeOwned,
+    C: Codec,
+{
+    /// Open (or create) a log in `dir` with records encoded by `codec`.
+    ///
+    /// A log must always be opened with the codec it was written with.
+    pub fn open_with_codec<P: AsRef<Path>>(
+        dir: P,
+        config: LogConfig,
+        codec: C,
+    ) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut bases = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "log") {
+                if let Some(base) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse::<u64>().ok())
+                {
+                    bases.push(base);
+                }
+            }
+        }
+        bases.sort_unstable();
+
+        let mut segments = Vec::with_capacity(bases.len().max(1));
+        if let Some((&active, sealed)) = bases.split_last() {
+            for &base in sealed {
+                segments.push(Segment::load::<E, C>(&dir, base, &codec)?);
+            }
+            segments.push(Segment::recover::<E, C>(&dir, active, &codec)?);
+        } else {
+            File::create(Segment::log_path(&dir, 0))?;
+            File::create(Segment::index_path(&dir, 0))?;
+            segments.push(Segment::new(0));
+        }
+
+        let ids = segments
+            .iter()
+            .flat_map(|segment| &segment.entries)
+            .map(|entry| (entry.id, entry.offset))
+            .collect();
+        let base = segments.last().map_or(0, |segment| segment.base);
+        let (log_file, index_file) = Self::open_files(&dir, base)?;
+
+        let mut log = Self {
+            dir,
+            config,
+            codec,
+            segments,
+            log_file,
+            index_file,
+            ids,
+            unsynced: 0,
+            last_sync: Instant::now(),
+            buf: Vec::new(),
+            _event: PhantomData,
+        };
+        log.apply_retention()?;
+        Ok(log)
+    }
+
+    fn open_files(dir: &Path, base: u64) -> io::Result<(File, File)> {
+        let mut options = File::options();
+        options.create(true).append(true);
+        Ok((
+            options.open(Segment::log_path(dir, base))?,
+            options.open(Segment::index_path(dir, base))?,
+        ))
+    }
+
+    /// Append an envelope and return its offset.
+    ///
+    /// The record is passed to the operating system before this returns;
+    /// whether it is also synced to disk depends on the [`FsyncPolicy`].
+    pub fn append(&mut self, envelope: &Envelope<E>) -> io::Result<u64> {
+        encode_record(&self.codec, envelope, &mut self.buf)?;
+        let len = self.buf.len() as u64;
+        if self.active().size > 0 && self.active().size + len > self.config.segment_size() {
+            self.roll()?;
+        }
+
+        let offset = self.next_offset();
+        let entry = IndexEntry {
+            offset,
+            position: self.active().size,
+            timestamp: envelope.meta().timestamp(),
+            id: envelope.id(),
+        };
+        self.log_file.write_all(&self.buf)?;
+        entry.write_to(&mut self.index_file)?;
+
+        let active = self.active_mut();
+        active.size += len;
+        active.entries.push(entry);
+        self.ids.insert(entry.id, offset);
+        self.unsynced += 1;
+
+        let sync = match self.config.fsync() {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::EveryN(n) => self.unsynced >= n,
+            FsyncPolicy::Interval(interval) => self.last_sync.elapsed() >= interval,
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.sync()?;
+        }
+        Ok(offset)
+    }
+
+    /// Force all appended records to disk.
+    pub fn sync(&mut self) -> io::Result<()> {
+        if self.unsynced > 0 {
+            self.log_file.sync_data()?;
+            self.index_file.sync_data()?;
+            self.unsynced = 0;
+        }
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    /// When unsynced records are due to be synced under
+    /// [`FsyncPolicy::Interval`]. `None` if nothing is waiting for a sync.
+    pub fn sync_deadline(&self) -> Option<Instant> {
+        match self.config.fsync() {
+            FsyncPolicy::Interval(interval) if self.unsynced > 0 => Some(self.last_sync + interval),
+            _ => None,
+        }
+    }
+
+    /// Seal the active segment and start a new one.
+    fn roll(&mut self) -> io::Result<()> {
+        self.log_file.sync_all()?;
+        self.index_file.sync_all()?;
+        self.unsynced = 0;
+
+        let base = self.next_offset();
+        let (log_file, index_file) = Self::open_files(&self.dir, base)?;
+        self.log_file = log_file;
+        self.index_file = index_file;
+        self.segments.push(Segment::new(base));
+        self.apply_retention()
+    }
+
+    /// Remove the oldest sealed segments exceeding the retention limits.
+    ///
+    /// Called whenever a segment is sealed and when the log is opened. The
+    /// active segment is never removed.
+    pub fn apply_retention(&mut self) -> io::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let mut total: u64 = self.segments.iter().map(|segment| segment.size).sum();
+        while self.segments.len() > 1 {
+            let oldest = &self.segments[0];
+            let too_big = self.config.retention_size().is_some_and(|max| total > max);
+            let too_old = self.config.retention_age().is_some_and(|age| {
+                oldest
+                    .entries
+                    .last()
+                    .is_none_or(|entry| now.saturating_sub(entry.timestamp) > age.as_nanos() as u64)
+            });
+            if !too_big && !too_old {
+                break;
+            }
+            oldest.remove(&self.dir)?;
+            for entry in &oldest.entries {
+                self.ids.remove(&entry.id);
+            }
+            total -= oldest.size;
+            tracing::debug!(segment=%oldest.base, "event log segment removed by retention");
+            self.segments.remove(0);
+        }
+        Ok(())
+    }
+
+    /// Read records starting at `offset`, in offset order.
+    ///
+    /// If `offset` was already removed by retention, reading starts at the
+    /// oldest retained record; check the offsets returned by the reader if
+    /// that matters. The reader covers records appended so far.
+    pub fn read_from(&self, offset: u64) -> io::Result<LogReader<E, C>> {
+        let spans: VecDeque<Span> = self
+            .segments
+            .iter()
+            .filter(|segment| segment.end() > offset)
+            .map(|segment| {
+                let start = offset.max(segment.base);
+                Span {
+                    path: Segment::log_path(&self.dir, segment.base),
+                    start,
+                    position: segment.entries[(start - segment.base) as usize].position,
+                    end: segment.end(),
+                }
+            })
+            .collect();
+        Ok(LogReader::new(self.codec.clone(), spans))
+    }
+
+    /// Read the record at `offset`.
+    pub fn get(&self, offset: u64) -> io::Result<Option<Envelope<E>>> {
+        if offset < self.first_offset() {
+            return Ok(None);
+        }
+        self.read_from(offset)?
+            .next()
+            .transpose()
+            .map(|record| record.map(|(_, envelope)| envelope))
+    }
+
+    /// Offset of the event with the given id, if it's retained.
+    pub fn offset_of(&self, id: EventId) -> Option<u64> {
+        self.ids.get(&id).copied()
+    }
+
+    /// Offset of the first record with a timestamp (nanoseconds since Unix
+    /// epoch, see [`Meta::timestamp`](crate::Meta::timestamp)) at or after
+    /// `timestamp`.
+    pub fn offset_at(&self, timestamp: u64) -> Option<u64> {
+        self.segments
+            .iter()
+            .flat_map(|segment| &segment.entries)
+            .find(|entry| entry.timestamp >= timestamp)
+            .map(|entry| entry.offset)
+    }
+
+    /// Offset of the oldest retained record.
+    pub fn first_offset(&self) -> u64 {
+        self.segments.first().map_or(0, |segment| segment.base)
+    }
+
+    /// Offset the next appended record will get.
+    pub fn next_offset(&self) -> u64 {
+        self.active().end()
+    }
+
+    /// Number of retained records.
+    pub fn len(&self) -> u64 {
+        self.next_offset() - self.first_offset()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total size of the retained segment files in bytes (without indexes).
+    pub fn size(&self) -> u64 {
+        self.segments.iter().map(|segment| segment.size).sum()
+    }
+
+    /// Number of segment files, including the active one.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn active(&self) -> &Segment {
+        self.segments
+            .last()
+            .expect("event log has an active segment")
+    }
+
+    fn active_mut(&mut self) -> &mut Segment {
+        self.segments
+            .last_mut()
+            .expect("event log has an active segment")
+    }
+}
+
+impl<E, C> Drop for EventLog<E, C> {
+    fn drop(&mut self) {
+        if self.unsynced > 0 {
+            let _ = self.log_file.sync_data();
+            let _ = self.index_file.sync_data();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{ActorId, Event};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Tick(u64);
+    impl Event for Tick {}
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("maiko_{name}_{}", uuid::Uuid::new_v4()));
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn envelope(n: u64) -> Envelope<Tick> {
+        Envelope::new(Tick(n), ActorId::new(Arc::from("clock")))
+    }
+
+    fn ticks(log: &EventLog<Tick>, offset: u64) -> Vec<(u64, u64)> {
+        log.read_from(offset)
+            .unwrap()
+            .map(|record| record.map(|(offset, e)| (offset, e.event().0)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn appends_survive_reopen_across_segments() {
+        let dir = TempDir::new("event_log_reopen");
+        let config = LogConfig::default().with_segment_size(200);
+        let envelopes: Vec<_> = (0..10).map(envelope).collect();
+        {
+            let mut log = EventLog::open(&dir.0, config.clone()).unwrap();
+            for (i, envelope) in envelopes.iter().enumerate() {
+                assert_eq!(log.append(envelope).unwrap(), i as u64);
+            }
+            assert!(log.segment_count() > 1);
+        }
+
+        let mut log = EventLog::<Tick>::open(&dir.0, config).unwrap();
+        assert_eq!(log.next_offset(), 10);
+        assert_eq!(ticks(&log, 7), vec![(7, 7), (8, 8), (9, 9)]);
+        assert_eq!(log.offset_of(envelopes[4].id()), Some(4));
+        assert_eq!(log.offset_at(envelopes[6].meta().timestamp()), Some(6));
+        assert_eq!(log.get(3).unwrap().unwrap().id(), envelopes[3].id());
+
+        assert_eq!(log.append(&envelope(10)).unwrap(), 10);
+        assert_eq!(ticks(&log, 9), vec![(9, 9), (10, 10)]);
+    }
+
+    #[test]
+    fn torn_tail_is_truncated_on_open() {
+        let dir = TempDir::new("event_log_torn");
+        {
+            let mut log = EventLog::open(&dir.0, LogConfig::default()).unwrap();
+            for n in 0..3 {
+                log.append(&envelope(n)).unwrap();
+            }
+        }
+        let path = Segment::log_path(&dir.0, 0);
+        let mut file = File::options().append(true).open(&path).unwrap();
+        file.write_all(&[0, 0, 0, 50, 1, 2, 3]).unwrap();
+        drop(file);
+
+        let mut log = EventLog::<Tick>::open(&dir.0, LogConfig::default()).unwrap();
+        assert_eq!(log.next_offset(), 3);
+        assert_eq!(log.append(&envelope(3)).unwrap(), 3);
+        assert_eq!(ticks(&log, 0).len(), 4);
+    }
+
+    #[test]
+    fn retention_removes_oldest_segments() {
+        let dir = TempDir::new("event_log_retention");
+        let config = LogConfig::default()
+            .with_segment_size(200)
+            .with_retention_size(400);
+        let mut log = EventLog::open(&dir.0, config).unwrap();
+        let first = envelope(0);
+        log.append(&first).unwrap();
+        for n in 1..30 {
+            log.append(&envelope(n)).unwrap();
+        }
+
+        assert!(log.first_offset() > 0);
+        assert!(log.size() <= 400 + 200);
+        assert_eq!(log.offset_of(first.id()), None);
+        assert_eq!(log.get(0).unwrap(), None);
+        let read = ticks(&log, 0);
+        assert_eq!(read.first().unwrap().0, log.first_offset());
+        assert_eq!(read.last().unwrap(), &(29, 29));
+
+        let stale = LogConfig::default()
+            .with_segment_size(200)
+            .with_retention_age(Duration::ZERO);
+        drop(log);
+        let log = EventLog::<Tick>::open(&dir.0, stale).unwrap();
+        assert_eq!(log.segment_count(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+/// When the [`EventLog`](super::EventLog) forces appended records to disk.
+///
+/// Records are handed to the operating system on every append, so they
+/// survive a crash of the process. `fsync` additionally protects them
+/// against power loss or a kernel crash, at the cost of latency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FsyncPolicy {
+    /// Sync after every appended record. Slowest, nothing is ever lost.
+    Always,
+    /// Sync after every `n` records.
+    EveryN(u32),
+    /// Sync when the given time has passed since the last sync. The
+    /// [`LogWriter`](super::LogWriter) also syncs when the log goes quiet.
+    Interval(Duration),
+    /// Never sync explicitly and leave it to the operating system
+    /// (segments are still synced when they are sealed).
+    Never,
+}
+
+/// Configuration of an [`EventLog`](super::EventLog).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use maiko::event_log::{FsyncPolicy, LogConfig};
+///
+/// let config = LogConfig::default()
+///     .with_segment_size(16 * 1024 * 1024)
+///     .with_fsync(FsyncPolicy::Always)
+///     .with_retention_size(1024 * 1024 * 1024)
+///     .with_retention_age(Duration::from_secs(7 * 24 * 3600));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogConfig {
+    segment_size: u64,
+    fsync: FsyncPolicy,
+    retention_size: Option<u64>,
+    retention_age: Option<Duration>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            segment_size: 64 * 1024 * 1024,
+            fsync: FsyncPolicy::Interval(Duration::from_secs(1)),
+            retention_size: None,
+            retention_age: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Size in bytes after which the active segment is sealed and a new one
+    /// started. A single record larger than this gets a segment of its own.
+    /// Default: 64 MiB
+    pub fn with_segment_size(mut self, bytes: u64) -> Self {
+        self.segment_size = bytes.max(1);
+        self
+    }
+
+    pub fn segment_size(&self) -> u64 {
+        self.segment_size
+    }
+
+    /// Default: [`FsyncPolicy::Interval`] of one second.
+    pub fn with_fsync(mut self, policy: FsyncPolicy) -> Self {
+        self.fsync = policy;
+        self
+    }
+
+    pub fn fsync(&self) -> FsyncPolicy {
+        self.fsync
+    }
+
+    /// Remove the oldest segments while the log takes more than `bytes`.
+    /// Default: unlimited
+    pub fn with_retention_size(mut self, bytes: u64) -> Self {
+        self.retention_size = Some(bytes);
+        self
+    }
+
+    pub fn retention_size(&self) -> Option<u64> {
+        self.retention_size
+    }
+
+    /// Remove segments whose newest record is older than `age`.
+    /// Default: unlimited
+    pub fn with_retention_age(mut self, age: Duration) -> Self {
+        self.retention_age = Some(age);
+        self
+    }
+
+    pub fn retention_age(&self) -> Option<Duration> {
+        self.retention_age
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufReader, Seek, SeekFrom},
+    marker::PhantomData,
+    path::PathBuf,
+};
+
+use serde::de::DeserializeOwned;
+
+use super::segment::read_record;
+use crate::{Envelope, codec::Codec};
+
+/// Part of a segment a [`LogReader`] still has to read.
+#[derive(Debug)]
+pub(crate) struct Span {
+    pub path: PathBuf,
+    pub start: u64,
+    pub position: u64,
+    pub end: u64,
+}
+
+/// Iterator over `(offset, envelope)` pairs of an [`EventLog`](super::EventLog),
+/// created with [`EventLog::read_from`](super::EventLog::read_from).
+///
+/// The reader covers the records that existed when it was created.
+pub struct LogReader<E, C> {
+    codec: C,
+    spans: VecDeque<Span>,
+    current: Option<(BufReader<File>, u64, u64)>,
+    buf: Vec<u8>,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E, C> LogReader<E, C>
+where
+    E: DeserializeOwned,
+    C: Codec,
+{
+    pub(crate) fn new(codec: C, spans: VecDeque<Span>) -> Self {
+        Self {
+            codec,
+            spans,
+            current: None,
+            buf: Vec::new(),
+            _event: PhantomData,
+        }
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<(u64, Envelope<E>)>> {
+        loop {
+            if let Some((reader, offset, _)) = self.current.as_mut().filter(|c| c.1 < c.2) {
+                if read_record(reader, &mut self.buf)?.is_none() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("event log segment ends before offset {offset}"),
+                    ));
+                }
+                let envelope = self.codec.decode(&self.buf)?;
+                *offset += 1;
+                return Ok(Some((*offset - 1, envelope)));
+            }
+            let Some(span) = self.spans.pop_front() else {
+                self.current = None;
+                return Ok(None);
+            };
+            let mut file = File::open(&span.path)?;
+            file.seek(SeekFrom::Start(span.position))?;
+            self.current = Some((BufReader::new(file), span.start, span.end));
+        }
+    }
+}
+
+impl<E, C> Iterator for LogReader<E, C>
+where
+    E: DeserializeOwned,
+    C: Codec,
+{
+    type Item = io::Result<(u64, Envelope<E>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_record() {
+            Ok(record) => record.map(Ok),
+            Err(e) => {
+                // Stop after an error instead of reading garbage.
+                self.spans.clear();
+                self.current = None;
+                Some(Err(e))
+            }
+        }
+    }
+}
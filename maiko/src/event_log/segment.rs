@@ -0,0 +1,228 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    Envelope, EventId,
+    codec::{Codec, MAX_RECORD_LEN},
+};
+
+/// Size of a record header: payload length and CRC32 of the payload.
+const HEADER_LEN: usize = 8;
+
+/// Size of a single index entry on disk.
+const INDEX_ENTRY_LEN: usize = 40;
+
+/// Position and identity of a record, as stored in a segment's index file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexEntry {
+    pub offset: u64,
+    pub position: u64,
+    pub timestamp: u64,
+    pub id: EventId,
+}
+
+impl IndexEntry {
+    fn to_bytes(self) -> [u8; INDEX_ENTRY_LEN] {
+        let mut bytes = [0; INDEX_ENTRY_LEN];
+        bytes[..8].copy_from_slice(&self.offset.to_be_bytes());
+        bytes[8..16].copy_from_slice(&self.position.to_be_bytes());
+        bytes[16..24].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[24..].copy_from_slice(&self.id.to_be_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let u64_at = |i: usize| u64::from_be_bytes(bytes[i..i + 8].try_into().unwrap_or_default());
+        Self {
+            offset: u64_at(0),
+            position: u64_at(8),
+            timestamp: u64_at(16),
+            id: u128::from_be_bytes(bytes[24..40].try_into().unwrap_or_default()),
+        }
+    }
+
+    pub fn write_to<W: Write>(self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// A segment file together with its in-memory index.
+#[derive(Debug)]
+pub(crate) struct Segment {
+    pub base: u64,
+    pub size: u64,
+    pub entries: Vec<IndexEntry>,
+}
+
+impl Segment {
+    pub fn new(base: u64) -> Self {
+        Self {
+            base,
+            size: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Offset the next record appended to this segment gets.
+    pub fn end(&self) -> u64 {
+        self.base + self.entries.len() as u64
+    }
+
+    pub fn log_path(dir: &Path, base: u64) -> PathBuf {
+        dir.join(format!("{base:020}.log"))
+    }
+
+    pub fn index_path(dir: &Path, base: u64) -> PathBuf {
+        dir.join(format!("{base:020}.idx"))
+    }
+
+    /// Load a sealed segment, using its index file when it's intact.
+    pub fn load<E, C>(dir: &Path, base: u64, codec: &C) -> io::Result<Self>
+    where
+        E: DeserializeOwned,
+        C: Codec,
+    {
+        let size = fs::metadata(Self::log_path(dir, base))?.len();
+        match fs::read(Self::index_path(dir, base)) {
+            Ok(bytes) if bytes.len() % INDEX_ENTRY_LEN == 0 => {
+                let entries: Vec<IndexEntry> = bytes
+                    .chunks_exact(INDEX_ENTRY_LEN)
+                    .map(IndexEntry::from_bytes)
+                    .collect();
+                let consistent = entries
+                    .iter()
+                    .enumerate()
+                    .all(|(i, entry)| entry.offset == base + i as u64 && entry.position < size);
+                if consistent {
+                    return Ok(Self {
+                        base,
+                        size,
+                        entries,
+                    });
+                }
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        tracing::warn!(segment=%base, "event log index is damaged, rebuilding it");
+        Self::recover::<E, C>(dir, base, codec)
+    }
+
+    /// Scan the segment file, truncate it after the last valid record
+    /// (e.g. one torn by a crash) and rewrite its index.
+    pub fn recover<E, C>(dir: &Path, base: u64, codec: &C) -> io::Result<Self>
+    where
+        E: DeserializeOwned,
+        C: Codec,
+    {
+        let log_path = Self::log_path(dir, base);
+        let mut reader = BufReader::new(File::open(&log_path)?);
+        let mut segment = Self::new(base);
+        let mut buf = Vec::new();
+        loop {
+            let len = match read_record(&mut reader, &mut buf) {
+                Ok(Some(len)) => len,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!(segment=%base, position=%segment.size, error=%e, "truncating event log after the last valid record");
+                    break;
+                }
+            };
+            let envelope: Envelope<E> = match codec.decode(&buf) {
+                Ok(envelope) => envelope,
+                Err(e) => {
+                    tracing::warn!(segment=%base, position=%segment.size, error=%e, "truncating event log after the last valid record");
+                    break;
+                }
+            };
+            segment.entries.push(IndexEntry {
+                offset: segment.end(),
+                position: segment.size,
+                timestamp: envelope.meta().timestamp(),
+                id: envelope.id(),
+            });
+            segment.size += len;
+        }
+
+        let file = File::options().write(true).open(&log_path)?;
+        if file.metadata()?.len() != segment.size {
+            file.set_len(segment.size)?;
+            file.sync_all()?;
+        }
+
+        let mut index = io::BufWriter::new(File::create(Self::index_path(dir, base))?);
+        for entry in &segment.entries {
+            entry.write_to(&mut index)?;
+        }
+        index.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        Ok(segment)
+    }
+
+    /// Delete the segment's files.
+    pub fn remove(&self, dir: &Path) -> io::Result<()> {
+        fs::remove_file(Self::log_path(dir, self.base))?;
+        match fs::remove_file(Self::index_path(dir, self.base)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Encode an envelope as a record: `u32` payload length, CRC32 of the
+/// payload (both big-endian), then the payload.
+pub(crate) fn encode_record<C: Codec, T: Serialize>(
+    codec: &C,
+    value: &T,
+    buf: &mut Vec<u8>,
+) -> io::Result<()> {
+    buf.clear();
+    buf.extend_from_slice(&[0; HEADER_LEN]);
+    codec.encode(value, buf)?;
+    let len = buf.len() - HEADER_LEN;
+    if len > MAX_RECORD_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("record of {len} bytes exceeds the limit"),
+        ));
+    }
+    let crc = crc32fast::hash(&buf[HEADER_LEN..]);
+    buf[..4].copy_from_slice(&(len as u32).to_be_bytes());
+    buf[4..HEADER_LEN].copy_from_slice(&crc.to_be_bytes());
+    Ok(())
+}
+
+/// Read the next record's payload into `buf`, returning the record's total
+/// size, or `None` at a clean end of the segment.
+pub(crate) fn read_record<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<Option<u64>> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    let mut header = [0; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let crc = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+    if len > MAX_RECORD_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("record of {len} bytes exceeds the limit"),
+        ));
+    }
+    buf.resize(len, 0);
+    reader.read_exact(buf)?;
+    if crc32fast::hash(buf) != crc {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "record checksum mismatch",
+        ));
+    }
+    Ok(Some((HEADER_LEN + len) as u64))
+}
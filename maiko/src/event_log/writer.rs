@@ -0,0 +1,105 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::EventLog;
+use crate::{Actor, Envelope, Event, Result, StepAction, codec::Codec};
+
+/// Actor appending every event it receives to an [`EventLog`].
+///
+/// Register it with the topics that need to be persisted. Combine it with
+/// [`OverflowPolicy::Block`](crate::OverflowPolicy::Block) for those topics,
+/// otherwise events may be dropped before they reach the log.
+///
+/// The writer syncs the log on shutdown and, with
+/// [`FsyncPolicy::Interval`](super::FsyncPolicy::Interval), once the
+/// interval passes after the last unsynced append. A failed append stops
+/// the actor.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let log = EventLog::open("data/orders", LogConfig::default())?;
+/// sup.add_actor("order-log", |_| LogWriter::new(log), &[Topic::Orders])?;
+/// ```
+pub struct LogWriter<E, C> {
+    log: EventLog<E, C>,
+}
+
+impl<E, C> LogWriter<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    pub fn new(log: EventLog<E, C>) -> Self {
+        Self { log }
+    }
+
+    /// The underlying log, e.g. to look up offsets.
+    pub fn log(&self) -> &EventLog<E, C> {
+        &self.log
+    }
+}
+
+impl<E, C> Actor for LogWriter<E, C>
+where
+    E: Event + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    type Event = E;
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        self.log.append(envelope)?;
+        Ok(())
+    }
+
+    async fn step(&mut self) -> Result<StepAction> {
+        let Some(deadline) = self.log.sync_deadline() else {
+            return Ok(StepAction::AwaitEvent);
+        };
+        tokio::time::sleep_until(deadline.into()).await;
+        self.log.sync()?;
+        Ok(StepAction::AwaitEvent)
+    }
+
+    async fn on_shutdown(&mut self) -> Result<()> {
+        self.log.sync()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{DefaultTopic, Supervisor, event_log::LogConfig};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Order(u32);
+    impl Event for Order {}
+
+    #[tokio::test(start_paused = true)]
+    async fn writer_persists_subscribed_events() {
+        let dir = std::env::temp_dir().join(format!("maiko_log_writer_{}", uuid::Uuid::new_v4()));
+        let log = EventLog::open(&dir, LogConfig::default()).unwrap();
+
+        let mut sup = Supervisor::<Order>::default();
+        sup.add_actor("order-log", |_| LogWriter::new(log), &[DefaultTopic])
+            .unwrap();
+        sup.start().await.unwrap();
+        sup.send(Order(1)).await.unwrap();
+        sup.send(Order(2)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        sup.stop().await.unwrap();
+
+        let log = EventLog::<Order>::open(&dir, LogConfig::default()).unwrap();
+        let orders: Vec<_> = log
+            .read_from(0)
+            .unwrap()
+            .map(|record| record.unwrap().1.event().0)
+            .collect();
+        assert_eq!(orders, vec![1, 2]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+
+use crate::{Envelope, Event};
+
+/// Outcome of running an [`Interceptor`] on an envelope.
+#[derive(Debug, Clone)]
+pub enum InterceptAction<E> {
+    /// Pass the envelope on unchanged.
+    Forward,
+    /// Route this envelope instead of the original one.
+    ///
+    /// Use `Envelope::from((&new_event, envelope.meta()))` to keep the
+    /// original metadata (id, sender, correlation) while changing the payload.
+    Replace(Envelope<E>),
+    /// Discard the envelope. The reason is reported to monitors.
+    Drop(Cow<'static, str>),
+    /// Route several envelopes in place of the original one.
+    FanOut(Vec<Envelope<E>>),
+}
+
+/// Central hook that sees every event before the broker routes it.
+///
+/// Interceptors are registered with [`Supervisor::add_interceptor`] and run
+/// inside the broker loop, in registration order. Each one receives the
+/// output of the previous one: a replaced envelope is passed on, every
+/// envelope of a fan-out goes through the remaining interceptors, and a
+/// dropped envelope stops there.
+///
+/// Typical uses are validation (rejecting events from unauthorized bridge
+/// actors), enrichment (stamping tenant IDs) and redaction.
+///
+/// Interceptors run on the broker task, so they should be fast and must not
+/// block. Any closure `FnMut(&Envelope<E>) -> InterceptAction<E>` is an
+/// interceptor.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// sup.add_interceptor("auth", |envelope: &Envelope<MyEvent>| {
+///     if envelope.meta().actor_name().starts_with("bridge-") {
+///         InterceptAction::Drop("unauthorized bridge".into())
+///     } else {
+///         InterceptAction::Forward
+///     }
+/// })?;
+/// ```
+///
+/// [`Supervisor::add_interceptor`]: crate::Supervisor::add_interceptor
+pub trait Interceptor<E: Event>: Send + 'static {
+    /// Inspect the envelope and decide what the broker routes in its place.
+    fn intercept(&mut self, envelope: &Envelope<E>) -> InterceptAction<E>;
+}
+
+impl<E, F> Interceptor<E> for F
+where
+    E: Event,
+    F: FnMut(&Envelope<E>) -> InterceptAction<E> + Send + 'static,
+{
+    fn intercept(&mut self, envelope: &Envelope<E>) -> InterceptAction<E> {
+        self(envelope)
+    }
+}
@@ -1,13 +1,23 @@
 mod actor_controller;
+mod bridge;
 mod broker;
+mod context_sender;
+mod diagram;
+mod event_span;
+mod retained_events;
 mod step_handler;
 mod step_pause;
 mod subscriber;
 mod subscription;
 
 pub(crate) use actor_controller::ActorController;
-pub(crate) use broker::Broker;
+pub(crate) use bridge::{Bridge, BridgeOutlet};
+pub(crate) use broker::{Broker, Registration};
+pub(crate) use context_sender::{ContextSender, Widening};
+pub(crate) use diagram::{dot_text, mermaid_id, mermaid_text};
+pub(crate) use event_span::{SpanLabels, event_span};
+pub(crate) use retained_events::RetainedEvents;
 pub(crate) use step_handler::StepHandler;
 pub(crate) use step_pause::StepPause;
-pub(crate) use subscriber::Subscriber;
+pub(crate) use subscriber::{EventFilter, Routing, Subscriber};
 pub(crate) use subscription::Subscription;
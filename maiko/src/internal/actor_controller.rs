@@ -1,15 +1,47 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use tokio::{select, sync::mpsc::Receiver};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 use crate::{
     Actor, Context, Envelope, Result, StepAction, Topic,
-    internal::{StepHandler, StepPause},
+    internal::{SpanLabels, StepHandler, StepPause, event_span},
 };
 
 #[cfg(feature = "monitoring")]
-use crate::monitoring::{MonitoringEvent, MonitoringSink};
+use std::time::Duration;
+
+#[cfg(feature = "monitoring")]
+use tokio::time::Instant;
+
+#[cfg(feature = "monitoring")]
+use crate::{
+    ActorId,
+    monitoring::{MonitoringEvent, MonitoringSink},
+};
+
+/// Calls the actor's `step()`, reporting its entry to monitors when step
+/// monitoring is enabled. Takes the controller's fields separately so the
+/// future doesn't borrow the receiver polled in the same `select!`.
+#[cfg(feature = "monitoring")]
+macro_rules! step {
+    ($controller:ident) => {
+        observed_step(
+            &mut $controller.actor,
+            &$controller.monitoring,
+            &$controller.ctx.actor_id,
+            &mut $controller.step_state,
+        )
+    };
+}
+
+#[cfg(not(feature = "monitoring"))]
+macro_rules! step {
+    ($controller:ident) => {
+        $controller.actor.step()
+    };
+}
 
 pub(crate) struct ActorController<A: Actor, T: Topic<A::Event>> {
     pub(crate) actor: A,
@@ -17,9 +49,12 @@ pub(crate) struct ActorController<A: Actor, T: Topic<A::Event>> {
     pub(crate) ctx: Context<A::Event>,
     pub(crate) max_events_per_tick: usize,
     pub(crate) cancel_token: Arc<CancellationToken>,
+    pub(crate) span_labels: Arc<OnceLock<SpanLabels<A::Event, T>>>,
 
     #[cfg(feature = "monitoring")]
     pub(crate) monitoring: MonitoringSink<A::Event, T>,
+    #[cfg(feature = "monitoring")]
+    pub(crate) step_state: Option<StepState>,
 
     pub(crate) _topic: std::marker::PhantomData<fn() -> T>,
 }
@@ -30,6 +65,9 @@ impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
         let token = self.cancel_token.clone();
         let mut step_handler = StepHandler::default();
         while self.ctx.is_alive() {
+            #[cfg(feature = "monitoring")]
+            self.notify_step_cancelled().await;
+
             select! {
                 biased;
 
@@ -46,23 +84,24 @@ impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
                     #[cfg(feature = "monitoring")]
                     let topic = {
                         let topic = Arc::new(T::from_event(event.event()));
-                        self.notify_event_delivered(&event, &topic);
+                        self.notify_event_delivered(&event, &topic).await;
                         topic
                     };
 
-                    let res = self.actor.handle_event(&event).await;
+                    let res = self.handle_event(&event).await;
 
                     #[cfg(feature = "monitoring")]
-                    self.notify_event_handled(&event, &topic);
+                    self.notify_event_handled(&event, &topic).await;
 
-                    self.handle_error(res)?;
+                    self.handle_error(res).await?;
 
                     let mut cnt = 1;
                     while let Ok(event) = self.receiver.try_recv() {
-                        #[cfg(feature = "monitoring")] self.notify_event_delivered(&event, &topic);
-                        let res = self.actor.handle_event(&event).await;
-                        #[cfg(feature = "monitoring")] self.notify_event_handled(&event, &topic);
-                        self.handle_error(res)?;
+                        #[cfg(feature = "monitoring")] let topic = Arc::new(T::from_event(event.event()));
+                        #[cfg(feature = "monitoring")] self.notify_event_delivered(&event, &topic).await;
+                        let res = self.handle_event(&event).await;
+                        #[cfg(feature = "monitoring")] self.notify_event_handled(&event, &topic).await;
+                        self.handle_error(res).await?;
                         cnt += 1;
                         if cnt == self.max_events_per_tick {
                             break;
@@ -79,11 +118,16 @@ impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
                     }
                 }, if step_handler.is_delayed() => {
                     let _ = step_handler.backoff.take();
-                    match self.actor.step().await {
+                    let res = step!(self).await;
+
+                    #[cfg(feature = "monitoring")]
+                    self.notify_step_finished(&res).await;
+
+                    match res {
                         Ok(action) => handle_step_action(action, &mut step_handler).await,
                         Err(e) => {
                             #[cfg(feature = "monitoring")]
-                            self.notify_error(&e);
+                            self.notify_error(&e).await;
 
                             self.actor.on_error(e)?;
                             step_handler.reset();
@@ -91,12 +135,15 @@ impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
                      }
                 }
 
-                res = self.actor.step(), if step_handler.can_step() => {
-                     match res {
+                res = step!(self), if step_handler.can_step() => {
+                    #[cfg(feature = "monitoring")]
+                    self.notify_step_finished(&res).await;
+
+                    match res {
                         Ok(action) => handle_step_action(action, &mut step_handler).await,
                         Err(e) => {
                             #[cfg(feature = "monitoring")]
-                            self.notify_error(&e);
+                            self.notify_error(&e).await;
 
                             self.actor.on_error(e)?;
                             step_handler.reset();
@@ -107,16 +154,26 @@ impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
         }
 
         #[cfg(feature = "monitoring")]
-        self.notify_exit();
+        {
+            self.notify_step_cancelled().await;
+            self.notify_exit().await;
+        }
 
         self.actor.on_shutdown().await
     }
 
+    /// Let the actor handle an event within the event's span.
     #[inline]
-    fn handle_error<R>(&self, result: Result<R>) -> Result<()> {
+    async fn handle_event(&mut self, event: &Envelope<A::Event>) -> Result<()> {
+        let span = event_span(event, &self.ctx.actor_id, self.span_labels.get().copied());
+        self.actor.handle_event(event).instrument(span).await
+    }
+
+    #[inline]
+    async fn handle_error<R>(&mut self, result: Result<R>) -> Result<()> {
         if let Err(e) = result {
             #[cfg(feature = "monitoring")]
-            self.notify_error(&e);
+            self.notify_error(&e).await;
 
             self.actor.on_error(e)?;
         }
@@ -143,45 +200,220 @@ async fn handle_step_action(step_action: StepAction, step_handler: &mut StepHand
     step_handler.pause = pause;
 }
 
+/// Progress of an observed step, left behind by the step future for the
+/// loop to report once the `select!` is over.
+#[cfg(feature = "monitoring")]
+pub(crate) enum StepState {
+    Running(Instant),
+    Finished(Duration),
+    Cancelled(Duration),
+}
+
+/// Marks a step finished, or cancelled if dropped before that.
+#[cfg(feature = "monitoring")]
+struct StepTimer<'a>(&'a mut Option<StepState>);
+
+#[cfg(feature = "monitoring")]
+impl<'a> StepTimer<'a> {
+    fn start(state: &'a mut Option<StepState>) -> Self {
+        *state = Some(StepState::Running(Instant::now()));
+        Self(state)
+    }
+
+    fn finish(self) {
+        if let Some(StepState::Running(start)) = *self.0 {
+            *self.0 = Some(StepState::Finished(start.elapsed()));
+        }
+    }
+}
+
+#[cfg(feature = "monitoring")]
+impl Drop for StepTimer<'_> {
+    fn drop(&mut self) {
+        if let Some(StepState::Running(start)) = *self.0 {
+            *self.0 = Some(StepState::Cancelled(start.elapsed()));
+        }
+    }
+}
+
+/// Reports the step's entry and times it. The exit is reported by the
+/// loop after the `select!`, so a step that completed is never lost to
+/// another branch winning while its exit is being sent.
+#[cfg(feature = "monitoring")]
+async fn observed_step<A: Actor, T: Topic<A::Event>>(
+    actor: &mut A,
+    monitoring: &MonitoringSink<A::Event, T>,
+    actor_id: &ActorId,
+    state: &mut Option<StepState>,
+) -> Result<StepAction> {
+    if !monitoring.observes_steps() {
+        return actor.step().await;
+    }
+
+    monitoring
+        .send(MonitoringEvent::StepEntered(actor_id.clone()))
+        .await;
+    let timer = StepTimer::start(state);
+    let result = actor.step().await;
+    timer.finish();
+    result
+}
+
+// The notifiers take `&mut self`: a shared borrow held across `.await`
+// would require the actor to be `Sync`.
 #[cfg(feature = "monitoring")]
 impl<A: Actor, T: Topic<A::Event>> ActorController<A, T> {
     #[inline]
-    fn notify_event_delivered(&self, event: &Arc<Envelope<A::Event>>, topic: &Arc<T>) {
+    async fn notify_event_delivered(&mut self, event: &Arc<Envelope<A::Event>>, topic: &Arc<T>) {
         if self.monitoring.is_active() {
-            self.monitoring.send(MonitoringEvent::EventDelivered(
-                event.clone(),
-                topic.clone(),
-                self.ctx.actor_id.clone(),
-            ));
+            self.monitoring
+                .send(MonitoringEvent::EventDelivered(
+                    event.clone(),
+                    topic.clone(),
+                    self.ctx.actor_id.clone(),
+                ))
+                .await;
         }
     }
 
     #[inline]
-    fn notify_event_handled(&self, event: &Arc<Envelope<A::Event>>, topic: &Arc<T>) {
+    async fn notify_event_handled(&mut self, event: &Arc<Envelope<A::Event>>, topic: &Arc<T>) {
         if self.monitoring.is_active() {
-            self.monitoring.send(MonitoringEvent::EventHandled(
-                event.clone(),
-                topic.clone(),
-                self.ctx.actor_id.clone(),
-            ));
+            self.monitoring
+                .send(MonitoringEvent::EventHandled(
+                    event.clone(),
+                    topic.clone(),
+                    self.ctx.actor_id.clone(),
+                ))
+                .await;
         }
     }
 
     #[inline]
-    fn notify_error(&self, error: &crate::Error) {
+    async fn notify_error(&mut self, error: &crate::Error) {
         if self.monitoring.is_active() {
-            self.monitoring.send(MonitoringEvent::Error(
-                error.to_string().into(),
-                self.ctx.actor_id.clone(),
-            ));
+            self.monitoring
+                .send(MonitoringEvent::Error(
+                    error.to_string().into(),
+                    self.ctx.actor_id.clone(),
+                ))
+                .await;
         }
     }
 
     #[inline]
-    fn notify_exit(&self) {
+    async fn notify_step_finished(&mut self, result: &Result<StepAction>) {
+        let Some(StepState::Finished(duration)) = self.step_state.take() else {
+            return;
+        };
+        let actor_id = self.ctx.actor_id.clone();
+        self.monitoring
+            .send(match result {
+                Ok(action) => MonitoringEvent::StepExited(*action, duration, actor_id),
+                Err(e) => MonitoringEvent::StepFailed(e.to_string().into(), duration, actor_id),
+            })
+            .await;
+    }
+
+    #[inline]
+    async fn notify_step_cancelled(&mut self) {
+        if let Some(StepState::Cancelled(duration)) = self.step_state.take() {
+            self.monitoring
+                .send(MonitoringEvent::StepCancelled(
+                    duration,
+                    self.ctx.actor_id.clone(),
+                ))
+                .await;
+        }
+    }
+
+    #[inline]
+    async fn notify_exit(&mut self) {
         if self.monitoring.is_active() {
             self.monitoring
-                .send(MonitoringEvent::ActorStopped(self.ctx.actor_id.clone()));
+                .send(MonitoringEvent::ActorStopped(self.ctx.actor_id.clone()))
+                .await;
         }
     }
 }
+
+#[cfg(all(test, feature = "monitoring"))]
+mod tests {
+    use std::{
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        Actor, ActorId, Config, DefaultTopic, Event, StepAction, Subscribe, Supervisor,
+        monitoring::Monitor,
+    };
+
+    #[derive(Clone, Debug)]
+    struct Ping;
+    impl Event for Ping {}
+
+    /// Takes long enough per step for an event to cancel it.
+    struct Slow;
+
+    impl Actor for Slow {
+        type Event = Ping;
+
+        async fn step(&mut self) -> crate::Result<StepAction> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(StepAction::AwaitEvent)
+        }
+    }
+
+    /// Overrides the untimed exit callback only.
+    #[derive(Clone, Default)]
+    struct Steps {
+        entered: Arc<AtomicUsize>,
+        exited: Arc<AtomicUsize>,
+        cancelled: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    impl Monitor<Ping, DefaultTopic> for Steps {
+        fn on_step_enter(&self, _: &ActorId) {
+            self.entered.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_step_exit(&self, _: &StepAction, _: &ActorId) {
+            self.exited.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_step_cancel(&self, duration: Duration, _: &ActorId) {
+            self.cancelled.lock().unwrap().push(duration);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn every_entered_step_is_exited_or_cancelled() {
+        let mut sup = Supervisor::<Ping>::new(Config::default().with_step_monitoring(true));
+        let steps = Steps::default();
+        sup.monitors().add(steps.clone()).await;
+        sup.add_actor("slow", |_| Slow, Subscribe::all()).unwrap();
+        sup.start().await.unwrap();
+
+        // Cancels the first step; the second runs to completion.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        sup.send(Ping).await.unwrap();
+        // Wakes the actor for a third step, cancelled 20ms in.
+        tokio::time::sleep(Duration::from_millis(90)).await;
+        sup.send(Ping).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sup.send(Ping).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sup.stop().await.unwrap();
+
+        assert_eq!(steps.entered.load(Ordering::Relaxed), 4);
+        assert_eq!(steps.exited.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            *steps.cancelled.lock().unwrap(),
+            [Duration::from_millis(10), Duration::from_millis(20)]
+        );
+    }
+}
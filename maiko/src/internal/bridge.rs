@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use crate::{Actor, Context, Envelope, Event, Result};
+
+/// Source side of an in-process bridge between two supervisors.
+///
+/// Subscribes to topics in the source supervisor, maps each event with the
+/// user function and publishes the result through the outlet's context in
+/// the target supervisor. The envelope id, timestamp and correlation id are
+/// kept; the sender changes to the outlet, and the original sender is kept
+/// as [`Meta::origin`](crate::Meta::origin).
+pub(crate) struct Bridge<E, E2, F> {
+    pub outlet: Context<E2>,
+    pub map: F,
+    pub _event: PhantomData<fn() -> E>,
+}
+
+impl<E, E2, F> Actor for Bridge<E, E2, F>
+where
+    E: Event,
+    E2: Event,
+    F: FnMut(&E) -> Option<E2> + Send + 'static,
+{
+    type Event = E;
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        let Some(event) = (self.map)(envelope.event()) else {
+            return Ok(());
+        };
+        let meta = envelope.meta().forwarded_by(self.outlet.actor_id().clone());
+        if let Err(err) = self
+            .outlet
+            .send_envelope(Envelope::with_meta(event, meta))
+            .await
+        {
+            tracing::warn!(bridge=%self.outlet.actor_name(), event_id=%envelope.id(), error=%err, "failed to forward event over bridge");
+        }
+        Ok(())
+    }
+}
+
+/// Target side of a bridge: a named placeholder actor that only publishes.
+pub(crate) struct BridgeOutlet<E>(pub PhantomData<fn() -> E>);
+
+impl<E: Event> Actor for BridgeOutlet<E> {
+    type Event = E;
+}
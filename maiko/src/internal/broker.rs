@@ -1,24 +1,61 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
-use futures_util::{FutureExt, StreamExt, future::join_all, stream::SelectAll};
+use futures_util::{FutureExt, Stream, StreamExt, future::join_all, stream::SelectAll};
 use tokio::{
     select,
-    sync::mpsc::{Receiver, error::TrySendError},
+    sync::mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender, error::TrySendError},
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
 
-use super::Subscriber;
-use crate::{ActorId, Config, Envelope, Error, Event, OverflowPolicy, Result, Topic};
+use super::{RetainedEvents, Subscriber, Subscription};
+use crate::{
+    ActorId, Config, Envelope, Error, Event, InterceptAction, Interceptor, OverflowPolicy, Result,
+    Topic, UndeclaredTopicPolicy,
+};
 
 #[cfg(feature = "monitoring")]
-use crate::monitoring::{MonitoringEvent, MonitoringSink};
+use crate::monitoring::{ChannelDepth, MonitoringEvent, MonitoringSink, QueueDepth};
 
 type Payload<E> = Arc<Envelope<E>>;
 
+/// An actor's channel to the broker (stage 1), tagged with the actor's id.
+struct SenderStream<E> {
+    #[cfg_attr(not(feature = "monitoring"), allow(dead_code))]
+    actor_id: ActorId,
+    inner: ReceiverStream<Payload<E>>,
+}
+
+impl<E> Stream for SenderStream<E> {
+    type Item = Payload<E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+/// An actor to add to the broker: its mailbox, its channel to the broker
+/// and its declared publications.
+pub(crate) struct Registration<E: Event, T: Topic<E>> {
+    pub subscriber: Subscriber<E, T>,
+    pub receiver: Receiver<Payload<E>>,
+    pub publications: Option<Subscription<T>>,
+}
+
 pub struct Broker<E: Event, T: Topic<E>> {
-    senders: SelectAll<ReceiverStream<Payload<E>>>,
+    senders: SelectAll<SenderStream<E>>,
+    registrar: UnboundedSender<Registration<E, T>>,
+    registrations: UnboundedReceiver<Registration<E, T>>,
     subscribers: Vec<Subscriber<E, T>>,
+    retained: RetainedEvents<E, T>,
+    interceptors: Vec<(Arc<str>, Box<dyn Interceptor<E>>)>,
+    /// Declared publications, for actors that declared them.
+    publications: HashMap<ActorId, Subscription<T>>,
     cancel_token: Arc<CancellationToken>,
     config: Arc<Config>,
 
@@ -32,9 +69,15 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
         config: Arc<Config>,
         #[cfg(feature = "monitoring")] monitoring: MonitoringSink<E, T>,
     ) -> Broker<E, T> {
+        let (registrar, registrations) = mpsc::unbounded_channel();
         Broker {
             senders: SelectAll::new(),
+            registrar,
+            registrations,
             subscribers: Vec::new(),
+            retained: RetainedEvents::new(),
+            interceptors: Vec::new(),
+            publications: HashMap::new(),
             cancel_token,
             config,
             #[cfg(feature = "monitoring")]
@@ -42,21 +85,178 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
         }
     }
 
-    pub(crate) fn add_subscriber(&mut self, subscriber: Subscriber<E, T>) -> Result<()> {
+    /// Returns a channel for adding actors, before or after the broker starts.
+    ///
+    /// Registrations are applied by [`run`](Self::run) ahead of any pending
+    /// events, so a subscriber registered before an event was sent receives it.
+    pub(crate) fn registrar(&self) -> UnboundedSender<Registration<E, T>> {
+        self.registrar.clone()
+    }
+
+    async fn register(&mut self, registration: Registration<E, T>) {
+        let actor_id = registration.subscriber.actor_id.clone();
+        if let Err(err) = self.add_subscriber(registration.subscriber).await {
+            tracing::error!(actor=%actor_id.name(), error=%err, "actor registration rejected");
+            return;
+        }
+        self.add_sender(actor_id.clone(), registration.receiver);
+        if let Some(publications) = registration.publications {
+            self.add_publisher(actor_id, publications);
+        }
+    }
+
+    pub(crate) async fn add_subscriber(&mut self, subscriber: Subscriber<E, T>) -> Result<()> {
         if self.subscribers.contains(&subscriber) {
             return Err(Error::SubscriberAlreadyExists(subscriber.actor_id.clone()));
         }
 
         #[cfg(feature = "monitoring")]
-        self.record_actor_registered(&subscriber.actor_id);
+        self.record_actor_registered(&subscriber.actor_id).await;
 
+        self.replay_retained(&subscriber).await;
         self.subscribers.push(subscriber);
 
         Ok(())
     }
 
-    pub(crate) fn add_sender(&mut self, receiver: Receiver<Payload<E>>) {
-        self.senders.push(ReceiverStream::new(receiver));
+    /// Returns a handle to the store of retained events.
+    pub(crate) fn retained_events(&self) -> RetainedEvents<E, T> {
+        self.retained.clone()
+    }
+
+    /// Deliver retained events matching the subscriber's topics, oldest first.
+    ///
+    /// Replay is best effort: it stops at the first full or closed channel
+    /// rather than applying the topic's overflow policy.
+    async fn replay_retained(&mut self, subscriber: &Subscriber<E, T>) {
+        for e in self.retained.matching(&subscriber.topics) {
+            if subscriber.actor_id == *e.meta().actor_id() || !subscriber.accepts(e.event()) {
+                continue;
+            }
+            if let Err(err) = subscriber.sender.try_send(e.clone()) {
+                tracing::warn!(actor=%subscriber.actor_id.name(), error=%err, "retained events replay interrupted");
+                break;
+            }
+
+            #[cfg(feature = "monitoring")]
+            if self.monitoring.is_active() {
+                self.monitoring
+                    .send(MonitoringEvent::EventReplayed(
+                        e.clone(),
+                        Arc::new(T::from_event(e.event())),
+                        subscriber.actor_id.clone(),
+                    ))
+                    .await;
+            }
+        }
+    }
+
+    pub(crate) fn add_sender(&mut self, actor_id: ActorId, receiver: Receiver<Payload<E>>) {
+        self.senders.push(SenderStream {
+            actor_id,
+            inner: ReceiverStream::new(receiver),
+        });
+    }
+
+    pub(crate) fn add_interceptor(&mut self, name: Arc<str>, interceptor: Box<dyn Interceptor<E>>) {
+        self.interceptors.push((name, interceptor));
+    }
+
+    pub(crate) fn add_publisher(&mut self, actor_id: ActorId, topics: Subscription<T>) {
+        self.publications.insert(actor_id, topics);
+    }
+
+    /// Check the event's topic against the sender's declared publications,
+    /// handling undeclared topics according to the configured policy.
+    /// Returns the topic if the event must be dropped.
+    fn check_publication(&self, e: &Envelope<E>) -> Option<T> {
+        let policy = self.config.undeclared_topic_policy();
+        if policy == UndeclaredTopicPolicy::Ignore {
+            return None;
+        }
+        let declared = self.publications.get(e.meta().actor_id())?;
+        let topic = T::from_event(e.event());
+        if declared.contains(&topic) {
+            return None;
+        }
+        match policy {
+            UndeclaredTopicPolicy::Error => {
+                tracing::error!(actor=%e.meta().actor_name(), event_id=%e.id(), "event sent to an undeclared topic dropped");
+                Some(topic)
+            }
+            _ => {
+                tracing::warn!(actor=%e.meta().actor_name(), event_id=%e.id(), "event sent to an undeclared topic");
+                None
+            }
+        }
+    }
+
+    /// Run the envelope through the interceptors (if any) and route the result.
+    async fn process(&mut self, e: Payload<E>) -> Result<()> {
+        if let Some(topic) = self.check_publication(&e) {
+            #[cfg(feature = "monitoring")]
+            if self.monitoring.is_active() {
+                self.monitoring
+                    .send(MonitoringEvent::EventRejected(e, Arc::new(topic)))
+                    .await;
+            }
+            #[cfg(not(feature = "monitoring"))]
+            let _ = topic;
+            return Ok(());
+        }
+        if self.interceptors.is_empty() {
+            return self.route(&e).await;
+        }
+        for e in self.intercept(e).await {
+            self.route(&e).await?;
+        }
+        Ok(())
+    }
+
+    /// Apply the interceptor pipeline in registration order.
+    async fn intercept(&mut self, e: Payload<E>) -> Vec<Payload<E>> {
+        let mut batch = vec![e];
+        for (name, interceptor) in self.interceptors.iter_mut() {
+            let mut next = Vec::with_capacity(batch.len());
+            for e in batch {
+                match interceptor.intercept(&e) {
+                    InterceptAction::Forward => next.push(e),
+                    InterceptAction::Replace(envelope) => next.push(inherit_span(envelope, &e)),
+                    InterceptAction::FanOut(envelopes) => next.extend(
+                        envelopes
+                            .into_iter()
+                            .map(|envelope| inherit_span(envelope, &e)),
+                    ),
+                    InterceptAction::Drop(reason) => {
+                        tracing::debug!(interceptor=%name, event_id=%e.id(), reason=%reason, "event dropped by interceptor");
+
+                        #[cfg(feature = "monitoring")]
+                        if self.monitoring.is_active() {
+                            self.monitoring
+                                .send(MonitoringEvent::EventDropped(
+                                    e,
+                                    name.clone(),
+                                    reason.into(),
+                                ))
+                                .await;
+                        }
+                    }
+                }
+            }
+            batch = next;
+        }
+        batch
+    }
+
+    async fn route(&mut self, e: &Payload<E>) -> Result<()> {
+        let tbc = self.send_event(e).await?;
+
+        // Close channels for subscribers that overflown with Fail policy
+        if let Some(to_be_closed) = tbc {
+            self.subscribers
+                .retain(|s| !to_be_closed.contains(&s.actor_id));
+        }
+        Ok(())
     }
 
     async fn send_event(&mut self, e: &Arc<Envelope<E>>) -> Result<Option<Vec<ActorId>>> {
@@ -79,29 +279,34 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
             .subscribers
             .iter()
             .filter(|s| s.topics.contains(&topic))
+            .filter(|s| s.accepts(e.event()))
             .filter(|s| !s.is_closed())
             .filter(|s| s.actor_id != *e.meta().actor_id())
         {
             match subscriber.sender.try_send(e.clone()) {
                 Ok(_) => {
                     #[cfg(feature = "monitoring")]
-                    self.record_event_dispatched(
+                    Self::record_event_dispatched(
+                        &self.monitoring,
                         is_recording,
                         e,
                         &topic_for_monitor,
                         &subscriber.actor_id,
-                    );
+                    )
+                    .await;
                 }
                 Err(TrySendError::Full(event)) => {
                     let policy = topic.overflow_policy();
                     #[cfg(feature = "monitoring")]
-                    self.record_overflow(
+                    Self::record_overflow(
+                        &self.monitoring,
                         is_recording,
                         e,
                         &topic_for_monitor,
                         &subscriber.actor_id,
                         policy,
-                    );
+                    )
+                    .await;
                     match policy {
                         OverflowPolicy::Fail => {
                             tracing::error!(actor=%subscriber.actor_id.name(), event_id=%e.id(), "closing channel due to OverflowPolicy Fail");
@@ -130,11 +335,22 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
             join_all(b).await;
         }
 
+        let retention = topic.retention();
+        if retention > 0 {
+            self.retained.retain(&topic, e, retention);
+        }
+
         Ok(to_be_closed)
     }
 
     pub async fn run(&mut self) -> Result<()> {
         let mut cleanup_interval = tokio::time::interval(self.config.maintenance_interval());
+        let depth_sampling = self
+            .config
+            .queue_depth_interval()
+            .filter(|interval| !interval.is_zero());
+        let mut depth_interval =
+            tokio::time::interval(depth_sampling.unwrap_or(self.config.maintenance_interval()));
         loop {
             select! {
                 biased;
@@ -142,15 +358,15 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
                 _ = cleanup_interval.tick() => {
                     self.cleanup();
                 }
+                _ = depth_interval.tick(), if depth_sampling.is_some() => {
+                    #[cfg(feature = "monitoring")]
+                    self.sample_queue_depths().await;
+                }
+                Some(registration) = self.registrations.recv() => {
+                    self.register(registration).await;
+                }
                 Some(event) = self.senders.next() => {
-                    let tbc = self.send_event(&event).await?;
-
-                    // Close channels for subscribers that overflown with Fail policy
-                    if let Some(to_be_closed) = tbc {
-                        self.subscribers.retain(|s|
-                            !to_be_closed.contains(&s.actor_id)
-                        );
-                    }
+                    self.process(event).await?;
                 },
             }
         }
@@ -167,7 +383,7 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
 
         // Drain any events still buffered in sender streams (best effort)
         while let Some(event) = self.senders.next().now_or_never().flatten() {
-            let _ = self.send_event(&event).await;
+            let _ = self.process(event).await;
         }
 
         tokio::task::yield_now().await;
@@ -187,11 +403,13 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
     }
 }
 
+// The async helpers borrow the sink rather than `&self`: a shared borrow of
+// the broker held across `.await` would require interceptors to be `Sync`.
 #[cfg(feature = "monitoring")]
 impl<E: Event, T: Topic<E>> Broker<E, T> {
     #[inline]
-    fn record_event_dispatched(
-        &self,
+    async fn record_event_dispatched(
+        monitoring: &MonitoringSink<E, T>,
         is_recording: bool,
         e: &Arc<Envelope<E>>,
         topic: &Option<Arc<T>>,
@@ -199,18 +417,20 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
     ) {
         if is_recording {
             if let Some(topic_for_monitor) = topic {
-                self.monitoring.send(MonitoringEvent::EventDispatched(
-                    e.clone(),
-                    topic_for_monitor.clone(),
-                    actor_id.clone(),
-                ));
+                monitoring
+                    .send(MonitoringEvent::EventDispatched(
+                        e.clone(),
+                        topic_for_monitor.clone(),
+                        actor_id.clone(),
+                    ))
+                    .await;
             }
         }
     }
 
     #[inline]
-    fn record_overflow(
-        &self,
+    async fn record_overflow(
+        monitoring: &MonitoringSink<E, T>,
         is_recording: bool,
         e: &Arc<Envelope<E>>,
         topic: &Option<Arc<T>>,
@@ -219,24 +439,78 @@ impl<E: Event, T: Topic<E>> Broker<E, T> {
     ) {
         if is_recording {
             if let Some(topic_for_monitor) = topic {
-                self.monitoring.send(MonitoringEvent::Overflow(
-                    e.clone(),
-                    topic_for_monitor.clone(),
-                    actor_id.clone(),
-                    policy,
-                ));
+                monitoring
+                    .send(MonitoringEvent::Overflow(
+                        e.clone(),
+                        topic_for_monitor.clone(),
+                        actor_id.clone(),
+                        policy,
+                    ))
+                    .await;
             }
         }
     }
 
-    fn record_actor_registered(&self, actor_id: &ActorId) {
+    /// Report the depth of every actor's sender channel and mailbox.
+    async fn sample_queue_depths(&mut self) {
+        if !self.monitoring.is_active() {
+            return;
+        }
+        for (actor_id, depth) in self.queue_depths() {
+            self.monitoring
+                .send(MonitoringEvent::QueueDepth(actor_id, depth))
+                .await;
+        }
+    }
+
+    fn queue_depths(&self) -> Vec<(ActorId, QueueDepth)> {
+        let mut mailboxes: HashMap<&ActorId, ChannelDepth> = self
+            .subscribers
+            .iter()
+            .filter(|s| !s.is_closed())
+            .map(|s| {
+                let tx = &s.sender;
+                let len = tx.max_capacity() - tx.capacity();
+                (&s.actor_id, ChannelDepth::new(len, tx.max_capacity()))
+            })
+            .collect();
+        let mut depths = Vec::with_capacity(self.senders.len() + mailboxes.len());
+        for stream in self.senders.iter() {
+            let receiver = stream.inner.as_ref();
+            let sender = ChannelDepth::new(receiver.len(), receiver.max_capacity());
+            let mailbox = mailboxes.remove(&stream.actor_id);
+            depths.push((
+                stream.actor_id.clone(),
+                QueueDepth::new(Some(sender), mailbox),
+            ));
+        }
+        depths.extend(
+            mailboxes.into_iter().map(|(actor_id, mailbox)| {
+                (actor_id.clone(), QueueDepth::new(None, Some(mailbox)))
+            }),
+        );
+        depths
+    }
+
+    async fn record_actor_registered(&mut self, actor_id: &ActorId) {
         if self.monitoring.is_active() {
             self.monitoring
-                .send(MonitoringEvent::ActorRegistered(actor_id.clone()));
+                .send(MonitoringEvent::ActorRegistered(actor_id.clone()))
+                .await;
         }
     }
 }
 
+/// Keep an intercepted event's span on the envelopes replacing it, so
+/// their handling still nests under the sender's span.
+fn inherit_span<E: Event>(envelope: Envelope<E>, origin: &Envelope<E>) -> Payload<E> {
+    if envelope.span().is_none() {
+        Arc::new(envelope.with_span(origin.span().clone()))
+    } else {
+        Arc::new(envelope)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Event, Topic, internal::Subscription, internal::broker::Broker};
@@ -263,6 +537,34 @@ mod tests {
                 TestTopic::B
             }
         }
+
+        fn retention(&self) -> usize {
+            match self {
+                TestTopic::A => 2,
+                TestTopic::B => 0,
+            }
+        }
+    }
+
+    fn make_broker() -> Broker<TestEvent, TestTopic> {
+        make_broker_with(crate::Config::default())
+    }
+
+    fn make_broker_with(config: crate::Config) -> Broker<TestEvent, TestTopic> {
+        let config = Arc::new(config);
+
+        #[cfg(feature = "monitoring")]
+        let monitoring = {
+            let registry = crate::monitoring::MonitorRegistry::<TestEvent, TestTopic>::new(&config);
+            registry.sink()
+        };
+
+        Broker::<TestEvent, TestTopic>::new(
+            Arc::new(CancellationToken::new()),
+            config,
+            #[cfg(feature = "monitoring")]
+            monitoring,
+        )
     }
 
     #[tokio::test]
@@ -285,19 +587,170 @@ mod tests {
             #[cfg(feature = "monitoring")]
             monitoring,
         );
-        broker.add_sender(rx);
+        broker.add_sender(ActorId::new(Arc::from("sender")), rx);
         let actor_id = ActorId::new(Arc::from("subscriber1"));
         let subscriber = super::Subscriber::new(
             actor_id.clone(),
             Subscription::Topics(HashSet::from([TestTopic::A])),
             tx.clone(),
         );
-        assert!(broker.add_subscriber(subscriber).is_ok());
+        assert!(broker.add_subscriber(subscriber).await.is_ok());
         let duplicate_subscriber = super::Subscriber::new(
             actor_id,
             Subscription::Topics(HashSet::from([TestTopic::B])),
             tx.clone(),
         );
-        assert!(broker.add_subscriber(duplicate_subscriber).is_err());
+        assert!(broker.add_subscriber(duplicate_subscriber).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retained_events_replayed_to_new_subscriber() {
+        use crate::{ActorId, Envelope};
+
+        let mut broker = make_broker();
+        let sender = ActorId::new(Arc::from("sender"));
+        for id in [2, 3, 4, 6] {
+            let envelope = Arc::new(Envelope::new(TestEvent { id }, sender.clone()));
+            broker.send_event(&envelope).await.unwrap();
+        }
+
+        let retained = broker.retained_events();
+        let ids: Vec<_> = retained
+            .get(&TestTopic::A)
+            .iter()
+            .map(|e| e.event().id)
+            .collect();
+        assert_eq!(ids, vec![4, 6]);
+        assert!(retained.get(&TestTopic::B).is_empty());
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let late = super::Subscriber::new(ActorId::new(Arc::from("late")), Subscription::All, tx);
+        broker.add_subscriber(late).await.unwrap();
+
+        assert_eq!(rx.try_recv().unwrap().event().id, 4);
+        assert_eq!(rx.try_recv().unwrap().event().id, 6);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retained_events_not_replayed_to_their_sender() {
+        use crate::{ActorId, Envelope};
+
+        let mut broker = make_broker();
+        let sender = ActorId::new(Arc::from("sender"));
+        let envelope = Arc::new(Envelope::new(TestEvent { id: 2 }, sender.clone()));
+        broker.send_event(&envelope).await.unwrap();
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let subscriber = super::Subscriber::new(
+            sender,
+            Subscription::Topics(HashSet::from([TestTopic::A])),
+            tx,
+        );
+        broker.add_subscriber(subscriber).await.unwrap();
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_interceptors_run_in_registration_order() {
+        use crate::{ActorId, Envelope, InterceptAction};
+
+        let mut broker = make_broker();
+        let sender = ActorId::new(Arc::from("sender"));
+        let (tx, mut rx) = mpsc::channel(10);
+        let receiver =
+            super::Subscriber::new(ActorId::new(Arc::from("receiver")), Subscription::All, tx);
+        broker.add_subscriber(receiver).await.unwrap();
+
+        let fan_out_sender = sender.clone();
+        broker.add_interceptor(
+            Arc::from("fan-out"),
+            Box::new(move |e: &Envelope<TestEvent>| {
+                if e.event().id == 1 {
+                    InterceptAction::FanOut(vec![
+                        Envelope::new(TestEvent { id: 1 }, fan_out_sender.clone()),
+                        Envelope::new(TestEvent { id: 3 }, fan_out_sender.clone()),
+                    ])
+                } else {
+                    InterceptAction::Forward
+                }
+            }),
+        );
+        broker.add_interceptor(
+            Arc::from("filter"),
+            Box::new(|e: &Envelope<TestEvent>| match e.event().id {
+                3 => InterceptAction::Drop("rejected".into()),
+                id => {
+                    InterceptAction::Replace(Envelope::from((&TestEvent { id: id * 10 }, e.meta())))
+                }
+            }),
+        );
+
+        for id in [1, 2] {
+            let envelope = Arc::new(Envelope::new(TestEvent { id }, sender.clone()));
+            broker.process(envelope).await.unwrap();
+        }
+
+        assert_eq!(rx.try_recv().unwrap().event().id, 10);
+        assert_eq!(rx.try_recv().unwrap().event().id, 20);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replaced_envelope_keeps_meta() {
+        use crate::{ActorId, Envelope, InterceptAction};
+
+        let mut broker = make_broker();
+        let (tx, mut rx) = mpsc::channel(10);
+        let receiver =
+            super::Subscriber::new(ActorId::new(Arc::from("receiver")), Subscription::All, tx);
+        broker.add_subscriber(receiver).await.unwrap();
+        broker.add_interceptor(
+            Arc::from("stamp"),
+            Box::new(|e: &Envelope<TestEvent>| {
+                InterceptAction::Replace(Envelope::from((&TestEvent { id: 42 }, e.meta())))
+            }),
+        );
+
+        let envelope = Arc::new(Envelope::with_correlation(
+            TestEvent { id: 1 },
+            ActorId::new(Arc::from("sender")),
+            7,
+        ));
+        let id = envelope.id();
+        broker.process(envelope).await.unwrap();
+
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received.event().id, 42);
+        assert_eq!(received.id(), id);
+        assert_eq!(received.meta().correlation_id(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_undeclared_topics_are_dropped_by_error_policy() {
+        use crate::{ActorId, Config, Envelope, UndeclaredTopicPolicy};
+
+        let config = Config::default().with_undeclared_topic_policy(UndeclaredTopicPolicy::Error);
+        let mut broker = make_broker_with(config);
+        let (tx, mut rx) = mpsc::channel(10);
+        let receiver =
+            super::Subscriber::new(ActorId::new(Arc::from("receiver")), Subscription::All, tx);
+        broker.add_subscriber(receiver).await.unwrap();
+
+        let declared = ActorId::new(Arc::from("declared"));
+        let undeclared = ActorId::new(Arc::from("undeclared"));
+        broker.add_publisher(
+            declared.clone(),
+            Subscription::Topics(HashSet::from([TestTopic::A])),
+        );
+
+        for (id, sender) in [(2, &declared), (3, &declared), (5, &undeclared)] {
+            let envelope = Arc::new(Envelope::new(TestEvent { id }, sender.clone()));
+            broker.process(envelope).await.unwrap();
+        }
+
+        assert_eq!(rx.try_recv().unwrap().event().id, 2);
+        assert_eq!(rx.try_recv().unwrap().event().id, 5);
+        assert!(rx.try_recv().is_err());
     }
 }
@@ -0,0 +1,76 @@
+use std::{fmt, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use tokio::sync::mpsc::Sender;
+
+use crate::{Envelope, Result};
+
+/// Outbound channel of a [`Context`](crate::Context).
+///
+/// Regular actors send straight into their stage 1 channel. Actors with a
+/// narrower event type send through a [`Widen`] adapter that converts each
+/// envelope into the system event type first.
+pub(crate) enum ContextSender<E> {
+    Direct(Sender<Arc<Envelope<E>>>),
+    Widened(Arc<dyn Widen<E>>),
+}
+
+impl<E> ContextSender<E> {
+    pub async fn send(&self, envelope: Envelope<E>) -> Result<()> {
+        match self {
+            ContextSender::Direct(sender) => sender.send(Arc::new(envelope)).await?,
+            ContextSender::Widened(widen) => widen.send(envelope).await?,
+        }
+        Ok(())
+    }
+
+    pub fn is_full(&self) -> bool {
+        match self {
+            ContextSender::Direct(sender) => sender.capacity() == 0,
+            ContextSender::Widened(widen) => widen.is_full(),
+        }
+    }
+}
+
+impl<E> Clone for ContextSender<E> {
+    fn clone(&self) -> Self {
+        match self {
+            ContextSender::Direct(sender) => ContextSender::Direct(sender.clone()),
+            ContextSender::Widened(widen) => ContextSender::Widened(widen.clone()),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ContextSender<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextSender::Direct(sender) => sender.fmt(f),
+            ContextSender::Widened(_) => f.write_str("Widened"),
+        }
+    }
+}
+
+/// Converts envelopes of a narrow event type `S` before sending them on.
+pub(crate) trait Widen<S>: Send + Sync {
+    fn send(&self, envelope: Envelope<S>) -> BoxFuture<'_, Result<()>>;
+    fn is_full(&self) -> bool;
+}
+
+/// [`Widen`] implementation forwarding into a context sender of type `E`.
+pub(crate) struct Widening<E>(pub ContextSender<E>);
+
+impl<S, E> Widen<S> for Widening<E>
+where
+    S: Into<E> + Send + 'static,
+    E: Send + Sync + 'static,
+{
+    fn send(&self, envelope: Envelope<S>) -> BoxFuture<'_, Result<()>> {
+        let (event, meta, span) = envelope.into_parts();
+        let envelope = Envelope::with_meta(event.into(), meta).with_span(span);
+        Box::pin(self.0.send(envelope))
+    }
+
+    fn is_full(&self) -> bool {
+        self.0.is_full()
+    }
+}
@@ -0,0 +1,31 @@
+/// Mermaid node id: the kind prefix and the name with anything but
+/// alphanumerics replaced, so an actor and a topic may share a name.
+pub(crate) fn mermaid_id(kind: &str, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{kind}_{name}")
+}
+
+/// Text for a quoted Mermaid label.
+pub(crate) fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+/// Text for a quoted DOT string.
+pub(crate) fn dot_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_names() {
+        assert_eq!(mermaid_id("a", "order-book 1"), "a_order_book_1");
+        assert_eq!(mermaid_text("say \"hi\""), "say #quot;hi#quot;");
+        assert_eq!(dot_text("a\\\"b"), "a\\\\\\\"b");
+    }
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use tracing::{Span, field};
+
+use crate::{ActorId, Envelope, Event, Topic};
+
+/// Labels of an event and its topic, recorded on handling spans.
+pub(crate) type SpanLabels<E, T> = fn(&E, &T) -> (Cow<'static, str>, Cow<'static, str>);
+
+/// The span in which an actor handles an event: a child of the span the
+/// event was sent from, or a root for events sent without one.
+pub(crate) fn event_span<E: Event, T: Topic<E>>(
+    envelope: &Envelope<E>,
+    actor_id: &ActorId,
+    labels: Option<SpanLabels<E, T>>,
+) -> Span {
+    let span = tracing::info_span!(
+        parent: envelope.span(),
+        "handle_event",
+        actor = %actor_id.name(),
+        event_id = %envelope.id(),
+        correlation_id = field::Empty,
+        label = field::Empty,
+        topic = field::Empty,
+    );
+    if span.is_disabled() {
+        return span;
+    }
+    if let Some(id) = envelope.meta().correlation_id() {
+        span.record("correlation_id", field::display(id));
+    }
+    if let Some(labels) = labels {
+        let (label, topic) = labels(envelope.event(), &T::from_event(envelope.event()));
+        span.record("label", label.as_ref());
+        span.record("topic", topic.as_ref());
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing::{
+        Subscriber,
+        field::{self, Visit},
+        span,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan};
+
+    use crate::{Actor, Context as ActorContext, Event, Label, Supervisor, Topic};
+
+    #[derive(Clone, Debug)]
+    enum Flow {
+        Order,
+        Fill,
+    }
+    impl Event for Flow {}
+    impl Label for Flow {
+        fn label(&self) -> std::borrow::Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    enum FlowTopic {
+        Orders,
+        Fills,
+    }
+    impl Topic<Flow> for FlowTopic {
+        fn from_event(event: &Flow) -> Self {
+            match event {
+                Flow::Order => FlowTopic::Orders,
+                Flow::Fill => FlowTopic::Fills,
+            }
+        }
+    }
+    impl Label for FlowTopic {
+        fn label(&self) -> std::borrow::Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    struct Exchange {
+        ctx: ActorContext<Flow>,
+    }
+    impl Actor for Exchange {
+        type Event = Flow;
+
+        async fn handle_event(&mut self, envelope: &crate::Envelope<Flow>) -> crate::Result<()> {
+            self.ctx.send_child_event(Flow::Fill, envelope.meta()).await
+        }
+    }
+
+    struct Trader;
+    impl Actor for Trader {
+        type Event = Flow;
+    }
+
+    /// Fields of `handle_event` spans, with the fields of their parent span.
+    type Spans = Arc<Mutex<Vec<(Fields, Option<Fields>)>>>;
+
+    #[derive(Clone, Debug, Default)]
+    struct Fields(HashMap<&'static str, String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name(), format!("{value:?}"));
+        }
+        fn record_str(&mut self, field: &field::Field, value: &str) {
+            self.0.insert(field.name(), value.to_string());
+        }
+    }
+
+    struct SpanTree(Spans);
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for SpanTree {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            ctx.span(id).unwrap().extensions_mut().insert(fields);
+        }
+
+        fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+            if let Some(fields) = ctx.span(id).unwrap().extensions_mut().get_mut::<Fields>() {
+                values.record(fields);
+            }
+        }
+
+        fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+            let span = ctx.span(&id).unwrap();
+            let fields = span.extensions().get::<Fields>().cloned().unwrap();
+            let parent = span
+                .parent()
+                .and_then(|p| p.extensions().get::<Fields>().cloned());
+            self.0.lock().unwrap().push((fields, parent));
+        }
+    }
+
+    #[tokio::test]
+    async fn child_events_are_handled_in_child_spans() {
+        let spans = Spans::default();
+        let _guard = tracing_subscriber::registry()
+            .with(SpanTree(spans.clone()))
+            .set_default();
+
+        let mut sup = Supervisor::<Flow, FlowTopic>::default();
+        sup.add_actor("exchange", |ctx| Exchange { ctx }, &[FlowTopic::Orders])
+            .unwrap();
+        sup.label_event_spans();
+        sup.add_actor("trader", |_| Trader, &[FlowTopic::Fills])
+            .unwrap();
+        sup.start().await.unwrap();
+        sup.send(Flow::Order).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        sup.stop().await.unwrap();
+
+        let spans = spans.lock().unwrap();
+        let field = |fields: &Fields, name| fields.0.get(name).cloned().unwrap_or_default();
+        let (order, order_parent) = spans
+            .iter()
+            .find(|(fields, _)| field(fields, "actor") == "exchange")
+            .unwrap();
+        let (fill, fill_parent) = spans
+            .iter()
+            .find(|(fields, _)| field(fields, "actor") == "trader")
+            .unwrap();
+
+        assert!(order_parent.is_none());
+        assert_eq!(field(order, "label"), "Order");
+        assert_eq!(field(order, "topic"), "Orders");
+        assert_eq!(field(fill, "label"), "Fill");
+        assert_eq!(field(fill, "correlation_id"), field(order, "event_id"));
+        let fill_parent = fill_parent.as_ref().unwrap();
+        assert_eq!(field(fill_parent, "event_id"), field(order, "event_id"));
+    }
+}
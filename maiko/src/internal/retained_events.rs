@@ -0,0 +1,96 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use crate::{Envelope, internal::Subscription};
+
+type Payload<E> = Arc<Envelope<E>>;
+
+/// Last-value store for topics that opt into retention.
+///
+/// Shared between the broker (which writes) and the supervisor (which
+/// inspects), hence the interior mutability.
+pub(crate) struct RetainedEvents<E, T: Eq + Hash> {
+    inner: Arc<Mutex<HashMap<T, VecDeque<Payload<E>>>>>,
+}
+
+impl<E, T: Eq + Hash + Clone> RetainedEvents<E, T> {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Store the envelope, keeping at most `limit` most recent entries for the topic.
+    ///
+    /// The stored copy doesn't keep the sender's span open.
+    pub fn retain(&self, topic: &T, envelope: &Payload<E>, limit: usize)
+    where
+        E: Clone,
+    {
+        let envelope = if envelope.span().is_none() {
+            envelope.clone()
+        } else {
+            Arc::new(envelope.without_span())
+        };
+        let mut lock = self.inner.lock().unwrap();
+        let queue = lock.entry(topic.clone()).or_default();
+        queue.push_back(envelope);
+        while queue.len() > limit {
+            queue.pop_front();
+        }
+    }
+
+    /// Returns the retained envelopes for a single topic, oldest first.
+    pub fn get(&self, topic: &T) -> Vec<Payload<E>> {
+        let lock = self.inner.lock().unwrap();
+        lock.get(topic)
+            .map(|q| q.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the retained envelopes matching the subscription, ordered by timestamp.
+    pub fn matching(&self, subscription: &Subscription<T>) -> Vec<Payload<E>> {
+        let lock = self.inner.lock().unwrap();
+        let mut events: Vec<_> = lock
+            .iter()
+            .filter(|(topic, _)| subscription.contains(topic))
+            .flat_map(|(_, q)| q.iter().cloned())
+            .collect();
+        events.sort_by_key(|e| e.meta().timestamp());
+        events
+    }
+}
+
+impl<E, T: Eq + Hash> Clone for RetainedEvents<E, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic};
+
+    #[test]
+    fn retained_copy_drops_the_span() {
+        let _guard = tracing_subscriber::registry().set_default();
+        let span = tracing::info_span!("sender");
+        let envelope = Envelope::new(1, ActorId::new(Arc::from("sender"))).with_span(span);
+        let envelope = Arc::new(envelope);
+        assert!(!envelope.span().is_none());
+
+        let retained = RetainedEvents::new();
+        retained.retain(&DefaultTopic, &envelope, 1);
+        let stored = retained.get(&DefaultTopic);
+        assert_eq!(stored[0].id(), envelope.id());
+        assert!(stored[0].span().is_none());
+    }
+}
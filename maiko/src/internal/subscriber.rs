@@ -1,14 +1,35 @@
-use std::{hash, sync::Arc};
+use std::{fmt, hash, sync::Arc};
 
 use tokio::sync::mpsc::Sender;
 
 use crate::{ActorId, Envelope, Event, Topic, internal::Subscription};
 
-#[derive(Debug)]
+/// Narrows a subscription below topic level: events for which it returns
+/// `false` are not dispatched to the subscriber.
+pub(crate) type EventFilter<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// What an actor is registered to receive and to publish.
+pub(crate) struct Routing<E, T: Eq + hash::Hash> {
+    pub topics: Subscription<T>,
+    pub filter: Option<EventFilter<E>>,
+    pub publications: Option<Subscription<T>>,
+}
+
+impl<E, T: Eq + hash::Hash> Routing<E, T> {
+    pub fn new(topics: Subscription<T>) -> Self {
+        Self {
+            topics,
+            filter: None,
+            publications: None,
+        }
+    }
+}
+
 pub(crate) struct Subscriber<E, T: Eq + hash::Hash> {
     pub actor_id: ActorId,
     pub topics: Subscription<T>,
     pub sender: Sender<Arc<Envelope<E>>>,
+    pub filter: Option<EventFilter<E>>,
 }
 
 impl<E, T: Eq + hash::Hash> Subscriber<E, T> {
@@ -21,14 +42,35 @@ impl<E, T: Eq + hash::Hash> Subscriber<E, T> {
             actor_id,
             topics,
             sender,
+            filter: None,
         }
     }
 
+    pub fn with_filter(mut self, filter: Option<EventFilter<E>>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Whether the subscriber wants `event`, given its topic matched.
+    pub fn accepts(&self, event: &E) -> bool {
+        self.filter.as_ref().is_none_or(|f| f(event))
+    }
+
     pub fn is_closed(&self) -> bool {
         self.sender.is_closed()
     }
 }
 
+impl<E, T: Eq + hash::Hash + fmt::Debug> fmt::Debug for Subscriber<E, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("actor_id", &self.actor_id)
+            .field("topics", &self.topics)
+            .field("filtered", &self.filter.is_some())
+            .finish()
+    }
+}
+
 impl<E, T: Eq + hash::Hash> PartialEq for Subscriber<E, T> {
     fn eq(&self, other: &Self) -> bool {
         self.actor_id == other.actor_id
@@ -22,4 +22,13 @@ impl<T: Eq + Hash> Subscription<T> {
             Subscription::None => false,
         }
     }
+
+    /// Whether no topic is included.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Subscription::All => false,
+            Subscription::Topics(topics) => topics.is_empty(),
+            Subscription::None => true,
+        }
+    }
 }
@@ -53,6 +53,7 @@
 //! | [`Envelope`] | Wraps events with metadata (sender, correlation ID) |
 //! | [`ActorId`] | Unique identifier for a registered actor |
 //! | [`OverflowPolicy`] | Controls behavior when a subscriber's channel is full |
+//! | [`Interceptor`] | Inspects, modifies or drops events before routing |
 //!
 //! ## Topic-Based Routing
 //!
@@ -108,9 +109,15 @@
 //!
 //! - **`macros`** (default) - `#[derive(Event)]`, `#[derive(Label)]`, and `#[derive(SelfRouting)]` macros
 //! - **`monitoring`** - Event lifecycle hooks for debugging, metrics, and logging
+//! - **`metrics`** - Built-in `Metrics` monitor with Prometheus text exposition (enables `monitoring`)
 //! - **`test-harness`** - Test utilities for recording, spying, and asserting on event flow (enables `monitoring`)
-//! - **`serde`** - JSON serialization support (e.g. `Supervisor::to_json()`)
-//! - **`recorder`** - Built-in `Recorder` monitor for writing events to JSON Lines files (enables `monitoring` and `serde`)
+//! - **`serde`** - JSON serialization support (e.g. `Supervisor::to_json()`) and JSON Lines [`actors`]
+//! - **`bridge`** - [`bridge`] actors for cross-process communication over Unix and TCP sockets (enables `serde`)
+//! - **`recorder`** - Built-in `Recorder` monitor for writing events to JSON Lines files, and [`replay`] to re-inject them (enables `monitoring` and `serde`)
+//! - **`gzip`** - Compression of files rotated by the `Recorder` (enables `recorder`)
+//! - **`postcard`** - Compact binary [`codec`] for recordings and bridges (enables `serde`)
+//! - **`msgpack`** - MessagePack [`codec`] for recordings and bridges (enables `serde`)
+//! - **`event-log`** - Persistent, segmented [`event_log`] for durable topics (enables `serde`)
 //!
 //! ## Examples
 //!
@@ -129,16 +136,40 @@ mod context;
 mod envelope;
 mod error;
 mod event;
+mod interceptor;
 mod label;
 mod meta;
+mod narrow_actor;
 mod overflow_policy;
 mod step_action;
 mod subscribe;
 mod supervisor;
 mod topic;
+mod undeclared_topic_policy;
+mod validation_report;
 
 mod internal;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod actors;
+
+#[cfg(feature = "bridge")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bridge")))]
+pub mod bridge;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod codec;
+
+#[cfg(feature = "event-log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "event-log")))]
+pub mod event_log;
+
+#[cfg(feature = "recorder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+pub mod replay;
+
 #[cfg(feature = "test-harness")]
 #[cfg_attr(docsrs, doc(cfg(feature = "test-harness")))]
 pub mod testing;
@@ -160,17 +191,25 @@ pub use context::Context;
 pub use envelope::Envelope;
 pub use error::Error;
 pub use event::Event;
+pub use interceptor::{InterceptAction, Interceptor};
 pub use label::Label;
 pub use meta::Meta;
+pub use narrow_actor::NarrowActor;
 pub use overflow_policy::OverflowPolicy;
 pub use step_action::StepAction;
 pub use subscribe::Subscribe;
 pub use supervisor::Supervisor;
 pub use topic::{DefaultTopic, Topic};
+pub use undeclared_topic_policy::UndeclaredTopicPolicy;
+pub use validation_report::ValidationReport;
 
 #[cfg(feature = "macros")]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 pub use maiko_macros::{Event, Label, SelfRouting};
 
+#[cfg(feature = "recorder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+pub use replay::Replayer;
+
 pub type Result<T = ()> = std::result::Result<T, Error>;
 pub type EventId = u128;
@@ -11,6 +11,7 @@ use crate::{ActorId, EventId};
 /// - `actor_name`: actor name emitting the event.
 /// - `correlation_id`: optional id to link related events together.  Useful for
 ///   tracing and debugging event flows.
+/// - `origin`: for events forwarded by a bridge, the actor that first sent them.
 ///
 /// There is no logic at Maiko built around the `correlation_id`, so the value doesn't
 /// have any special meaning to the runtime.  It's up to the user to set and interpret it.
@@ -23,6 +24,8 @@ pub struct Meta {
     timestamp: u64,
     actor_id: ActorId,
     correlation_id: Option<EventId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    origin: Option<ActorId>,
 }
 
 impl Meta {
@@ -40,6 +43,7 @@ impl Meta {
                 .as_nanos() as u64,
             actor_id,
             correlation_id,
+            origin: None,
         }
     }
 
@@ -67,6 +71,22 @@ impl Meta {
     pub fn correlation_id(&self) -> Option<EventId> {
         self.correlation_id
     }
+
+    /// The actor that first sent a forwarded event, on the other side of a
+    /// bridge. `None` for events sent in this supervisor.
+    pub fn origin(&self) -> Option<&ActorId> {
+        self.origin.as_ref()
+    }
+
+    /// Copy of this metadata re-sent by a bridge actor, keeping the first
+    /// sender as [`origin`](Self::origin).
+    pub(crate) fn forwarded_by(&self, actor_id: ActorId) -> Self {
+        Self {
+            origin: Some(self.origin.clone().unwrap_or_else(|| self.actor_id.clone())),
+            actor_id,
+            ..self.clone()
+        }
+    }
 }
 
 impl fmt::Display for Meta {
@@ -81,6 +101,9 @@ impl fmt::Display for Meta {
         if let Some(correlation_id) = self.correlation_id() {
             write!(f, ", correlation_id: {}", correlation_id)?;
         }
+        if let Some(origin) = self.origin() {
+            write!(f, ", origin: {}", origin.name())?;
+        }
         write!(f, "}}")?;
         Ok(())
     }
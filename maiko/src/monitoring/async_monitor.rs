@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use crate::{
+    ActorId, DefaultTopic, Envelope, Event, OverflowPolicy, StepAction, Topic,
+    monitoring::QueueDepth,
+};
+
+/// A monitor that runs on its own task, for callbacks that need to `.await`
+/// or take long enough to slow the other monitors down (writing to a
+/// database, sending over the network).
+///
+/// Register it with
+/// [`MonitorRegistry::add_async`](crate::monitoring::MonitorRegistry::add_async);
+/// the returned [`MonitorHandle`](crate::monitoring::MonitorHandle) pauses,
+/// filters, flushes and removes it like any other monitor. The dispatcher
+/// hands callbacks to the monitor's task through a bounded queue of
+/// [`queue_capacity`](Self::queue_capacity) and applies
+/// [`overflow_policy`](Self::overflow_policy) when the queue is full:
+///
+/// | Policy | On full queue |
+/// |--------|---------------|
+/// | [`Drop`](OverflowPolicy::Drop) (default) | Discard the callback; [`on_lagged`](Self::on_lagged) reports the count later |
+/// | [`Block`](OverflowPolicy::Block) | Wait for space, holding up every other monitor |
+/// | [`Fail`](OverflowPolicy::Fail) | Remove the monitor |
+///
+/// Callbacks mirror [`Monitor`](crate::monitoring::Monitor), take
+/// `&mut self`, and run one at a time in dispatch order. Stopping the
+/// supervisor waits for queued callbacks to finish.
+///
+/// # Example
+///
+/// ```ignore
+/// struct AuditWriter {
+///     db: Database,
+/// }
+///
+/// impl AsyncMonitor<MyEvent, MyTopic> for AuditWriter {
+///     async fn on_event_handled(&mut self, envelope: &Envelope<MyEvent>, _: &MyTopic, _: &ActorId) {
+///         self.db.insert(envelope.id()).await;
+///     }
+/// }
+///
+/// let handle = sup.monitors().add_async(AuditWriter { db }).await;
+/// ```
+pub trait AsyncMonitor<E: Event, T: Topic<E> = DefaultTopic>: Send + 'static {
+    /// Capacity of the queue between the dispatcher and this monitor's task.
+    ///
+    /// Read once, when the monitor is registered. Zero is treated as one.
+    fn queue_capacity(&self) -> usize {
+        128
+    }
+
+    /// What the dispatcher does when this monitor's queue is full.
+    ///
+    /// Read once, when the monitor is registered.
+    fn overflow_policy(&self) -> OverflowPolicy {
+        OverflowPolicy::Drop
+    }
+
+    /// Called when the broker dispatches an event to a subscriber.
+    fn on_event_dispatched(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _t = topic;
+        let _r = receiver;
+        async {}
+    }
+
+    /// Called when the broker dispatches a [retained](crate::Topic::retention)
+    /// event, published earlier, to an actor added at runtime.
+    ///
+    /// Defaults to [`on_event_dispatched`](Self::on_event_dispatched).
+    fn on_event_replayed(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        self.on_event_dispatched(envelope, topic, receiver)
+    }
+
+    /// Called when an actor receives an event from its mailbox.
+    fn on_event_delivered(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _t = topic;
+        let _r = receiver;
+        async {}
+    }
+
+    /// Called after an actor finishes processing an event.
+    fn on_event_handled(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _t = topic;
+        let _r = receiver;
+        async {}
+    }
+
+    /// Called when a new actor is registered in the system.
+    fn on_actor_registered(&mut self, actor_id: &ActorId) -> impl Future<Output = ()> + Send {
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when an actor's handler returns an error.
+    fn on_error(&mut self, err: &str, actor_id: &ActorId) -> impl Future<Output = ()> + Send {
+        let _e = err;
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when an actor enters its `step()` method.
+    ///
+    /// Only made when enabled with
+    /// [`Config::with_step_monitoring`](crate::Config::with_step_monitoring).
+    fn on_step_enter(&mut self, actor_id: &ActorId) -> impl Future<Output = ()> + Send {
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when an actor exits its `step()` method.
+    fn on_step_exit(
+        &mut self,
+        step_action: &StepAction,
+        actor_id: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _s = step_action;
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when an actor exits its `step()` method, with the time it took.
+    ///
+    /// Defaults to [`on_step_exit`](Self::on_step_exit).
+    fn on_step_exit_timed(
+        &mut self,
+        step_action: &StepAction,
+        duration: Duration,
+        actor_id: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _d = duration;
+        self.on_step_exit(step_action, actor_id)
+    }
+
+    /// Called when an actor's `step()` returns an error, with the time it took.
+    fn on_step_error(
+        &mut self,
+        err: &str,
+        duration: Duration,
+        actor_id: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = err;
+        let _d = duration;
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when an actor's `step()` is cancelled before returning, with
+    /// the time it ran.
+    fn on_step_cancel(
+        &mut self,
+        duration: Duration,
+        actor_id: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _d = duration;
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called when a subscriber's channel is full and an overflow policy is triggered.
+    fn on_overflow(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+        policy: OverflowPolicy,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _t = topic;
+        let _r = receiver;
+        let _p = policy;
+        async {}
+    }
+
+    /// Called when an [`Interceptor`](crate::Interceptor) drops an event before routing.
+    ///
+    /// `interceptor` is the name the interceptor was registered under.
+    fn on_event_dropped(
+        &mut self,
+        envelope: &Envelope<E>,
+        interceptor: &str,
+        reason: &str,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _i = interceptor;
+        let _r = reason;
+        async {}
+    }
+
+    /// Called when the broker drops an event sent to a `topic` its sender
+    /// didn't declare, under
+    /// [`UndeclaredTopicPolicy::Error`](crate::UndeclaredTopicPolicy::Error).
+    fn on_event_rejected(
+        &mut self,
+        envelope: &Envelope<E>,
+        topic: &T,
+    ) -> impl Future<Output = ()> + Send {
+        let _e = envelope;
+        let _t = topic;
+        async {}
+    }
+
+    /// Called with the sampled queue depths of an actor's channels.
+    fn on_queue_depth(
+        &mut self,
+        depth: &QueueDepth,
+        actor_id: &ActorId,
+    ) -> impl Future<Output = ()> + Send {
+        let _d = depth;
+        let _a = actor_id;
+        async {}
+    }
+
+    /// Called with the number of callbacks this monitor lost, either
+    /// because the monitoring channel was full or because its own queue
+    /// was full under [`OverflowPolicy::Drop`].
+    fn on_lagged(&mut self, count: u64) -> impl Future<Output = ()> + Send {
+        let _c = count;
+        async {}
+    }
+
+    /// Called when an actor stops.
+    fn on_actor_stop(&mut self, actor_id: &ActorId) -> impl Future<Output = ()> + Send {
+        let _a = actor_id;
+        async {}
+    }
+}
@@ -0,0 +1,347 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    task::JoinHandle,
+};
+
+use crate::{
+    Event, OverflowPolicy, Topic,
+    monitoring::{AsyncMonitor, MonitoringEvent},
+};
+
+/// Dispatcher side of an [`AsyncMonitor`]: the bounded queue feeding the
+/// monitor's task, and the task itself.
+pub(crate) struct AsyncQueue<E, T> {
+    sender: mpsc::Sender<MonitoringEvent<E, T>>,
+    policy: OverflowPolicy,
+    lagged: u64,
+    pending: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl<E: Event, T: Topic<E>> AsyncQueue<E, T> {
+    /// Spawn a task running `monitor`.
+    pub fn spawn<M: AsyncMonitor<E, T>>(monitor: M) -> Self {
+        let (sender, receiver) = mpsc::channel(monitor.queue_capacity().max(1));
+        let policy = monitor.overflow_policy();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let task = tokio::spawn(run(monitor, receiver, pending.clone()));
+        Self {
+            sender,
+            policy,
+            lagged: 0,
+            pending,
+            task,
+        }
+    }
+
+    /// Queue a callback for the monitor, reporting earlier drops first.
+    ///
+    /// Returns the reason the monitor should be removed: its task is gone,
+    /// or its queue is full under [`OverflowPolicy::Fail`].
+    pub async fn push(&mut self, event: &MonitoringEvent<E, T>) -> Result<(), &'static str> {
+        if self.lagged > 0 {
+            if !self.send(MonitoringEvent::Lagged(self.lagged)).await? {
+                self.lagged += 1;
+                return Ok(());
+            }
+            self.lagged = 0;
+        }
+        if !self.send(event.clone()).await? {
+            self.lagged += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the monitor has finished every queued callback, or
+    /// its task is gone (a panicking callback never finishes the rest).
+    pub fn is_idle(&self) -> bool {
+        self.pending.load(Ordering::Acquire) == 0 || self.is_finished()
+    }
+
+    /// Returns `true` if the monitor's task has ended, e.g. by panicking.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Close the queue and wait for the task to finish the queued callbacks.
+    pub async fn close(self) {
+        drop(self.sender);
+        let _ = self.task.await;
+    }
+
+    /// Returns `false` if the event was dropped.
+    async fn send(&self, event: MonitoringEvent<E, T>) -> Result<bool, &'static str> {
+        self.pending.fetch_add(1, Ordering::AcqRel);
+        let sent = self.try_send(event).await;
+        if !matches!(sent, Ok(true)) {
+            self.pending.fetch_sub(1, Ordering::AcqRel);
+        }
+        sent
+    }
+
+    async fn try_send(&self, event: MonitoringEvent<E, T>) -> Result<bool, &'static str> {
+        if self.policy.is_block() {
+            return match self.sender.send(event).await {
+                Ok(()) => Ok(true),
+                Err(_) => Err("task stopped"),
+            };
+        }
+        match self.sender.try_send(event) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) if self.policy.is_drop() => Ok(false),
+            Err(TrySendError::Full(_)) => Err("queue full"),
+            Err(TrySendError::Closed(_)) => Err("task stopped"),
+        }
+    }
+}
+
+async fn run<E: Event, T: Topic<E>, M: AsyncMonitor<E, T>>(
+    mut monitor: M,
+    mut receiver: mpsc::Receiver<MonitoringEvent<E, T>>,
+    pending: Arc<AtomicUsize>,
+) {
+    use MonitoringEvent::*;
+    while let Some(event) = receiver.recv().await {
+        match &event {
+            EventDispatched(envelope, topic, actor_id) => {
+                monitor.on_event_dispatched(envelope, topic, actor_id).await
+            }
+            EventReplayed(envelope, topic, actor_id) => {
+                monitor.on_event_replayed(envelope, topic, actor_id).await
+            }
+            EventDelivered(envelope, topic, actor_id) => {
+                monitor.on_event_delivered(envelope, topic, actor_id).await
+            }
+            EventHandled(envelope, topic, actor_id) => {
+                monitor.on_event_handled(envelope, topic, actor_id).await
+            }
+            Overflow(envelope, topic, actor_id, policy) => {
+                monitor
+                    .on_overflow(envelope, topic, actor_id, *policy)
+                    .await
+            }
+            EventDropped(envelope, interceptor, reason) => {
+                monitor
+                    .on_event_dropped(envelope, interceptor, reason)
+                    .await
+            }
+            EventRejected(envelope, topic) => monitor.on_event_rejected(envelope, topic).await,
+            Error(error, actor_id) => monitor.on_error(error, actor_id).await,
+            ActorRegistered(actor_id) => monitor.on_actor_registered(actor_id).await,
+            ActorStopped(actor_id) => monitor.on_actor_stop(actor_id).await,
+            StepEntered(actor_id) => monitor.on_step_enter(actor_id).await,
+            StepExited(action, duration, actor_id) => {
+                monitor
+                    .on_step_exit_timed(action, *duration, actor_id)
+                    .await
+            }
+            StepFailed(error, duration, actor_id) => {
+                monitor.on_step_error(error, *duration, actor_id).await
+            }
+            StepCancelled(duration, actor_id) => monitor.on_step_cancel(*duration, actor_id).await,
+            QueueDepth(actor_id, depth) => monitor.on_queue_depth(depth, actor_id).await,
+            Lagged(count) => monitor.on_lagged(*count).await,
+        }
+        pending.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::sync::Notify;
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic};
+
+    #[derive(Clone, Debug)]
+    struct TestEvent;
+    impl Event for TestEvent {}
+
+    type Queue = AsyncQueue<TestEvent, DefaultTopic>;
+
+    /// Records callbacks, holding the first one until released.
+    struct Gated {
+        policy: OverflowPolicy,
+        gate: Arc<Notify>,
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AsyncMonitor<TestEvent> for Gated {
+        fn queue_capacity(&self) -> usize {
+            2
+        }
+
+        fn overflow_policy(&self) -> OverflowPolicy {
+            self.policy
+        }
+
+        async fn on_actor_registered(&mut self, actor_id: &ActorId) {
+            if self.seen.lock().unwrap().is_empty() {
+                self.gate.notified().await;
+            }
+            self.seen.lock().unwrap().push(actor_id.name().to_string());
+        }
+
+        async fn on_lagged(&mut self, count: u64) {
+            self.seen.lock().unwrap().push(format!("lagged {count}"));
+        }
+    }
+
+    struct Panicking;
+    impl AsyncMonitor<TestEvent> for Panicking {
+        async fn on_actor_registered(&mut self, _: &ActorId) {
+            panic!("monitor bug");
+        }
+    }
+
+    fn gated(policy: OverflowPolicy) -> (Queue, Arc<Notify>, Arc<Mutex<Vec<String>>>) {
+        let gate = Arc::new(Notify::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let monitor = Gated {
+            policy,
+            gate: gate.clone(),
+            seen: seen.clone(),
+        };
+        (AsyncQueue::spawn(monitor), gate, seen)
+    }
+
+    fn registered(name: &str) -> MonitoringEvent<TestEvent, DefaultTopic> {
+        MonitoringEvent::ActorRegistered(ActorId::new(Arc::from(name)))
+    }
+
+    /// Fill the queue while the task is held at the first callback.
+    async fn fill(queue: &mut Queue) {
+        queue.push(&registered("a")).await.unwrap();
+        wait_empty(queue).await;
+        queue.push(&registered("b")).await.unwrap();
+        queue.push(&registered("c")).await.unwrap();
+    }
+
+    /// Wait for the task to take every queued callback.
+    async fn wait_empty(queue: &Queue) {
+        while queue.sender.capacity() < queue.sender.max_capacity() {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_policy_reports_lost_callbacks() {
+        let (mut queue, gate, seen) = gated(OverflowPolicy::Drop);
+        fill(&mut queue).await;
+        queue.push(&registered("d")).await.unwrap();
+        queue.push(&registered("e")).await.unwrap();
+        gate.notify_one();
+        wait_empty(&queue).await;
+        queue.push(&registered("f")).await.unwrap();
+        queue.close().await;
+        assert_eq!(*seen.lock().unwrap(), ["a", "b", "c", "lagged 2", "f"]);
+    }
+
+    #[tokio::test]
+    async fn fail_policy_rejects_when_full() {
+        let (mut queue, gate, _) = gated(OverflowPolicy::Fail);
+        fill(&mut queue).await;
+        assert_eq!(queue.push(&registered("d")).await, Err("queue full"));
+        gate.notify_one();
+        queue.close().await;
+    }
+
+    #[tokio::test]
+    async fn close_waits_for_queued_callbacks() {
+        let (mut queue, gate, seen) = gated(OverflowPolicy::Block);
+        fill(&mut queue).await;
+        gate.notify_one();
+        queue.close().await;
+        assert_eq!(*seen.lock().unwrap(), ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn panicked_task_counts_as_idle() {
+        let mut queue: Queue = AsyncQueue::spawn(Panicking);
+        queue.push(&registered("a")).await.unwrap();
+        queue.push(&registered("b")).await.unwrap();
+        while !queue.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        assert!(queue.is_idle());
+    }
+
+    #[tokio::test]
+    async fn flush_removes_panicked_monitor() {
+        use std::time::Duration;
+
+        use crate::{Actor, Config, Subscribe, Supervisor};
+
+        struct Sink;
+        impl Actor for Sink {
+            type Event = TestEvent;
+        }
+
+        let mut sup = Supervisor::<TestEvent>::new(Config::default());
+        let handle = sup.monitors().add_async(Panicking).await;
+        sup.add_actor("a", |_| Sink, Subscribe::all()).unwrap();
+        sup.add_actor("b", |_| Sink, Subscribe::all()).unwrap();
+        sup.start().await.unwrap();
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            handle.flush(Duration::from_millis(1)),
+        )
+        .await
+        .expect("flush must not wait on a panicked monitor");
+        sup.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn runs_through_the_registry() {
+        use std::time::Duration;
+
+        use crate::{Actor, Config, Envelope, Subscribe, Supervisor};
+
+        #[derive(Clone, Default)]
+        struct Slow(Arc<Mutex<u32>>);
+        impl AsyncMonitor<TestEvent> for Slow {
+            async fn on_event_handled(
+                &mut self,
+                _: &Envelope<TestEvent>,
+                _: &DefaultTopic,
+                _: &ActorId,
+            ) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                *self.0.lock().unwrap() += 1;
+            }
+        }
+
+        struct Sink;
+        impl Actor for Sink {
+            type Event = TestEvent;
+        }
+
+        let mut sup = Supervisor::<TestEvent>::new(Config::default());
+        let monitor = Slow::default();
+        let handle = sup.monitors().add_async(monitor.clone()).await;
+        sup.add_actor("sink", |_| Sink, Subscribe::all()).unwrap();
+        sup.start().await.unwrap();
+        for _ in 0..3 {
+            sup.send(TestEvent).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        handle.flush(Duration::from_millis(1)).await;
+        assert_eq!(*monitor.0.lock().unwrap(), 3);
+
+        sup.send(TestEvent).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        sup.stop().await.unwrap();
+        assert_eq!(
+            *monitor.0.lock().unwrap(),
+            4,
+            "stop waits for queued callbacks"
+        );
+    }
+}
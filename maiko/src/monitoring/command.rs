@@ -4,12 +4,17 @@ use tokio::sync::oneshot;
 
 use crate::{
     Event, Topic,
-    monitoring::{Monitor, MonitorId, MonitoringEvent},
+    monitoring::{MonitorFilter, MonitorId, MonitorKind, MonitoringEvent},
 };
 
 pub(crate) enum MonitorCommand<E: Event, T: Topic<E>> {
-    AddMonitor(Box<dyn Monitor<E, T>>, oneshot::Sender<MonitorId>),
+    AddMonitor(
+        MonitorKind<E, T>,
+        Option<MonitorFilter<E, T>>,
+        oneshot::Sender<MonitorId>,
+    ),
     RemoveMonitor(MonitorId),
+    SetFilter(MonitorId, Option<MonitorFilter<E, T>>),
     PauseAll,
     ResumeAll,
     PauseOne(MonitorId),
@@ -17,21 +17,41 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{
     Event, Topic,
-    monitoring::{Monitor, MonitorCommand, MonitorId, MonitoringEvent},
+    monitoring::{AsyncQueue, Monitor, MonitorCommand, MonitorFilter, MonitorId, MonitoringEvent},
 };
 
+/// A registered monitor: called on the dispatcher task, or fed through the
+/// queue of its own task.
+pub(crate) enum MonitorKind<E: Event, T: Topic<E>> {
+    Sync(Box<dyn Monitor<E, T>>),
+    Async(AsyncQueue<E, T>),
+}
+
 struct MonitorEntry<E: Event, T: Topic<E>> {
-    monitor: Box<dyn Monitor<E, T>>,
+    monitor: MonitorKind<E, T>,
+    filter: Option<MonitorFilter<E, T>>,
     paused: bool,
 }
 
 impl<E: Event, T: Topic<E>> MonitorEntry<E, T> {
-    fn new(monitor: Box<dyn Monitor<E, T>>) -> Self {
+    fn new(monitor: MonitorKind<E, T>, filter: Option<MonitorFilter<E, T>>) -> Self {
         Self {
             monitor,
+            filter,
             paused: false,
         }
     }
+
+    fn accepts(&self, event: &MonitoringEvent<E, T>) -> bool {
+        !self.paused && self.filter.as_ref().is_none_or(|f| f.accepts(event))
+    }
+
+    fn is_idle(&self) -> bool {
+        match &self.monitor {
+            MonitorKind::Sync(_) => true,
+            MonitorKind::Async(queue) => queue.is_idle(),
+        }
+    }
 }
 
 pub(crate) struct MonitorDispatcher<E: Event, T: Topic<E>> {
@@ -87,9 +107,29 @@ impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
         self.update_is_active();
     }
 
+    /// Removes async monitors whose task has ended, which only happens when
+    /// one of their callbacks panicked.
+    fn remove_finished_monitors(&mut self) {
+        for (id, entry) in &self.monitors {
+            if let MonitorKind::Async(queue) = &entry.monitor {
+                if queue.is_finished() {
+                    tracing::error!(monitor_id = %id, "Async monitor panicked, removing");
+                    self.ids_to_remove.push(*id);
+                }
+            }
+        }
+        while let Some(id) = self.ids_to_remove.pop() {
+            self.remove_monitor(id);
+        }
+    }
+
     fn try_complete_flush(&mut self) {
+        self.remove_finished_monitors();
         if let Some((_, settle_window)) = &self.flush_pending {
-            if self.receiver.is_empty() && self.last_activity.elapsed() >= *settle_window {
+            if self.receiver.is_empty()
+                && self.monitors.values().all(MonitorEntry::is_idle)
+                && self.last_activity.elapsed() >= *settle_window
+            {
                 if let Some((response, _)) = self.flush_pending.take() {
                     let _ = response.send(());
                 }
@@ -107,21 +147,27 @@ impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
                 }
                 Some(cmd) = self.receiver.recv() => {
                     self.last_activity = Instant::now();
-                    self.handle_command(cmd);
+                    self.handle_command(cmd).await;
                 }
                 _ = tokio::time::sleep(FLUSH_CHECK_INTERVAL), if self.flush_pending.is_some() => {
                     self.try_complete_flush();
                 }
             }
         }
+
+        for (_, entry) in self.monitors.drain() {
+            if let MonitorKind::Async(queue) = entry.monitor {
+                queue.close().await;
+            }
+        }
     }
 
-    fn handle_command(&mut self, cmd: MonitorCommand<E, T>) {
+    async fn handle_command(&mut self, cmd: MonitorCommand<E, T>) {
         use MonitorCommand::*;
         match cmd {
-            AddMonitor(monitor, resp) => {
+            AddMonitor(monitor, filter, resp) => {
                 let id = self.last_id;
-                self.monitors.insert(id, MonitorEntry::new(monitor));
+                self.monitors.insert(id, MonitorEntry::new(monitor, filter));
                 self.last_id += 1;
                 self.update_is_active();
                 let _ = resp.send(id);
@@ -129,6 +175,11 @@ impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
             RemoveMonitor(id) => {
                 self.remove_monitor(id);
             }
+            SetFilter(id, filter) => {
+                if let Some(entry) = self.monitors.get_mut(&id) {
+                    entry.filter = filter;
+                }
+            }
             PauseAll => {
                 self.set_monitors_paused_to_all(true);
             }
@@ -142,7 +193,7 @@ impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
                 self.set_monitor_paused(id, false);
             }
             DispatchEvent(event) if self.is_active.load(Ordering::Relaxed) => {
-                self.handle_event(event);
+                self.handle_event(event).await;
             }
             Flush {
                 response,
@@ -155,45 +206,106 @@ impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
         }
     }
 
-    fn handle_event(&mut self, event: MonitoringEvent<E, T>) {
+    async fn handle_event(&mut self, event: MonitoringEvent<E, T>) {
         use MonitoringEvent::*;
-        match event {
+        match &event {
             EventDispatched(envelope, topic, actor_id) => {
-                self.notify(|m| m.on_event_dispatched(&envelope, &topic, &actor_id));
+                self.notify(&event, |m| m.on_event_dispatched(envelope, topic, actor_id))
+                    .await;
+            }
+            EventReplayed(envelope, topic, actor_id) => {
+                self.notify(&event, |m| m.on_event_replayed(envelope, topic, actor_id))
+                    .await;
             }
             EventDelivered(envelope, topic, actor_id) => {
-                self.notify(|m| m.on_event_delivered(&envelope, &topic, &actor_id));
+                self.notify(&event, |m| m.on_event_delivered(envelope, topic, actor_id))
+                    .await;
             }
             EventHandled(envelope, topic, actor_id) => {
-                self.notify(|m| m.on_event_handled(&envelope, &topic, &actor_id));
+                self.notify(&event, |m| m.on_event_handled(envelope, topic, actor_id))
+                    .await;
             }
             Overflow(envelope, topic, actor_id, policy) => {
-                self.notify(|m| m.on_overflow(&envelope, &topic, &actor_id, policy));
+                self.notify(&event, |m| {
+                    m.on_overflow(envelope, topic, actor_id, *policy)
+                })
+                .await;
+            }
+            EventDropped(envelope, interceptor, reason) => {
+                self.notify(&event, |m| {
+                    m.on_event_dropped(envelope, interceptor, reason)
+                })
+                .await;
+            }
+            EventRejected(envelope, topic) => {
+                self.notify(&event, |m| m.on_event_rejected(envelope, topic))
+                    .await;
             }
             Error(error, actor_id) => {
-                self.notify(|m| m.on_error(&error, &actor_id));
+                self.notify(&event, |m| m.on_error(error, actor_id)).await;
             }
             ActorRegistered(actor_id) => {
-                self.notify(|m| m.on_actor_registered(&actor_id));
+                self.notify(&event, |m| m.on_actor_registered(actor_id))
+                    .await;
             }
             ActorStopped(actor_id) => {
-                self.notify(|m| m.on_actor_stop(&actor_id));
+                self.notify(&event, |m| m.on_actor_stop(actor_id)).await;
+            }
+            StepEntered(actor_id) => {
+                self.notify(&event, |m| m.on_step_enter(actor_id)).await;
+            }
+            StepExited(action, duration, actor_id) => {
+                self.notify(&event, |m| {
+                    m.on_step_exit_timed(action, *duration, actor_id)
+                })
+                .await;
+            }
+            StepFailed(error, duration, actor_id) => {
+                self.notify(&event, |m| m.on_step_error(error, *duration, actor_id))
+                    .await;
+            }
+            StepCancelled(duration, actor_id) => {
+                self.notify(&event, |m| m.on_step_cancel(*duration, actor_id))
+                    .await;
+            }
+            QueueDepth(actor_id, depth) => {
+                self.notify(&event, |m| m.on_queue_depth(depth, actor_id))
+                    .await;
+            }
+            Lagged(count) => {
+                self.notify(&event, |m| m.on_lagged(*count)).await;
             }
         }
     }
 }
 
 impl<E: Event, T: Topic<E>> MonitorDispatcher<E, T> {
-    fn notify(&mut self, f: impl Fn(&dyn Monitor<E, T>)) {
-        for (id, entry) in &self.monitors {
-            if entry.paused {
+    /// Calls `f` on each synchronous monitor accepting `event`, and queues
+    /// `event` for each asynchronous one.
+    async fn notify(
+        &mut self,
+        event: &MonitoringEvent<E, T>,
+        f: impl Fn(&dyn Monitor<E, T>) + Send,
+    ) {
+        for (id, entry) in &mut self.monitors {
+            if !entry.accepts(event) {
                 continue;
             }
 
-            let result = catch_unwind(AssertUnwindSafe(|| f(entry.monitor.as_ref())));
-            if result.is_err() {
-                tracing::error!(monitor_id = %id, "Monitor panicked, removing");
-                self.ids_to_remove.push(*id);
+            match &mut entry.monitor {
+                MonitorKind::Sync(monitor) => {
+                    let result = catch_unwind(AssertUnwindSafe(|| f(monitor.as_ref())));
+                    if result.is_err() {
+                        tracing::error!(monitor_id = %id, "Monitor panicked, removing");
+                        self.ids_to_remove.push(*id);
+                    }
+                }
+                MonitorKind::Async(queue) => {
+                    if let Err(reason) = queue.push(event).await {
+                        tracing::error!(monitor_id = %id, reason, "Async monitor failed, removing");
+                        self.ids_to_remove.push(*id);
+                    }
+                }
             }
         }
 
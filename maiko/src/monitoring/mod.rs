@@ -30,22 +30,32 @@
 //! let handle = sup.monitors().add(EventLogger).await;
 //! ```
 
+mod async_monitor;
+mod async_queue;
 mod command;
 mod dispatcher;
 mod monitor;
+mod monitor_filter;
 mod monitor_handle;
 mod monitoring_event;
+mod queue_depth;
 mod registry;
 mod sink;
+mod stage;
 
 /// Unique identifier for a registered monitor.
 pub type MonitorId = u16;
 
+pub use async_monitor::AsyncMonitor;
+pub(crate) use async_queue::AsyncQueue;
 pub(crate) use command::MonitorCommand;
-pub(crate) use dispatcher::MonitorDispatcher;
+pub(crate) use dispatcher::{MonitorDispatcher, MonitorKind};
 pub use monitor::Monitor;
+pub use monitor_filter::MonitorFilter;
 pub use monitor_handle::MonitorHandle;
 pub(crate) use monitoring_event::MonitoringEvent;
+pub use queue_depth::{ChannelDepth, QueueDepth};
 pub use registry::MonitorRegistry;
+pub use stage::Stage;
 
-pub(crate) use sink::MonitoringSink;
+pub(crate) use sink::{LostEvents, MonitoringSink};
@@ -1,4 +1,9 @@
-use crate::{ActorId, DefaultTopic, Envelope, Event, OverflowPolicy, StepAction, Topic};
+use std::time::Duration;
+
+use crate::{
+    ActorId, DefaultTopic, Envelope, Event, OverflowPolicy, StepAction, Topic,
+    monitoring::QueueDepth,
+};
 
 /// Trait for observing event flow through the system.
 ///
@@ -43,6 +48,14 @@ pub trait Monitor<E: Event, T: Topic<E> = DefaultTopic>: Send {
         let _r = receiver;
     }
 
+    /// Called when the broker dispatches a [retained](crate::Topic::retention)
+    /// event, published earlier, to an actor added at runtime.
+    ///
+    /// Defaults to [`on_event_dispatched`](Self::on_event_dispatched).
+    fn on_event_replayed(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        self.on_event_dispatched(envelope, topic, receiver);
+    }
+
     /// Called when an actor receives an event from its mailbox.
     ///
     /// This fires just before `handle_event()` is called.
@@ -75,6 +88,13 @@ pub trait Monitor<E: Event, T: Topic<E> = DefaultTopic>: Send {
     }
 
     /// Called when an actor enters its `step()` method.
+    ///
+    /// Step callbacks are only made when enabled with
+    /// [`Config::with_step_monitoring`](crate::Config::with_step_monitoring).
+    /// Every enter is followed by exactly one of
+    /// [`on_step_exit_timed`](Self::on_step_exit_timed),
+    /// [`on_step_error`](Self::on_step_error) or
+    /// [`on_step_cancel`](Self::on_step_cancel).
     fn on_step_enter(&self, actor_id: &ActorId) {
         let _a = actor_id;
     }
@@ -85,6 +105,34 @@ pub trait Monitor<E: Event, T: Topic<E> = DefaultTopic>: Send {
         let _a = actor_id;
     }
 
+    /// Called when an actor exits its `step()` method, with the time it took.
+    ///
+    /// Defaults to [`on_step_exit`](Self::on_step_exit); override this one
+    /// to get the duration.
+    fn on_step_exit_timed(&self, step_action: &StepAction, duration: Duration, actor_id: &ActorId) {
+        let _d = duration;
+        self.on_step_exit(step_action, actor_id);
+    }
+
+    /// Called when an actor's `step()` returns an error, with the time it took.
+    ///
+    /// [`on_error`](Self::on_error) is called for the same error.
+    fn on_step_error(&self, err: &str, duration: Duration, actor_id: &ActorId) {
+        let _e = err;
+        let _d = duration;
+        let _a = actor_id;
+    }
+
+    /// Called when an actor's `step()` is cancelled before returning, with
+    /// the time it ran.
+    ///
+    /// A step is cancelled when an event arrives first (see
+    /// [`Actor::step`](crate::Actor::step)) or the actor stops.
+    fn on_step_cancel(&self, duration: Duration, actor_id: &ActorId) {
+        let _d = duration;
+        let _a = actor_id;
+    }
+
     /// Called when a subscriber's channel is full and an overflow policy is triggered.
     ///
     /// Fires once per affected subscriber, before the policy action (drop, block,
@@ -102,6 +150,47 @@ pub trait Monitor<E: Event, T: Topic<E> = DefaultTopic>: Send {
         let _p = policy;
     }
 
+    /// Called when an [`Interceptor`](crate::Interceptor) drops an event before routing.
+    ///
+    /// `interceptor` is the name the interceptor was registered under.
+    fn on_event_dropped(&self, envelope: &Envelope<E>, interceptor: &str, reason: &str) {
+        let _e = envelope;
+        let _i = interceptor;
+        let _r = reason;
+    }
+
+    /// Called when the broker drops an event sent to a `topic` its sender
+    /// didn't declare, under
+    /// [`UndeclaredTopicPolicy::Error`](crate::UndeclaredTopicPolicy::Error).
+    fn on_event_rejected(&self, envelope: &Envelope<E>, topic: &T) {
+        let _e = envelope;
+        let _t = topic;
+    }
+
+    /// Called with the sampled queue depths of an actor's channels.
+    ///
+    /// Samples are only taken when enabled with
+    /// [`Config::with_queue_depth_sampling`](crate::Config::with_queue_depth_sampling),
+    /// once per interval for every actor (and the supervisor). A mailbox
+    /// filling up is the early warning before overflow policies kick in.
+    fn on_queue_depth(&self, depth: &QueueDepth, actor_id: &ActorId) {
+        let _d = depth;
+        let _a = actor_id;
+    }
+
+    /// Called when monitoring events were lost because the monitoring
+    /// channel was full, with the number of events lost.
+    ///
+    /// Fires once there is room again, in the place of the missing events,
+    /// so anything reconstructed from callbacks (counts, event chains) is
+    /// incomplete around this point. Enlarge the channel with
+    /// [`Config::with_monitoring_channel_capacity`](crate::Config::with_monitoring_channel_capacity),
+    /// or make producers wait with
+    /// [`Config::with_blocking_monitoring`](crate::Config::with_blocking_monitoring).
+    fn on_lagged(&self, count: u64) {
+        let _c = count;
+    }
+
     /// Called when an actor stops.
     fn on_actor_stop(&self, actor_id: &ActorId) {
         let _a = actor_id;
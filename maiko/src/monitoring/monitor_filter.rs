@@ -0,0 +1,354 @@
+use std::{borrow::Cow, collections::HashSet, fmt};
+
+use crate::{
+    ActorId, Envelope, Event, Label, Topic,
+    monitoring::{MonitoringEvent, Stage},
+};
+
+type EventLabel<E> = fn(&E) -> Cow<'static, str>;
+
+/// Selects which callbacks a monitor receives.
+///
+/// Pass it to [`MonitorRegistry::add_filtered`](crate::monitoring::MonitorRegistry::add_filtered)
+/// or change it later with [`MonitorHandle::set_filter`](crate::monitoring::MonitorHandle::set_filter).
+/// The dispatcher evaluates the filter before calling the monitor, so a
+/// monitor watching one topic does not pay for the rest of the traffic.
+///
+/// Each criterion narrows the selection; a callback must pass all of them.
+/// A criterion that was never set lets everything through:
+///
+/// - **actors**: the actor a callback concerns. Event callbacks match on
+///   either the sender or the receiving actor.
+/// - **topics** and **labels**: the event's topic and [`Label`]. Only event
+///   callbacks carry an event, so setting either of them excludes actor
+///   callbacks.
+/// - **stages**: the callback's [`Stage`]. Step and queue depth callbacks
+///   have no stage and are excluded once stages are set.
+///
+/// [`Monitor::on_lagged`](crate::monitoring::Monitor::on_lagged) is always
+/// delivered, since losses affect every monitor.
+///
+/// # Example
+///
+/// ```ignore
+/// let filter = MonitorFilter::new()
+///     .with_topics([MarketTopic::Quotes])
+///     .with_stages([Stage::Handled]);
+/// let handle = sup.monitors().add_filtered(recorder, filter).await;
+/// ```
+pub struct MonitorFilter<E, T> {
+    actors: Option<HashSet<String>>,
+    topics: Option<HashSet<T>>,
+    labels: Option<(HashSet<String>, EventLabel<E>)>,
+    stages: Option<HashSet<Stage>>,
+}
+
+impl<E: Event, T: Topic<E>> MonitorFilter<E, T> {
+    /// Create a filter that lets every callback through.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only pass callbacks concerning the actors with these names.
+    pub fn with_actors<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        self.actors = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only pass event callbacks for events on these topics.
+    pub fn with_topics(mut self, topics: impl IntoIterator<Item = T>) -> Self {
+        self.topics = Some(topics.into_iter().collect());
+        self
+    }
+
+    /// Only pass event callbacks for events with these labels.
+    pub fn with_labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self
+    where
+        E: Label,
+    {
+        let labels = labels.into_iter().map(Into::into).collect();
+        self.labels = Some((labels, E::label));
+        self
+    }
+
+    /// Only pass callbacks for these lifecycle stages.
+    pub fn with_stages(mut self, stages: impl IntoIterator<Item = Stage>) -> Self {
+        self.stages = Some(stages.into_iter().collect());
+        self
+    }
+
+    /// Whether a monitor with this filter should receive the callback for
+    /// `event`.
+    pub(crate) fn accepts(&self, event: &MonitoringEvent<E, T>) -> bool {
+        use MonitoringEvent::*;
+        match event {
+            EventDispatched(envelope, topic, actor_id)
+            | EventReplayed(envelope, topic, actor_id) => {
+                self.accepts_event(Stage::Dispatched, envelope, Some(topic), Some(actor_id))
+            }
+            EventDelivered(envelope, topic, actor_id) => {
+                self.accepts_event(Stage::Delivered, envelope, Some(topic), Some(actor_id))
+            }
+            EventHandled(envelope, topic, actor_id) => {
+                self.accepts_event(Stage::Handled, envelope, Some(topic), Some(actor_id))
+            }
+            Overflow(envelope, topic, actor_id, _) => {
+                self.accepts_event(Stage::Overflow, envelope, Some(topic), Some(actor_id))
+            }
+            EventDropped(envelope, _, _) => {
+                self.accepts_event(Stage::Dropped, envelope, None, None)
+            }
+            EventRejected(envelope, topic) => {
+                self.accepts_event(Stage::Rejected, envelope, Some(topic), None)
+            }
+            Error(_, actor_id) => self.accepts_actor(Some(Stage::Error), actor_id),
+            ActorRegistered(actor_id) => self.accepts_actor(Some(Stage::ActorRegistered), actor_id),
+            ActorStopped(actor_id) => self.accepts_actor(Some(Stage::ActorStopped), actor_id),
+            StepEntered(actor_id)
+            | StepExited(_, _, actor_id)
+            | StepFailed(_, _, actor_id)
+            | StepCancelled(_, actor_id)
+            | QueueDepth(actor_id, _) => self.accepts_actor(None, actor_id),
+            Lagged(_) => true,
+        }
+    }
+
+    fn accepts_stage(&self, stage: Option<Stage>) -> bool {
+        self.stages
+            .as_ref()
+            .is_none_or(|stages| stage.is_some_and(|stage| stages.contains(&stage)))
+    }
+
+    fn accepts_actor(&self, stage: Option<Stage>, actor_id: &ActorId) -> bool {
+        self.accepts_stage(stage)
+            && self.topics.is_none()
+            && self.labels.is_none()
+            && self
+                .actors
+                .as_ref()
+                .is_none_or(|names| names.contains(actor_id.name()))
+    }
+
+    fn accepts_event(
+        &self,
+        stage: Stage,
+        envelope: &Envelope<E>,
+        topic: Option<&T>,
+        receiver: Option<&ActorId>,
+    ) -> bool {
+        if !self.accepts_stage(Some(stage)) {
+            return false;
+        }
+        let actor_matches = self.actors.as_ref().is_none_or(|names| {
+            names.contains(envelope.meta().actor_name())
+                || receiver.is_some_and(|id| names.contains(id.name()))
+        });
+        if !actor_matches {
+            return false;
+        }
+        let topic_matches = self.topics.as_ref().is_none_or(|topics| match topic {
+            Some(topic) => topics.contains(topic),
+            None => topics.contains(&T::from_event(envelope.event())),
+        });
+        topic_matches
+            && self
+                .labels
+                .as_ref()
+                .is_none_or(|(labels, label)| labels.contains(label(envelope.event()).as_ref()))
+    }
+}
+
+impl<E, T> Default for MonitorFilter<E, T> {
+    fn default() -> Self {
+        Self {
+            actors: None,
+            topics: None,
+            labels: None,
+            stages: None,
+        }
+    }
+}
+
+impl<E, T: Clone> Clone for MonitorFilter<E, T> {
+    fn clone(&self) -> Self {
+        Self {
+            actors: self.actors.clone(),
+            topics: self.topics.clone(),
+            labels: self.labels.clone(),
+            stages: self.stages.clone(),
+        }
+    }
+}
+
+impl<E, T: fmt::Debug> fmt::Debug for MonitorFilter<E, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MonitorFilter")
+            .field("actors", &self.actors)
+            .field("topics", &self.topics)
+            .field("labels", &self.labels.as_ref().map(|(labels, _)| labels))
+            .field("stages", &self.stages)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::OverflowPolicy;
+
+    #[derive(Clone, Debug)]
+    enum Trade {
+        Order,
+        Fill,
+    }
+    impl Event for Trade {}
+    impl Label for Trade {
+        fn label(&self) -> Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    enum TradeTopic {
+        Orders,
+        Fills,
+    }
+    impl Topic<Trade> for TradeTopic {
+        fn from_event(event: &Trade) -> Self {
+            match event {
+                Trade::Order => TradeTopic::Orders,
+                Trade::Fill => TradeTopic::Fills,
+            }
+        }
+    }
+
+    type Filter = MonitorFilter<Trade, TradeTopic>;
+
+    fn make_id(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    fn handled(event: Trade, sender: &str, receiver: &str) -> MonitoringEvent<Trade, TradeTopic> {
+        let topic = TradeTopic::from_event(&event);
+        MonitoringEvent::EventHandled(
+            Arc::new(Envelope::new(event, make_id(sender))),
+            Arc::new(topic),
+            make_id(receiver),
+        )
+    }
+
+    #[test]
+    fn empty_filter_accepts_everything() {
+        let filter = Filter::new();
+        assert!(filter.accepts(&handled(Trade::Order, "trader", "exchange")));
+        assert!(filter.accepts(&MonitoringEvent::ActorStopped(make_id("trader"))));
+        assert!(filter.accepts(&MonitoringEvent::StepEntered(make_id("trader"))));
+    }
+
+    #[test]
+    fn matches_sender_or_receiver() {
+        let filter = Filter::new().with_actors(["exchange"]);
+        assert!(filter.accepts(&handled(Trade::Order, "trader", "exchange")));
+        assert!(filter.accepts(&handled(Trade::Fill, "exchange", "trader")));
+        assert!(!filter.accepts(&handled(Trade::Order, "trader", "audit")));
+        assert!(filter.accepts(&MonitoringEvent::ActorRegistered(make_id("exchange"))));
+        assert!(!filter.accepts(&MonitoringEvent::ActorRegistered(make_id("trader"))));
+    }
+
+    #[test]
+    fn topics_and_labels_select_events() {
+        let by_topic = Filter::new().with_topics([TradeTopic::Fills]);
+        let by_label = Filter::new().with_labels(["Fill"]);
+        for filter in [by_topic, by_label] {
+            assert!(filter.accepts(&handled(Trade::Fill, "exchange", "trader")));
+            assert!(!filter.accepts(&handled(Trade::Order, "trader", "exchange")));
+            assert!(!filter.accepts(&MonitoringEvent::ActorStopped(make_id("trader"))));
+        }
+    }
+
+    #[test]
+    fn dropped_events_match_on_their_topic() {
+        let filter = Filter::new().with_topics([TradeTopic::Orders]);
+        let dropped = MonitoringEvent::EventDropped(
+            Arc::new(Envelope::new(Trade::Order, make_id("trader"))),
+            Arc::from("auth"),
+            Arc::from("denied"),
+        );
+        assert!(filter.accepts(&dropped));
+    }
+
+    #[test]
+    fn stages_exclude_steps_but_not_lagged() {
+        let filter = Filter::new().with_stages([Stage::Overflow]);
+        let overflow = MonitoringEvent::Overflow(
+            Arc::new(Envelope::new(Trade::Order, make_id("trader"))),
+            Arc::new(TradeTopic::Orders),
+            make_id("exchange"),
+            OverflowPolicy::Drop,
+        );
+        assert!(filter.accepts(&overflow));
+        assert!(!filter.accepts(&handled(Trade::Order, "trader", "exchange")));
+        assert!(!filter.accepts(&MonitoringEvent::StepEntered(make_id("trader"))));
+        assert!(filter.accepts(&MonitoringEvent::Lagged(3)));
+    }
+
+    #[tokio::test]
+    async fn filters_callbacks_and_changes_at_runtime() {
+        use std::{sync::Mutex, time::Duration};
+
+        use crate::{Actor, Config, Supervisor, monitoring::Monitor};
+
+        #[derive(Clone, Default)]
+        struct Handled(Arc<Mutex<Vec<String>>>);
+        impl Monitor<Trade, TradeTopic> for Handled {
+            fn on_event_handled(&self, envelope: &Envelope<Trade>, _: &TradeTopic, _: &ActorId) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(envelope.event().label().into_owned());
+            }
+        }
+
+        struct Sink;
+        impl Actor for Sink {
+            type Event = Trade;
+
+            async fn handle_event(&mut self, _envelope: &Envelope<Trade>) -> crate::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut sup = Supervisor::<Trade, TradeTopic>::new(Config::default());
+        let monitor = Handled::default();
+        let filter = Filter::new().with_topics([TradeTopic::Fills]);
+        let handle = sup.monitors().add_filtered(monitor.clone(), filter).await;
+        sup.add_actor("sink", |_| Sink, &[TradeTopic::Orders, TradeTopic::Fills])
+            .unwrap();
+        sup.start().await.unwrap();
+
+        let settle = Duration::from_millis(20);
+        sup.send(Trade::Order).await.unwrap();
+        sup.send(Trade::Fill).await.unwrap();
+        tokio::time::sleep(settle).await;
+        handle.flush(settle).await;
+        assert_eq!(*monitor.0.lock().unwrap(), ["Fill"]);
+
+        handle
+            .set_filter(Filter::new().with_labels(["Order"]))
+            .await;
+        sup.send(Trade::Order).await.unwrap();
+        sup.send(Trade::Fill).await.unwrap();
+        tokio::time::sleep(settle).await;
+        handle.flush(settle).await;
+        assert_eq!(*monitor.0.lock().unwrap(), ["Fill", "Order"]);
+
+        handle.clear_filter().await;
+        sup.send(Trade::Fill).await.unwrap();
+        tokio::time::sleep(settle).await;
+        handle.flush(settle).await;
+        assert_eq!(*monitor.0.lock().unwrap(), ["Fill", "Order", "Fill"]);
+        sup.stop().await.unwrap();
+    }
+}
@@ -4,12 +4,13 @@ use tokio::sync::{mpsc::Sender, oneshot};
 
 use crate::{
     Event, Topic,
-    monitoring::{MonitorCommand, MonitorId},
+    monitoring::{MonitorCommand, MonitorFilter, MonitorId},
 };
 
 /// Handle for controlling a registered monitor.
 ///
-/// Returned by [`MonitorRegistry::add`](crate::monitoring::MonitorRegistry::add).
+/// Returned by [`MonitorRegistry::add`](crate::monitoring::MonitorRegistry::add)
+/// and its `add_*` variants.
 ///
 /// # Example
 ///
@@ -64,10 +65,29 @@ impl<E: Event, T: Topic<E>> MonitorHandle<E, T> {
         let _ = self.sender.send(MonitorCommand::ResumeOne(self.id)).await;
     }
 
+    /// Replace this monitor's filter.
+    ///
+    /// Applies to monitoring events the dispatcher hasn't processed yet.
+    pub async fn set_filter(&self, filter: MonitorFilter<E, T>) {
+        let _ = self
+            .sender
+            .send(MonitorCommand::SetFilter(self.id, Some(filter)))
+            .await;
+    }
+
+    /// Remove this monitor's filter so it receives every callback again.
+    pub async fn clear_filter(&self) {
+        let _ = self
+            .sender
+            .send(MonitorCommand::SetFilter(self.id, None))
+            .await;
+    }
+
     /// Flush waits for the monitoring dispatcher queue to be empty and stay
     /// empty for the specified settle window before returning.
     ///
-    /// This ensures all events queued before the flush call have been processed.
+    /// This ensures all events queued before the flush call have been processed,
+    /// including the queued callbacks of [`AsyncMonitor`](crate::monitoring::AsyncMonitor)s.
     pub async fn flush(&self, settle_window: Duration) {
         let (tx, rx) = oneshot::channel();
         let _ = self
@@ -1,13 +1,27 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use crate::{ActorId, Envelope, OverflowPolicy};
+use crate::{ActorId, Envelope, OverflowPolicy, StepAction, monitoring::QueueDepth};
 
+#[derive(Clone)]
 pub(crate) enum MonitoringEvent<E, T> {
     EventDispatched(Arc<Envelope<E>>, Arc<T>, ActorId),
+    /// A retained event dispatched again to an actor added at runtime.
+    EventReplayed(Arc<Envelope<E>>, Arc<T>, ActorId),
     EventDelivered(Arc<Envelope<E>>, Arc<T>, ActorId),
     EventHandled(Arc<Envelope<E>>, Arc<T>, ActorId),
     Overflow(Arc<Envelope<E>>, Arc<T>, ActorId, OverflowPolicy),
+    /// Envelope, interceptor name, reason.
+    EventDropped(Arc<Envelope<E>>, Arc<str>, Arc<str>),
+    /// Envelope and the topic its sender didn't declare.
+    EventRejected(Arc<Envelope<E>>, Arc<T>),
     ActorRegistered(ActorId),
     ActorStopped(ActorId),
     Error(Arc<str>, ActorId),
+    StepEntered(ActorId),
+    StepExited(StepAction, Duration, ActorId),
+    StepFailed(Arc<str>, Duration, ActorId),
+    StepCancelled(Duration, ActorId),
+    QueueDepth(ActorId, QueueDepth),
+    /// Number of monitoring events lost to a full monitoring channel.
+    Lagged(u64),
 }
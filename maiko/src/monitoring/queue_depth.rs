@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// Queue depths of an actor's channels, sampled periodically by the broker.
+///
+/// Enabled with [`Config::with_queue_depth_sampling`](crate::Config::with_queue_depth_sampling)
+/// and reported through [`Monitor::on_queue_depth`](super::Monitor::on_queue_depth).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueueDepth {
+    sender: Option<ChannelDepth>,
+    mailbox: Option<ChannelDepth>,
+}
+
+/// Number of queued events in a bounded channel, and its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelDepth {
+    len: usize,
+    capacity: usize,
+}
+
+impl QueueDepth {
+    pub(crate) fn new(sender: Option<ChannelDepth>, mailbox: Option<ChannelDepth>) -> Self {
+        Self { sender, mailbox }
+    }
+
+    /// The actor's channel to the broker (stage 1), holding events the actor
+    /// sent that the broker hasn't routed yet.
+    ///
+    /// `None` once the channel is closed.
+    pub fn sender(&self) -> Option<ChannelDepth> {
+        self.sender
+    }
+
+    /// The actor's mailbox (stage 2), holding events waiting to be handled.
+    ///
+    /// `None` for the supervisor, which has no mailbox, and once the
+    /// mailbox is closed.
+    pub fn mailbox(&self) -> Option<ChannelDepth> {
+        self.mailbox
+    }
+}
+
+impl ChannelDepth {
+    pub(crate) fn new(len: usize, capacity: usize) -> Self {
+        Self { len, capacity }
+    }
+
+    /// Number of queued events.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len >= self.capacity
+    }
+
+    /// Fraction of the capacity in use, from 0.0 to 1.0.
+    pub fn usage(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        self.len as f64 / self.capacity as f64
+    }
+}
+
+/// `len/capacity`, e.g. `96/128`.
+impl fmt::Display for ChannelDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.len, self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_depth_usage() {
+        let depth = ChannelDepth::new(96, 128);
+        assert_eq!(depth.usage(), 0.75);
+        assert!(!depth.is_full());
+        assert_eq!(depth.to_string(), "96/128");
+        assert!(ChannelDepth::new(4, 4).is_full());
+    }
+}
@@ -6,7 +6,8 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     Config, Event, Topic,
     monitoring::{
-        Monitor, MonitorCommand, MonitorDispatcher, MonitorHandle, MonitorId, MonitoringSink,
+        AsyncMonitor, AsyncQueue, LostEvents, Monitor, MonitorCommand, MonitorDispatcher,
+        MonitorFilter, MonitorHandle, MonitorId, MonitorKind, MonitoringSink,
     },
 };
 
@@ -34,6 +35,9 @@ pub struct MonitorRegistry<E: Event, T: Topic<E>> {
     dispatcher_handle: Option<tokio::task::JoinHandle<()>>,
     pub(crate) sender: tokio::sync::mpsc::Sender<MonitorCommand<E, T>>,
     pub(crate) is_active: Arc<AtomicBool>,
+    monitor_steps: bool,
+    blocking: bool,
+    lost: Arc<LostEvents>,
 }
 
 impl<E: Event, T: Topic<E>> MonitorRegistry<E, T> {
@@ -48,6 +52,9 @@ impl<E: Event, T: Topic<E>> MonitorRegistry<E, T> {
             dispatcher: Some(dispatcher),
             dispatcher_handle: None,
             is_active,
+            monitor_steps: config.step_monitoring(),
+            blocking: config.blocking_monitoring(),
+            lost: Arc::new(LostEvents::default()),
         }
     }
 
@@ -74,7 +81,20 @@ impl<E: Event, T: Topic<E>> MonitorRegistry<E, T> {
     }
 
     pub(crate) fn sink(&self) -> MonitoringSink<E, T> {
-        MonitoringSink::new(self.sender.clone(), self.is_active.clone())
+        MonitoringSink::new(
+            self.sender.clone(),
+            self.is_active.clone(),
+            self.monitor_steps,
+            self.blocking,
+            self.lost.clone(),
+        )
+    }
+
+    /// Total number of monitoring events lost because the monitoring channel
+    /// was full. Monitors learn about losses through
+    /// [`Monitor::on_lagged`].
+    pub fn lost_events(&self) -> u64 {
+        self.lost.total()
     }
 
     /// Register a new monitor and return a handle for controlling it.
@@ -85,10 +105,68 @@ impl<E: Event, T: Topic<E>> MonitorRegistry<E, T> {
     ///
     /// Panics if the dispatcher channel is closed (supervisor already stopped).
     pub async fn add<M: Monitor<E, T> + 'static>(&self, monitor: M) -> MonitorHandle<E, T> {
+        self.register(MonitorKind::Sync(Box::new(monitor)), None)
+            .await
+    }
+
+    /// Register a monitor that only receives the callbacks selected by
+    /// `filter`.
+    ///
+    /// The filter is evaluated by the dispatcher before the monitor is
+    /// called, and can be replaced later with [`MonitorHandle::set_filter`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dispatcher channel is closed (supervisor already stopped).
+    pub async fn add_filtered<M: Monitor<E, T> + 'static>(
+        &self,
+        monitor: M,
+        filter: MonitorFilter<E, T>,
+    ) -> MonitorHandle<E, T> {
+        self.register(MonitorKind::Sync(Box::new(monitor)), Some(filter))
+            .await
+    }
+
+    /// Register an [`AsyncMonitor`], spawning the task it runs on.
+    ///
+    /// The returned handle controls it like any other monitor; its
+    /// [`flush`](MonitorHandle::flush) also waits for the monitor's own
+    /// queue to drain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dispatcher channel is closed (supervisor already stopped)
+    /// or the monitor's queue capacity is zero.
+    pub async fn add_async<M: AsyncMonitor<E, T>>(&self, monitor: M) -> MonitorHandle<E, T> {
+        self.register(MonitorKind::Async(AsyncQueue::spawn(monitor)), None)
+            .await
+    }
+
+    /// Register an [`AsyncMonitor`] that only receives the callbacks selected
+    /// by `filter`. See [`add_filtered`](Self::add_filtered).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dispatcher channel is closed (supervisor already stopped)
+    /// or the monitor's queue capacity is zero.
+    pub async fn add_async_filtered<M: AsyncMonitor<E, T>>(
+        &self,
+        monitor: M,
+        filter: MonitorFilter<E, T>,
+    ) -> MonitorHandle<E, T> {
+        self.register(MonitorKind::Async(AsyncQueue::spawn(monitor)), Some(filter))
+            .await
+    }
+
+    async fn register(
+        &self,
+        monitor: MonitorKind<E, T>,
+        filter: Option<MonitorFilter<E, T>>,
+    ) -> MonitorHandle<E, T> {
         let (tx, rx) = oneshot::channel();
         let _ = self
             .sender
-            .send(MonitorCommand::AddMonitor(Box::new(monitor), tx))
+            .send(MonitorCommand::AddMonitor(monitor, filter, tx))
             .await;
         let id = rx
             .await
@@ -1,23 +1,58 @@
 use std::sync::{
     Arc,
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
 };
 
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::{Sender, error::TrySendError};
 
 use crate::{
     Event, Topic,
     monitoring::{MonitorCommand, MonitoringEvent},
 };
 
+/// Counters of monitoring events lost to a full monitoring channel,
+/// shared by all sinks of a registry.
+#[derive(Debug, Default)]
+pub(crate) struct LostEvents {
+    /// Lost events not yet reported to monitors with `on_lagged`.
+    pending: AtomicU64,
+    total: AtomicU64,
+}
+
+impl LostEvents {
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    fn record(&self) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
 pub(crate) struct MonitoringSink<E: Event, T: Topic<E>> {
     is_active: Arc<AtomicBool>,
     sender: Sender<MonitorCommand<E, T>>,
+    monitor_steps: bool,
+    blocking: bool,
+    lost: Arc<LostEvents>,
 }
 
 impl<E: Event, T: Topic<E>> MonitoringSink<E, T> {
-    pub fn new(sender: Sender<MonitorCommand<E, T>>, is_active: Arc<AtomicBool>) -> Self {
-        Self { sender, is_active }
+    pub fn new(
+        sender: Sender<MonitorCommand<E, T>>,
+        is_active: Arc<AtomicBool>,
+        monitor_steps: bool,
+        blocking: bool,
+        lost: Arc<LostEvents>,
+    ) -> Self {
+        Self {
+            sender,
+            is_active,
+            monitor_steps,
+            blocking,
+            lost,
+        }
     }
 
     #[inline]
@@ -25,9 +60,140 @@ impl<E: Event, T: Topic<E>> MonitoringSink<E, T> {
         self.is_active.load(Ordering::Relaxed)
     }
 
+    /// Whether step entry and exit should be reported.
+    #[inline]
+    pub fn observes_steps(&self) -> bool {
+        self.monitor_steps && self.is_active()
+    }
+
+    /// Send an event to the monitors. In blocking mode waits for space in
+    /// the monitoring channel, otherwise behaves as [`try_send`](Self::try_send).
     #[inline]
-    pub fn send(&self, event: MonitoringEvent<E, T>) {
-        let msg = MonitorCommand::DispatchEvent(event);
-        let _ = self.sender.try_send(msg);
+    pub async fn send(&self, event: MonitoringEvent<E, T>) {
+        if !self.blocking {
+            return self.try_send(event);
+        }
+        self.report_lagged();
+        let _ = self.sender.send(MonitorCommand::DispatchEvent(event)).await;
+    }
+
+    /// Send an event without waiting, counting it as lost when the
+    /// monitoring channel is full.
+    #[inline]
+    pub fn try_send(&self, event: MonitoringEvent<E, T>) {
+        self.report_lagged();
+        if let Err(TrySendError::Full(_)) =
+            self.sender.try_send(MonitorCommand::DispatchEvent(event))
+        {
+            self.lost.record();
+        }
+    }
+
+    /// Tell monitors about lost events, if any, as soon as there is room,
+    /// so the notice lands where the gap is in the event stream.
+    #[inline]
+    fn report_lagged(&self) {
+        if self.lost.pending.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let count = self.lost.pending.swap(0, Ordering::AcqRel);
+        if count == 0 {
+            return;
+        }
+        let msg = MonitorCommand::DispatchEvent(MonitoringEvent::Lagged(count));
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(msg) {
+            self.lost.pending.fetch_add(count, Ordering::AcqRel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::{Receiver, channel};
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic};
+
+    #[derive(Clone, Debug)]
+    struct TestEvent;
+    impl Event for TestEvent {}
+
+    type Command = MonitorCommand<TestEvent, DefaultTopic>;
+
+    fn sink(capacity: usize) -> (MonitoringSink<TestEvent, DefaultTopic>, Receiver<Command>) {
+        let (tx, rx) = channel(capacity);
+        let active = Arc::new(AtomicBool::new(true));
+        let sink = MonitoringSink::new(tx, active, false, false, Arc::default());
+        (sink, rx)
+    }
+
+    fn stopped() -> MonitoringEvent<TestEvent, DefaultTopic> {
+        MonitoringEvent::ActorStopped(ActorId::new(Arc::from("actor")))
+    }
+
+    #[tokio::test]
+    async fn lost_events_are_reported_once_there_is_room() {
+        let (sink, mut rx) = sink(2);
+        for _ in 0..5 {
+            sink.send(stopped()).await;
+        }
+        assert_eq!(sink.lost.total(), 3);
+
+        // Room for the lag notice only: the event itself is lost again.
+        rx.recv().await.unwrap();
+        sink.try_send(stopped());
+        assert_eq!(sink.lost.total(), 4);
+
+        rx.recv().await.unwrap();
+        assert!(matches!(
+            rx.recv().await,
+            Some(MonitorCommand::DispatchEvent(MonitoringEvent::Lagged(3)))
+        ));
+        sink.try_send(stopped());
+        assert!(matches!(
+            rx.recv().await,
+            Some(MonitorCommand::DispatchEvent(MonitoringEvent::Lagged(1)))
+        ));
+        assert!(matches!(
+            rx.recv().await,
+            Some(MonitorCommand::DispatchEvent(
+                MonitoringEvent::ActorStopped(_)
+            ))
+        ));
+        assert_eq!(sink.lost.total(), 4);
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[tokio::test]
+    async fn blocking_monitoring_loses_nothing() {
+        use std::time::Duration;
+
+        use crate::{Actor, Config, Subscribe, Supervisor, testing::Harness};
+
+        struct Sink;
+        impl Actor for Sink {
+            type Event = TestEvent;
+        }
+
+        let config = Config::default()
+            .with_monitoring_channel_capacity(1)
+            .with_blocking_monitoring(true);
+        let mut sup = Supervisor::<TestEvent>::new(config);
+        let mut test = Harness::new(&mut sup).await;
+        sup.add_actor("sink", |_| Sink, Subscribe::all()).unwrap();
+        sup.start().await.unwrap();
+        test.record().await;
+        for _ in 0..100 {
+            sup.send(TestEvent).await.unwrap();
+        }
+        test.settle_on(|events| events.count() >= 100)
+            .within(Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(test.event_count(), 100);
+        assert_eq!(test.lagged(), 0);
+        assert_eq!(sup.monitors().lost_events(), 0);
+        sup.stop().await.unwrap();
     }
 }
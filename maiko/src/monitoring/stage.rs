@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Lifecycle stage reported to monitors, one per event or actor
+/// [`Monitor`](super::Monitor) callback (steps and queue depth samples are
+/// not included).
+///
+/// Used to label what happened when monitoring output is stored or
+/// displayed, e.g. in [`Recorder`](crate::monitors::Recorder) records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Stage {
+    /// The broker routed an event to a subscriber.
+    Dispatched,
+    /// An actor took an event from its mailbox.
+    Delivered,
+    /// An actor finished handling an event.
+    Handled,
+    /// A subscriber's mailbox was full and an overflow policy was applied.
+    Overflow,
+    /// An interceptor dropped an event before routing.
+    Dropped,
+    /// The broker dropped an event sent to a topic its sender didn't declare.
+    Rejected,
+    /// An actor reported an error.
+    Error,
+    /// An actor was registered.
+    ActorRegistered,
+    /// An actor stopped.
+    ActorStopped,
+}
+
+impl Stage {
+    /// All stages, in lifecycle order.
+    pub const ALL: [Stage; 9] = [
+        Stage::Dispatched,
+        Stage::Delivered,
+        Stage::Handled,
+        Stage::Overflow,
+        Stage::Dropped,
+        Stage::Rejected,
+        Stage::Error,
+        Stage::ActorRegistered,
+        Stage::ActorStopped,
+    ];
+
+    /// Whether the stage refers to a single event (as opposed to an actor).
+    pub fn is_event_stage(&self) -> bool {
+        matches!(
+            self,
+            Stage::Dispatched
+                | Stage::Delivered
+                | Stage::Handled
+                | Stage::Overflow
+                | Stage::Dropped
+                | Stage::Rejected
+        )
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Dispatched => "dispatched",
+            Stage::Delivered => "delivered",
+            Stage::Handled => "handled",
+            Stage::Overflow => "overflow",
+            Stage::Dropped => "dropped",
+            Stage::Rejected => "rejected",
+            Stage::Error => "error",
+            Stage::ActorRegistered => "actor_registered",
+            Stage::ActorStopped => "actor_stopped",
+        };
+        f.write_str(name)
+    }
+}
@@ -2,9 +2,12 @@ use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
-use crate::{ActorId, Envelope, Event, OverflowPolicy, Topic, monitoring::Monitor};
+use crate::{
+    ActorId, Envelope, Event, OverflowPolicy, Topic,
+    monitoring::{Monitor, QueueDepth},
+};
 
-/// Monitor that tracks actor lifecycle and overflow counts.
+/// Monitor that tracks actor lifecycle, overflow counts and queue depths.
 ///
 /// Register with the supervisor to passively observe actor registration,
 /// shutdown, and overflow events. Query at any time from any thread.
@@ -27,6 +30,7 @@ struct ActorMonitorInner {
     active: HashSet<ActorId>,
     stopped: HashSet<ActorId>,
     overflow_counts: HashMap<ActorId, usize>,
+    queue_depths: HashMap<ActorId, QueueDepth>,
 }
 
 impl ActorMonitor {
@@ -37,6 +41,7 @@ impl ActorMonitor {
                 active: HashSet::new(),
                 stopped: HashSet::new(),
                 overflow_counts: HashMap::new(),
+                queue_depths: HashMap::new(),
             })),
         }
     }
@@ -64,6 +69,15 @@ impl ActorMonitor {
         let lock = self.inner.lock().unwrap();
         lock.overflow_counts.get(actor).copied().unwrap_or(0)
     }
+
+    /// Returns the latest queue depth sampled for this actor.
+    ///
+    /// Requires [`Config::with_queue_depth_sampling`](crate::Config::with_queue_depth_sampling);
+    /// cleared when the actor stops.
+    pub fn queue_depth(&self, actor: &ActorId) -> Option<QueueDepth> {
+        let lock = self.inner.lock().unwrap();
+        lock.queue_depths.get(actor).copied()
+    }
 }
 
 impl<E, T> Monitor<E, T> for ActorMonitor
@@ -81,6 +95,14 @@ where
         let mut lock = self.inner.lock().unwrap();
         lock.active.remove(actor_id);
         lock.stopped.insert(actor_id.clone());
+        lock.queue_depths.remove(actor_id);
+    }
+
+    fn on_queue_depth(&self, depth: &QueueDepth, actor_id: &ActorId) {
+        let mut lock = self.inner.lock().unwrap();
+        if !lock.stopped.contains(actor_id) {
+            lock.queue_depths.insert(actor_id.clone(), *depth);
+        }
     }
 
     fn on_overflow(
@@ -208,4 +230,42 @@ mod tests {
 
         assert!(query.is_alive(&a));
     }
+
+    #[tokio::test]
+    async fn samples_queue_depth() {
+        use std::time::Duration;
+
+        use crate::{Actor, Config, Subscribe, Supervisor};
+
+        struct Slow;
+        impl Actor for Slow {
+            type Event = TestEvent;
+
+            async fn handle_event(&mut self, _envelope: &Envelope<TestEvent>) -> crate::Result<()> {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok(())
+            }
+        }
+
+        let config = Config::default()
+            .with_default_actor_channel_capacity(8)
+            .with_queue_depth_sampling(Duration::from_millis(5));
+        let mut sup = Supervisor::<TestEvent>::new(config);
+        let monitor = ActorMonitor::new();
+        sup.monitors().add(monitor.clone()).await;
+        let slow = sup.add_actor("slow", |_| Slow, Subscribe::all()).unwrap();
+        sup.start().await.unwrap();
+        for _ in 0..4 {
+            sup.send(TestEvent).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let depth = monitor.queue_depth(&slow).unwrap();
+        let mailbox = depth.mailbox().unwrap();
+        assert_eq!(mailbox.capacity(), 8);
+        assert_eq!(mailbox.len(), 3, "one event is being handled");
+        assert!(depth.sender().unwrap().is_empty());
+        assert!(monitor.queue_depth(&make_id("supervisor")).is_some());
+        sup.stop().await.unwrap();
+    }
 }
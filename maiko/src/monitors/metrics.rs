@@ -0,0 +1,730 @@
+mod histogram;
+mod server;
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::{self, Write as _},
+    io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::net::ToSocketAddrs;
+
+use crate::{
+    ActorId, Envelope, Event, EventId, Label, OverflowPolicy, Topic,
+    monitoring::{ChannelDepth, Monitor, QueueDepth},
+};
+
+use histogram::{DEFAULT_BUCKETS, Histogram};
+pub use server::MetricsServer;
+
+/// Monitor aggregating event counters and latency histograms, exposed in the
+/// [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+///
+/// Collected metrics (prefixed with `maiko_` by default):
+///
+/// | Metric | Type | Labels |
+/// |---|---|---|
+/// | `events_dispatched_total` | counter | `actor`, `topic` |
+/// | `events_delivered_total` | counter | `actor`, `topic` |
+/// | `events_handled_total` | counter | `actor`, `topic` |
+/// | `overflows_total` | counter | `actor`, `topic`, `policy` |
+/// | `errors_total` | counter | `actor` |
+/// | `events_dropped_total` | counter | `interceptor` |
+/// | `events_rejected_total` | counter | `actor`, `topic` |
+/// | `monitoring_events_lost_total` | counter | |
+/// | `queue_depth` | gauge | `actor`, `queue` |
+/// | `queue_capacity` | gauge | `actor`, `queue` |
+/// | `dispatch_to_handled_seconds` | histogram | `actor`, `topic` |
+/// | `handler_duration_seconds` | histogram | `actor`, `topic` |
+///
+/// `actor` is the receiving actor, except in `events_rejected_total`, which
+/// counts events dropped because their sender didn't declare the topic
+/// (see [`UndeclaredTopicPolicy`](crate::UndeclaredTopicPolicy)). Durations are measured between the
+/// callbacks as the monitor observes them, so they include a little jitter
+/// from the monitoring channel. Events delivered after a
+/// [blocking](OverflowPolicy::Block) overflow are timed from the overflow.
+///
+/// The queue gauges (`queue` being `sender` or `mailbox`) are only present
+/// with [queue depth sampling](crate::Config::with_queue_depth_sampling).
+///
+/// Dispatch and delivery times are kept until the event is handled, for at
+/// most 65 536 events in flight. Beyond that the oldest are forgotten, and
+/// all are forgotten when monitoring events are lost (a lost `Handled`
+/// callback would otherwise keep its entry forever); those events are
+/// counted but not timed.
+///
+/// The monitor is cheap to clone; keep a clone to read the metrics while
+/// the original is registered:
+///
+/// ```ignore
+/// let metrics = Metrics::new();
+/// sup.monitors().add(metrics.clone()).await;
+///
+/// let server = metrics.serve("127.0.0.1:9184").await?;
+/// // or, for node_exporter's textfile collector:
+/// metrics.write_to("/var/lib/node_exporter/maiko.prom")?;
+/// ```
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<Mutex<MetricsInner>>,
+}
+
+struct MetricsInner {
+    prefix: String,
+    buckets: Arc<[f64]>,
+    series: BTreeMap<SeriesKey, Series>,
+    overflows: BTreeMap<(SeriesKey, &'static str), u64>,
+    errors: BTreeMap<String, u64>,
+    dropped: BTreeMap<String, u64>,
+    rejected: BTreeMap<SeriesKey, u64>,
+    lost: u64,
+    queues: BTreeMap<(String, &'static str), ChannelDepth>,
+    in_flight: InFlight,
+}
+
+/// How many events in flight [`Timestamps`] remembers.
+const IN_FLIGHT_CAPACITY: usize = 65_536;
+
+/// When events still being handled were dispatched and delivered.
+#[derive(Default)]
+struct InFlight {
+    dispatched_at: Timestamps,
+    delivered_at: Timestamps,
+}
+
+/// Per event and receiver timestamps, bounded to [`IN_FLIGHT_CAPACITY`] by
+/// evicting the oldest.
+#[derive(Default)]
+struct Timestamps {
+    at: HashMap<(EventId, ActorId), Instant>,
+    order: VecDeque<((EventId, ActorId), Instant)>,
+}
+
+/// Receiving actor and topic label.
+type SeriesKey = (String, String);
+
+/// Metric name, help text and accessor of a per-series counter.
+type CounterMetric = (&'static str, &'static str, fn(&Series) -> u64);
+
+/// Metric name, help text and accessor of a per-queue gauge.
+type QueueMetric = (&'static str, &'static str, fn(&ChannelDepth) -> usize);
+
+/// Metric name, help text and accessor of a per-series histogram.
+type HistogramMetric = (&'static str, &'static str, fn(&Series) -> &Histogram);
+
+struct Series {
+    dispatched: u64,
+    delivered: u64,
+    handled: u64,
+    dispatch_to_handled: Histogram,
+    handler_duration: Histogram,
+}
+
+impl Metrics {
+    /// Create a monitor with the `maiko` prefix and default latency buckets
+    /// (10µs to 5s).
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MetricsInner {
+                prefix: "maiko".to_string(),
+                buckets: DEFAULT_BUCKETS.iter().map(Duration::as_secs_f64).collect(),
+                series: BTreeMap::new(),
+                overflows: BTreeMap::new(),
+                errors: BTreeMap::new(),
+                dropped: BTreeMap::new(),
+                rejected: BTreeMap::new(),
+                lost: 0,
+                queues: BTreeMap::new(),
+                in_flight: InFlight::default(),
+            })),
+        }
+    }
+
+    /// Set the metric name prefix. An empty prefix leaves names bare.
+    pub fn with_prefix(self, prefix: impl Into<String>) -> Self {
+        self.inner.lock().unwrap().prefix = prefix.into();
+        self
+    }
+
+    /// Set the upper bounds of the histogram buckets. Bounds are sorted,
+    /// and the `+Inf` bucket is always added.
+    ///
+    /// Applies to series created afterwards, so set it before registering.
+    pub fn with_buckets(self, buckets: &[Duration]) -> Self {
+        let mut bounds: Vec<f64> = buckets.iter().map(Duration::as_secs_f64).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        self.inner.lock().unwrap().buckets = bounds.into();
+        self
+    }
+
+    /// Number of events handled by an actor, over all topics.
+    pub fn handled_count(&self, actor: &ActorId) -> u64 {
+        let lock = self.inner.lock().unwrap();
+        lock.series
+            .iter()
+            .filter(|((name, _), _)| name == actor.name())
+            .map(|(_, series)| series.handled)
+            .sum()
+    }
+
+    /// Number of errors reported by an actor.
+    pub fn error_count(&self, actor: &ActorId) -> u64 {
+        let lock = self.inner.lock().unwrap();
+        lock.errors.get(actor.name()).copied().unwrap_or(0)
+    }
+
+    /// Render all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        self.inner.lock().unwrap().render()
+    }
+
+    /// Write the rendered metrics to a file.
+    ///
+    /// The file is replaced atomically, through a temporary file in the same
+    /// directory, so scrapers never read a partial file.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, self.render())?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Serve the metrics over HTTP on the given address.
+    ///
+    /// Every `GET /metrics` (or `GET /`) gets the current metrics. Serving
+    /// stops when the returned [`MetricsServer`] is dropped.
+    pub async fn serve(&self, addr: impl ToSocketAddrs) -> io::Result<MetricsServer> {
+        MetricsServer::bind(self.clone(), addr).await
+    }
+
+    fn series(
+        &self,
+        receiver: &ActorId,
+        topic: String,
+        f: impl FnOnce(&mut Series, &mut InFlight, Instant),
+    ) {
+        let now = Instant::now();
+        let mut lock = self.inner.lock().unwrap();
+        let inner = &mut *lock;
+        let series = inner
+            .series
+            .entry((receiver.name().to_string(), topic))
+            .or_insert_with(|| Series::new(inner.buckets.clone()));
+        f(series, &mut inner.in_flight, now);
+    }
+}
+
+impl MetricsInner {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let name = |metric: &str| {
+            if self.prefix.is_empty() {
+                metric.to_string()
+            } else {
+                format!("{}_{metric}", self.prefix)
+            }
+        };
+
+        let counters: [CounterMetric; 3] = [
+            (
+                "events_dispatched_total",
+                "Events routed to a subscriber.",
+                |s| s.dispatched,
+            ),
+            (
+                "events_delivered_total",
+                "Events taken from an actor's mailbox.",
+                |s| s.delivered,
+            ),
+            (
+                "events_handled_total",
+                "Events an actor finished handling.",
+                |s| s.handled,
+            ),
+        ];
+        for (metric, help, value) in counters {
+            let metric = name(metric);
+            header(&mut out, &metric, help, "counter");
+            for ((actor, topic), series) in &self.series {
+                let labels = labels(&[("actor", actor), ("topic", topic)]);
+                let _ = writeln!(out, "{metric}{{{labels}}} {}", value(series));
+            }
+        }
+
+        let metric = name("overflows_total");
+        header(
+            &mut out,
+            &metric,
+            "Events that found a subscriber's mailbox full.",
+            "counter",
+        );
+        for (((actor, topic), policy), count) in &self.overflows {
+            let labels = labels(&[("actor", actor), ("topic", topic), ("policy", policy)]);
+            let _ = writeln!(out, "{metric}{{{labels}}} {count}");
+        }
+
+        let metric = name("errors_total");
+        header(&mut out, &metric, "Errors reported by actors.", "counter");
+        for (actor, count) in &self.errors {
+            let _ = writeln!(out, "{metric}{{{}}} {count}", labels(&[("actor", actor)]));
+        }
+
+        let metric = name("events_dropped_total");
+        header(
+            &mut out,
+            &metric,
+            "Events dropped by interceptors before routing.",
+            "counter",
+        );
+        for (interceptor, count) in &self.dropped {
+            let labels = labels(&[("interceptor", interceptor)]);
+            let _ = writeln!(out, "{metric}{{{labels}}} {count}");
+        }
+
+        let metric = name("events_rejected_total");
+        header(
+            &mut out,
+            &metric,
+            "Events dropped for a topic their sender didn't declare.",
+            "counter",
+        );
+        for ((actor, topic), count) in &self.rejected {
+            let labels = labels(&[("actor", actor), ("topic", topic)]);
+            let _ = writeln!(out, "{metric}{{{labels}}} {count}");
+        }
+
+        let metric = name("monitoring_events_lost_total");
+        header(
+            &mut out,
+            &metric,
+            "Monitoring events lost to a full monitoring channel.",
+            "counter",
+        );
+        let _ = writeln!(out, "{metric} {}", self.lost);
+
+        let gauges: [QueueMetric; 2] = [
+            (
+                "queue_depth",
+                "Events queued in a channel.",
+                ChannelDepth::len,
+            ),
+            (
+                "queue_capacity",
+                "Capacity of a channel.",
+                ChannelDepth::capacity,
+            ),
+        ];
+        for (metric, help, value) in gauges {
+            let metric = name(metric);
+            header(&mut out, &metric, help, "gauge");
+            for ((actor, queue), depth) in &self.queues {
+                let labels = labels(&[("actor", actor), ("queue", queue)]);
+                let _ = writeln!(out, "{metric}{{{labels}}} {}", value(depth));
+            }
+        }
+
+        let histograms: [HistogramMetric; 2] = [
+            (
+                "dispatch_to_handled_seconds",
+                "Time from dispatch to the end of handling.",
+                |s| &s.dispatch_to_handled,
+            ),
+            (
+                "handler_duration_seconds",
+                "Time from delivery to the end of handling.",
+                |s| &s.handler_duration,
+            ),
+        ];
+        for (metric, help, histogram) in histograms {
+            let metric = name(metric);
+            header(&mut out, &metric, help, "histogram");
+            for ((actor, topic), series) in &self.series {
+                let labels = labels(&[("actor", actor), ("topic", topic)]);
+                histogram(series).render(&mut out, &metric, &labels);
+            }
+        }
+        out
+    }
+}
+
+impl InFlight {
+    fn retain_receivers(&mut self, f: impl Fn(&ActorId) -> bool) {
+        self.dispatched_at.retain_receivers(&f);
+        self.delivered_at.retain_receivers(&f);
+    }
+
+    fn clear(&mut self) {
+        self.dispatched_at = Timestamps::default();
+        self.delivered_at = Timestamps::default();
+    }
+}
+
+impl Timestamps {
+    fn insert(&mut self, key: (EventId, ActorId), at: Instant) {
+        self.at.insert(key.clone(), at);
+        self.order.push_back((key, at));
+        while self.order.len() > IN_FLIGHT_CAPACITY {
+            if let Some((old, old_at)) = self.order.pop_front() {
+                // Skip keys handled or re-inserted since.
+                if self.at.get(&old) == Some(&old_at) {
+                    self.at.remove(&old);
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &(EventId, ActorId)) -> Option<Instant> {
+        self.at.remove(key)
+    }
+
+    fn retain_receivers(&mut self, f: impl Fn(&ActorId) -> bool) {
+        self.at.retain(|(_, receiver), _| f(receiver));
+        self.order.retain(|((_, receiver), _)| f(receiver));
+    }
+
+    fn len(&self) -> usize {
+        self.at.len()
+    }
+
+    #[cfg(test)]
+    fn is_empty(&self) -> bool {
+        self.at.is_empty()
+    }
+}
+
+impl Series {
+    fn new(buckets: Arc<[f64]>) -> Self {
+        Self {
+            dispatched: 0,
+            delivered: 0,
+            handled: 0,
+            dispatch_to_handled: Histogram::new(buckets.clone()),
+            handler_duration: Histogram::new(buckets),
+        }
+    }
+}
+
+fn header(out: &mut String, metric: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {metric} {help}");
+    let _ = writeln!(out, "# TYPE {metric} {kind}");
+}
+
+/// Render a label set without braces, escaping values.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let mut out = String::new();
+    for (i, (name, value)) in pairs.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(name);
+        out.push_str("=\"");
+        for c in value.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+    out
+}
+
+fn policy_label(policy: OverflowPolicy) -> &'static str {
+    match policy {
+        OverflowPolicy::Fail => "fail",
+        OverflowPolicy::Drop => "drop",
+        OverflowPolicy::Block => "block",
+    }
+}
+
+impl<E, T> Monitor<E, T> for Metrics
+where
+    E: Event,
+    T: Topic<E> + Label,
+{
+    fn on_event_dispatched(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        let key = (envelope.id(), receiver.clone());
+        self.series(
+            receiver,
+            topic.label().into_owned(),
+            |series, in_flight, now| {
+                series.dispatched += 1;
+                in_flight.dispatched_at.insert(key, now);
+            },
+        );
+    }
+
+    fn on_event_delivered(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        let key = (envelope.id(), receiver.clone());
+        self.series(
+            receiver,
+            topic.label().into_owned(),
+            |series, in_flight, now| {
+                series.delivered += 1;
+                in_flight.delivered_at.insert(key, now);
+            },
+        );
+    }
+
+    fn on_event_handled(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        let key = (envelope.id(), receiver.clone());
+        self.series(
+            receiver,
+            topic.label().into_owned(),
+            |series, in_flight, now| {
+                series.handled += 1;
+                if let Some(at) = in_flight.dispatched_at.remove(&key) {
+                    series.dispatch_to_handled.observe(now - at);
+                }
+                if let Some(at) = in_flight.delivered_at.remove(&key) {
+                    series.handler_duration.observe(now - at);
+                }
+            },
+        );
+    }
+
+    fn on_error(&self, _err: &str, actor_id: &ActorId) {
+        let mut lock = self.inner.lock().unwrap();
+        *lock.errors.entry(actor_id.name().to_string()).or_default() += 1;
+    }
+
+    fn on_overflow(
+        &self,
+        envelope: &Envelope<E>,
+        topic: &T,
+        receiver: &ActorId,
+        policy: OverflowPolicy,
+    ) {
+        let key = (receiver.name().to_string(), topic.label().into_owned());
+        let mut lock = self.inner.lock().unwrap();
+        *lock
+            .overflows
+            .entry((key, policy_label(policy)))
+            .or_default() += 1;
+        if policy == OverflowPolicy::Block {
+            lock.in_flight
+                .dispatched_at
+                .insert((envelope.id(), receiver.clone()), Instant::now());
+        }
+    }
+
+    fn on_event_dropped(&self, _envelope: &Envelope<E>, interceptor: &str, _reason: &str) {
+        let mut lock = self.inner.lock().unwrap();
+        *lock.dropped.entry(interceptor.to_string()).or_default() += 1;
+    }
+
+    fn on_event_rejected(&self, envelope: &Envelope<E>, topic: &T) {
+        let key = (
+            envelope.meta().actor_name().to_string(),
+            topic.label().into_owned(),
+        );
+        let mut lock = self.inner.lock().unwrap();
+        *lock.rejected.entry(key).or_default() += 1;
+    }
+
+    fn on_lagged(&self, count: u64) {
+        let mut lock = self.inner.lock().unwrap();
+        lock.lost += count;
+        lock.in_flight.clear();
+    }
+
+    fn on_queue_depth(&self, depth: &QueueDepth, actor_id: &ActorId) {
+        let mut lock = self.inner.lock().unwrap();
+        for (queue, channel) in [("sender", depth.sender()), ("mailbox", depth.mailbox())] {
+            let key = (actor_id.name().to_string(), queue);
+            match channel {
+                Some(channel) => lock.queues.insert(key, channel),
+                None => lock.queues.remove(&key),
+            };
+        }
+    }
+
+    fn on_actor_stop(&self, actor_id: &ActorId) {
+        let mut lock = self.inner.lock().unwrap();
+        lock.queues.retain(|(actor, _), _| actor != actor_id.name());
+        lock.in_flight
+            .retain_receivers(|receiver| receiver != actor_id);
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lock = self.inner.lock().unwrap();
+        f.debug_struct("Metrics")
+            .field("prefix", &lock.prefix)
+            .field("series", &lock.series.len())
+            .field("in_flight", &lock.in_flight.dispatched_at.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DefaultTopic;
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct TestEvent;
+    impl Event for TestEvent {}
+
+    fn make_id(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    fn handle(metrics: &Metrics, envelope: &Envelope<TestEvent>, receiver: &ActorId) {
+        let m: &dyn Monitor<TestEvent, DefaultTopic> = metrics;
+        m.on_event_dispatched(envelope, &DefaultTopic, receiver);
+        m.on_event_delivered(envelope, &DefaultTopic, receiver);
+        m.on_event_handled(envelope, &DefaultTopic, receiver);
+    }
+
+    #[test]
+    fn counts_event_lifecycle() {
+        let metrics = Metrics::new();
+        let sender = make_id("sender");
+        let receiver = make_id("receiver");
+        handle(
+            &metrics,
+            &Envelope::new(TestEvent, sender.clone()),
+            &receiver,
+        );
+        handle(&metrics, &Envelope::new(TestEvent, sender), &receiver);
+
+        let text = metrics.render();
+        for metric in ["dispatched_total", "delivered_total", "handled_total"] {
+            assert!(
+                text.contains(&format!(
+                    "maiko_events_{metric}{{actor=\"receiver\",topic=\"default\"}} 2\n"
+                )),
+                "{text}"
+            );
+        }
+        assert!(text.contains(
+            "maiko_handler_duration_seconds_bucket{actor=\"receiver\",topic=\"default\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains(
+            "maiko_dispatch_to_handled_seconds_count{actor=\"receiver\",topic=\"default\"} 2\n"
+        ));
+        assert_eq!(metrics.handled_count(&receiver), 2);
+        assert!(
+            metrics
+                .inner
+                .lock()
+                .unwrap()
+                .in_flight
+                .dispatched_at
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn counts_errors_overflows_and_drops() {
+        let metrics = Metrics::new().with_prefix("app");
+        let actor = make_id("slow");
+        let envelope = Envelope::new(TestEvent, actor.clone());
+        let m: &dyn Monitor<TestEvent, DefaultTopic> = &metrics;
+        m.on_error("boom", &actor);
+        m.on_overflow(&envelope, &DefaultTopic, &actor, OverflowPolicy::Drop);
+        m.on_overflow(&envelope, &DefaultTopic, &actor, OverflowPolicy::Block);
+        m.on_event_dropped(&envelope, "rate \"limit\"", "too fast");
+        m.on_event_rejected(&envelope, &DefaultTopic);
+        m.on_lagged(5);
+
+        let text = metrics.render();
+        assert!(
+            text.contains("# TYPE app_errors_total counter\napp_errors_total{actor=\"slow\"} 1\n")
+        );
+        assert!(
+            text.contains(
+                "app_overflows_total{actor=\"slow\",topic=\"default\",policy=\"drop\"} 1\n"
+            )
+        );
+        assert!(text.contains(
+            "app_overflows_total{actor=\"slow\",topic=\"default\",policy=\"block\"} 1\n"
+        ));
+        assert!(text.contains("app_events_dropped_total{interceptor=\"rate \\\"limit\\\"\"} 1\n"));
+        assert!(text.contains("app_events_rejected_total{actor=\"slow\",topic=\"default\"} 1\n"));
+        assert!(text.contains("app_monitoring_events_lost_total 5\n"));
+        assert_eq!(metrics.error_count(&actor), 1);
+
+        let depth = QueueDepth::new(None, Some(ChannelDepth::new(3, 8)));
+        m.on_queue_depth(&depth, &actor);
+        let text = metrics.render();
+        assert!(text.contains("app_queue_depth{actor=\"slow\",queue=\"mailbox\"} 3\n"));
+        assert!(text.contains("app_queue_capacity{actor=\"slow\",queue=\"mailbox\"} 8\n"));
+        assert!(!text.contains("queue=\"sender\""));
+
+        m.on_actor_stop(&actor);
+        assert!(
+            metrics
+                .inner
+                .lock()
+                .unwrap()
+                .in_flight
+                .dispatched_at
+                .is_empty()
+        );
+        assert!(!metrics.render().contains("app_queue_depth{"));
+    }
+
+    #[test]
+    fn in_flight_timestamps_are_bounded() {
+        let metrics = Metrics::new();
+        let actor = make_id("actor");
+        let m: &dyn Monitor<TestEvent, DefaultTopic> = &metrics;
+        let first = Envelope::new(TestEvent, actor.clone());
+        m.on_event_dispatched(&first, &DefaultTopic, &actor);
+        for _ in 0..IN_FLIGHT_CAPACITY {
+            let envelope = Envelope::new(TestEvent, actor.clone());
+            m.on_event_dispatched(&envelope, &DefaultTopic, &actor);
+        }
+        {
+            let lock = metrics.inner.lock().unwrap();
+            assert_eq!(lock.in_flight.dispatched_at.len(), IN_FLIGHT_CAPACITY);
+            assert_eq!(lock.in_flight.dispatched_at.order.len(), IN_FLIGHT_CAPACITY);
+        }
+
+        m.on_event_handled(&first, &DefaultTopic, &actor);
+        assert!(metrics.render().contains(
+            "maiko_dispatch_to_handled_seconds_count{actor=\"actor\",topic=\"default\"} 0\n"
+        ));
+
+        m.on_lagged(1);
+        assert!(
+            metrics
+                .inner
+                .lock()
+                .unwrap()
+                .in_flight
+                .dispatched_at
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn writes_to_file() {
+        let path =
+            std::env::temp_dir().join(format!("maiko-metrics-{}.prom", uuid::Uuid::new_v4()));
+        let metrics = Metrics::new();
+        let actor = make_id("actor");
+        handle(&metrics, &Envelope::new(TestEvent, actor.clone()), &actor);
+
+        metrics.write_to(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), metrics.render());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
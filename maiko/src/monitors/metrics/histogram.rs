@@ -0,0 +1,94 @@
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+/// Default histogram buckets, suited to in-process latencies.
+pub(crate) const DEFAULT_BUCKETS: [Duration; 12] = [
+    Duration::from_micros(10),
+    Duration::from_micros(50),
+    Duration::from_micros(100),
+    Duration::from_micros(500),
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+];
+
+/// A Prometheus histogram of durations, in seconds.
+#[derive(Debug, Clone)]
+pub(crate) struct Histogram {
+    bounds: Arc<[f64]>,
+    /// Observations per bucket (not cumulative), the last one being `+Inf`.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: Arc<[f64]>) -> Self {
+        Self {
+            counts: vec![0; bounds.len() + 1],
+            bounds,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    /// Write the `_bucket`, `_sum` and `_count` samples. `labels` is the
+    /// rendered label set without braces, possibly empty.
+    pub fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            let le = match self.bounds.get(i) {
+                Some(bound) => bound.to_string(),
+                None => "+Inf".to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{sep}le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_cumulative() {
+        let mut histogram = Histogram::new(Arc::from([0.001, 0.01]));
+        histogram.observe(Duration::from_micros(500));
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_secs(1));
+
+        let mut out = String::new();
+        histogram.render(&mut out, "latency", "actor=\"a\"");
+        assert_eq!(
+            out,
+            "latency_bucket{actor=\"a\",le=\"0.001\"} 1\n\
+             latency_bucket{actor=\"a\",le=\"0.01\"} 2\n\
+             latency_bucket{actor=\"a\",le=\"+Inf\"} 3\n\
+             latency_sum{actor=\"a\"} 1.0055\n\
+             latency_count{actor=\"a\"} 3\n"
+        );
+    }
+}
@@ -6,7 +6,12 @@
 //! # Available Monitors
 //!
 //! - [`Tracer`] - Logs event lifecycle via `tracing` crate
+//! - [`Topology`] - Builds the observed sender → topic → receiver graph, exported as Mermaid or DOT
 //! - [`Recorder`] - Records events to a JSON Lines file (requires `recorder` feature)
+//! - [`Metrics`] - Counters and latency histograms in the Prometheus text format (requires `metrics` feature)
+//!
+//! The recorder's counterpart, which re-injects a recording into a running
+//! supervisor, is [`Replayer`](crate::replay::Replayer).
 //!
 //! # Example
 //!
@@ -22,9 +27,19 @@ pub use tracer::Tracer;
 mod actor_monitor;
 pub use actor_monitor::ActorMonitor;
 
+mod topology;
+pub use topology::{Topology, TopologyEdge};
+
 #[cfg(feature = "recorder")]
 mod recorder;
 
 #[cfg(feature = "recorder")]
 #[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
-pub use recorder::Recorder;
+pub use recorder::{FileNaming, Record, RecordMode, Recorder, RecorderConfig, RecorderStats};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use metrics::{Metrics, MetricsServer};
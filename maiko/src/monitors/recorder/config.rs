@@ -0,0 +1,207 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::monitoring::Stage;
+
+/// How many records a [`Recorder`](crate::monitors::Recorder) writes for an
+/// event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RecordMode {
+    /// A record for every stage of every delivery: an event received by
+    /// three actors produces three `dispatched`, three `delivered` and three
+    /// `handled` records.
+    #[default]
+    PerDelivery,
+    /// A single `dispatched` record per event, without a receiver. Delivery
+    /// and handling aren't recorded; overflows, drops, errors and actor
+    /// lifecycle still are.
+    PerEvent,
+}
+
+/// How a [`Recorder`](crate::monitors::Recorder) names files it has rotated.
+///
+/// The active file always keeps the configured path. When it is rotated, it
+/// is renamed according to this scheme (and gets a `.gz` suffix when
+/// compressed).
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FileNaming {
+    /// `events.jsonl` becomes `events.1.jsonl`, `events.2.jsonl`, ...
+    #[default]
+    Sequence,
+    /// `events.jsonl` becomes `events.<unix millis>.jsonl`.
+    Timestamp,
+    /// Name derived from the active path and the rotation's sequence number.
+    Custom(fn(&Path, u64) -> PathBuf),
+}
+
+impl FileNaming {
+    pub(crate) fn rotated_path(&self, path: &Path, sequence: u64) -> PathBuf {
+        match self {
+            FileNaming::Sequence => with_infix(path, &sequence.to_string()),
+            FileNaming::Timestamp => {
+                let millis = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_millis());
+                with_infix(path, &millis.to_string())
+            }
+            FileNaming::Custom(name) => name(path, sequence),
+        }
+    }
+}
+
+/// `dir/stem.ext` -> `dir/stem.infix.ext`
+fn with_infix(path: &Path, infix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}.{infix}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{infix}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Configuration of a [`Recorder`](crate::monitors::Recorder).
+///
+/// By default the recorder writes every stage of every delivery to a single,
+/// unbounded file from the monitor dispatcher, flushing after every record.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use maiko::monitoring::Stage;
+/// use maiko::monitors::{FileNaming, RecordMode, RecorderConfig};
+///
+/// let config = RecorderConfig::default()
+///     .with_mode(RecordMode::PerEvent)
+///     .with_stages([Stage::Dispatched, Stage::Overflow, Stage::Error])
+///     .with_max_file_size(64 * 1024 * 1024)
+///     .with_max_file_age(Duration::from_secs(3600))
+///     .with_file_naming(FileNaming::Timestamp)
+///     .with_max_files(24)
+///     .with_background_writer(4096);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    mode: RecordMode,
+    stages: Vec<Stage>,
+    max_file_size: Option<u64>,
+    max_file_age: Option<Duration>,
+    file_naming: FileNaming,
+    max_files: Option<usize>,
+    compress: bool,
+    queue_capacity: Option<usize>,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            mode: RecordMode::default(),
+            stages: Stage::ALL.to_vec(),
+            max_file_size: None,
+            max_file_age: None,
+            file_naming: FileNaming::default(),
+            max_files: None,
+            compress: false,
+            queue_capacity: None,
+        }
+    }
+}
+
+impl RecorderConfig {
+    /// Default: [`RecordMode::PerDelivery`]
+    pub fn with_mode(mut self, mode: RecordMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> RecordMode {
+        self.mode
+    }
+
+    /// Record only the given stages.
+    /// Default: all stages
+    pub fn with_stages(mut self, stages: impl IntoIterator<Item = Stage>) -> Self {
+        self.stages = stages.into_iter().collect();
+        self
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Rotate the file before it would grow beyond `bytes`.
+    /// Default: no limit
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    pub fn max_file_size(&self) -> Option<u64> {
+        self.max_file_size
+    }
+
+    /// Rotate the file once it's been written to for `age`. Checked when
+    /// events are recorded, so an idle recorder doesn't rotate.
+    /// Default: no limit
+    pub fn with_max_file_age(mut self, age: Duration) -> Self {
+        self.max_file_age = Some(age);
+        self
+    }
+
+    pub fn max_file_age(&self) -> Option<Duration> {
+        self.max_file_age
+    }
+
+    /// Default: [`FileNaming::Sequence`]
+    pub fn with_file_naming(mut self, naming: FileNaming) -> Self {
+        self.file_naming = naming;
+        self
+    }
+
+    pub fn file_naming(&self) -> FileNaming {
+        self.file_naming
+    }
+
+    /// Keep at most `count` rotated files, deleting the oldest ones.
+    /// Only files rotated by the same recorder are counted.
+    /// Default: keep all
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count);
+        self
+    }
+
+    pub fn max_files(&self) -> Option<usize> {
+        self.max_files
+    }
+
+    /// Gzip rotated files. Compression runs on the thread that writes
+    /// records, so combine it with
+    /// [`with_background_writer`](Self::with_background_writer) for large
+    /// files.
+    #[cfg(feature = "gzip")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    pub fn compression(&self) -> bool {
+        self.compress
+    }
+
+    /// Hand records to a dedicated writer thread through a queue of
+    /// `capacity` records, so the monitor dispatcher never waits for the
+    /// disk. Records that don't fit into the queue are dropped and counted
+    /// in [`RecorderStats`](crate::monitors::RecorderStats).
+    /// Default: write directly from the dispatcher
+    pub fn with_background_writer(mut self, capacity: usize) -> Self {
+        self.queue_capacity = Some(capacity.max(1));
+        self
+    }
+
+    pub fn background_writer(&self) -> Option<usize> {
+        self.queue_capacity
+    }
+}
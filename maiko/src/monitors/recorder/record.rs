@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ActorId, Envelope, monitoring::Stage};
+
+/// A single entry of a [`Recorder`](crate::monitors::Recorder) recording.
+///
+/// Every record has a sequence number, increasing by one per record (gaps
+/// mean records were dropped), the [`Stage`] it describes and the time it
+/// was made. The other fields depend on the stage:
+///
+/// | Stage | `actor` | `topic` | `envelope` | `detail` |
+/// |-------|---------|---------|------------|----------|
+/// | `dispatched`, `delivered`, `handled` | receiver¹ | ✓ | ✓ | |
+/// | `overflow` | receiver | ✓ | ✓ | overflow policy |
+/// | `dropped` | | | ✓ | `interceptor: reason` |
+/// | `error` | actor | | | error message |
+/// | `actor_registered`, `actor_stopped` | actor | | | |
+///
+/// ¹ Empty for `dispatched` records in [`RecordMode::PerEvent`](super::RecordMode::PerEvent).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "E: serde::de::DeserializeOwned"))]
+pub struct Record<E> {
+    seq: u64,
+    stage: Stage,
+    timestamp: u64,
+    actor: Option<ActorId>,
+    topic: Option<String>,
+    envelope: Option<Envelope<E>>,
+    detail: Option<String>,
+}
+
+impl<E> Record<E> {
+    /// Position of the record in the recording, starting at 0.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Time the record was made, in nanoseconds since Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Receiving actor for event stages, the actor concerned otherwise.
+    pub fn actor(&self) -> Option<&ActorId> {
+        self.actor.as_ref()
+    }
+
+    /// [`Label`](crate::Label) of the topic the event was routed under.
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    pub fn envelope(&self) -> Option<&Envelope<E>> {
+        self.envelope.as_ref()
+    }
+
+    pub fn into_envelope(self) -> Option<Envelope<E>> {
+        self.envelope
+    }
+
+    /// Overflow policy, drop reason or error message.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+}
+
+/// Borrowed form of [`Record`] used for writing, so envelopes aren't cloned.
+#[derive(Serialize)]
+#[serde(bound(serialize = "E: Serialize"))]
+pub(crate) struct RecordRef<'a, E> {
+    pub seq: u64,
+    pub stage: Stage,
+    pub timestamp: u64,
+    pub actor: Option<&'a ActorId>,
+    pub topic: Option<&'a str>,
+    pub envelope: Option<&'a Envelope<E>>,
+    pub detail: Option<&'a str>,
+}
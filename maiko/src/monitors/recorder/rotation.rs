@@ -0,0 +1,143 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, atomic::Ordering},
+    time::Instant,
+};
+
+use super::{RecorderConfig, RecorderStats};
+
+/// Writes records to the recorder's file, rotating it as configured.
+pub(crate) struct RotatingWriter {
+    path: PathBuf,
+    config: RecorderConfig,
+    writer: BufWriter<File>,
+    size: u64,
+    opened: Instant,
+    sequence: u64,
+    rotated: VecDeque<PathBuf>,
+    stats: Arc<RecorderStats>,
+}
+
+impl RotatingWriter {
+    pub fn create(
+        path: &Path,
+        config: RecorderConfig,
+        stats: Arc<RecorderStats>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            config,
+            writer: BufWriter::new(File::create(path)?),
+            size: 0,
+            opened: Instant::now(),
+            sequence: 0,
+            rotated: VecDeque::new(),
+            stats,
+        })
+    }
+
+    /// Append a complete record, rotating the file first if needed.
+    pub fn write_record(&mut self, record: &[u8]) -> io::Result<()> {
+        if self.needs_rotation(record.len() as u64) {
+            self.rotate()?;
+        }
+        self.writer.write_all(record)?;
+        self.size += record.len() as u64;
+        self.stats.written.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn needs_rotation(&self, len: u64) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_big = self
+            .config
+            .max_file_size()
+            .is_some_and(|max| self.size + len > max);
+        let too_old = self
+            .config
+            .max_file_age()
+            .is_some_and(|age| self.opened.elapsed() >= age);
+        too_big || too_old
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        let target = self.next_rotated_path();
+        fs::rename(&self.path, &target)?;
+        self.writer = BufWriter::new(File::create(&self.path)?);
+        self.size = 0;
+        self.opened = Instant::now();
+
+        let target = if self.config.compression() {
+            compress(&target)?
+        } else {
+            target
+        };
+        self.stats.rotations.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(file=%target.display(), "recorder rotated file");
+
+        self.rotated.push_back(target);
+        if let Some(max) = self.config.max_files() {
+            while self.rotated.len() > max {
+                if let Some(old) = self.rotated.pop_front() {
+                    if let Err(e) = fs::remove_file(&old) {
+                        tracing::warn!(file=%old.display(), error=%e, "recorder failed to remove old file");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Next name for a rotated file that doesn't clash with an existing one.
+    fn next_rotated_path(&mut self) -> PathBuf {
+        loop {
+            self.sequence += 1;
+            let path = self
+                .config
+                .file_naming()
+                .rotated_path(&self.path, self.sequence);
+            if !path.exists() && !gz_path(&path).exists() {
+                return path;
+            }
+        }
+    }
+}
+
+impl Drop for RotatingWriter {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+#[cfg(feature = "gzip")]
+fn compress(path: &Path) -> io::Result<PathBuf> {
+    use flate2::{Compression, write::GzEncoder};
+
+    let target = gz_path(path);
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    io::copy(&mut File::open(path)?, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(path)?;
+    Ok(target)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn compress(path: &Path) -> io::Result<PathBuf> {
+    Ok(path.to_path_buf())
+}
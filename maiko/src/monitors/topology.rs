@@ -0,0 +1,434 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    ActorId, Envelope, Event, EventId, Label, Topic,
+    internal::{dot_text, mermaid_id, mermaid_text},
+    monitoring::Monitor,
+};
+
+/// Monitor that builds the observed data flow graph: which actors publish
+/// to which topics, and which actors receive from them.
+///
+/// Unlike [`Supervisor::to_mermaid`](crate::Supervisor::to_mermaid), which
+/// shows declared subscriptions only, the graph also shows producers and
+/// counts the events that actually flowed along each edge. Export it at
+/// any time as Mermaid or Graphviz DOT.
+///
+/// Only routed events are observed: an event no actor subscribes to never
+/// reaches the monitors, so its sender shows no outgoing edge.
+///
+/// ```ignore
+/// let topology = Topology::new();
+/// sup.monitors().add(topology.clone()).await;
+///
+/// // Later, from any thread:
+/// std::fs::write("flow.dot", topology.to_dot())?;
+/// ```
+#[derive(Clone)]
+pub struct Topology {
+    inner: Arc<Mutex<TopologyInner>>,
+}
+
+#[derive(Default)]
+struct TopologyInner {
+    actors: BTreeSet<String>,
+    published: BTreeMap<(String, String), EdgeStats>,
+    received: BTreeMap<(String, String), EdgeStats>,
+    /// The event whose dispatches were seen last. The broker dispatches an
+    /// event to all its receivers in a row, so it's published only once.
+    /// Replays of retained events come through `on_event_replayed` and
+    /// don't break the run; lost callbacks can only hide a publication.
+    last_published: Option<EventId>,
+}
+
+#[derive(Default)]
+struct EdgeStats {
+    count: u64,
+    labels: BTreeSet<String>,
+}
+
+/// An edge of the observed topology, from an actor to a topic it
+/// published to or from a topic to an actor that received from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopologyEdge {
+    from: String,
+    to: String,
+    count: u64,
+    labels: Vec<String>,
+}
+
+impl TopologyEdge {
+    /// Name of the sending actor or topic label.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Topic label or name of the receiving actor.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Number of events that flowed along the edge.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Distinct labels of those events, sorted.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Edge caption for the diagrams, e.g. `Fill, Order (12)`.
+    fn caption(&self) -> String {
+        format!("{} ({})", self.labels.join(", "), self.count)
+    }
+}
+
+impl Topology {
+    /// Create an empty `Topology`.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(TopologyInner::default())),
+        }
+    }
+
+    /// Names of all actors seen, registered or sending, sorted.
+    pub fn actors(&self) -> Vec<String> {
+        let lock = self.inner.lock().unwrap();
+        lock.actors.iter().cloned().collect()
+    }
+
+    /// Actor → topic edges, one per sender and topic it published to.
+    pub fn published(&self) -> Vec<TopologyEdge> {
+        let lock = self.inner.lock().unwrap();
+        edges(&lock.published)
+    }
+
+    /// Topic → actor edges, one per topic and actor it was dispatched to.
+    pub fn received(&self) -> Vec<TopologyEdge> {
+        let lock = self.inner.lock().unwrap();
+        edges(&lock.received)
+    }
+
+    /// Forget all observed actors and edges.
+    pub fn clear(&self) {
+        let mut lock = self.inner.lock().unwrap();
+        *lock = TopologyInner::default();
+    }
+
+    /// Render the graph as a Mermaid flowchart.
+    ///
+    /// Actors are boxes and topics circles, as in
+    /// [`Supervisor::to_mermaid`](crate::Supervisor::to_mermaid). Edges are
+    /// captioned with the event labels and count.
+    ///
+    /// ```text
+    /// flowchart LR
+    ///     a_exchange["exchange"]
+    ///     t_Orders(("Orders"))
+    ///     a_trader -->|"Order (3)"| t_Orders
+    ///     t_Orders -->|"Order (3)"| a_exchange
+    /// ```
+    pub fn to_mermaid(&self) -> String {
+        let (actors, topics, edges) = self.graph();
+        let mut lines = vec!["flowchart LR".to_string()];
+        for actor in &actors {
+            lines.push(format!(
+                "    {}[\"{}\"]",
+                mermaid_id("a", actor),
+                mermaid_text(actor)
+            ));
+        }
+        for topic in &topics {
+            lines.push(format!(
+                "    {}((\"{}\"))",
+                mermaid_id("t", topic),
+                mermaid_text(topic)
+            ));
+        }
+        for (edge, from, to) in &edges {
+            lines.push(format!(
+                "    {} -->|\"{}\"| {}",
+                mermaid_id(from, &edge.from),
+                mermaid_text(&edge.caption()),
+                mermaid_id(to, &edge.to)
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Render the graph in the Graphviz DOT language.
+    ///
+    /// ```text
+    /// digraph topology {
+    ///     rankdir=LR;
+    ///     "a:exchange" [label="exchange", shape=box];
+    ///     "t:Orders" [label="Orders", shape=ellipse];
+    ///     "a:trader" -> "t:Orders" [label="Order (3)"];
+    ///     "t:Orders" -> "a:exchange" [label="Order (3)"];
+    /// }
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let (actors, topics, edges) = self.graph();
+        let mut lines = vec![
+            "digraph topology {".to_string(),
+            "    rankdir=LR;".to_string(),
+        ];
+        for actor in &actors {
+            lines.push(format!(
+                "    \"a:{0}\" [label=\"{0}\", shape=box];",
+                dot_text(actor)
+            ));
+        }
+        for topic in &topics {
+            lines.push(format!(
+                "    \"t:{0}\" [label=\"{0}\", shape=ellipse];",
+                dot_text(topic)
+            ));
+        }
+        for (edge, from, to) in &edges {
+            lines.push(format!(
+                "    \"{}:{}\" -> \"{}:{}\" [label=\"{}\"];",
+                from,
+                dot_text(&edge.from),
+                to,
+                dot_text(&edge.to),
+                dot_text(&edge.caption())
+            ));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Actors, topics, and all edges.
+    fn graph(&self) -> (Vec<String>, BTreeSet<String>, Vec<KindedEdge>) {
+        let lock = self.inner.lock().unwrap();
+        let actors = lock.actors.iter().cloned().collect();
+        let topics = lock
+            .published
+            .keys()
+            .map(|(_, topic)| topic)
+            .chain(lock.received.keys().map(|(topic, _)| topic))
+            .cloned()
+            .collect();
+        let published = edges(&lock.published).into_iter().map(|e| (e, "a", "t"));
+        let received = edges(&lock.received).into_iter().map(|e| (e, "t", "a"));
+        (actors, topics, published.chain(received).collect())
+    }
+}
+
+/// An edge with the kinds of the nodes it joins: `a` for actor, `t` for topic.
+type KindedEdge = (TopologyEdge, &'static str, &'static str);
+
+fn edges(stats: &BTreeMap<(String, String), EdgeStats>) -> Vec<TopologyEdge> {
+    stats
+        .iter()
+        .map(|((from, to), stats)| TopologyEdge {
+            from: from.clone(),
+            to: to.clone(),
+            count: stats.count,
+            labels: stats.labels.iter().cloned().collect(),
+        })
+        .collect()
+}
+
+fn record(stats: &mut BTreeMap<(String, String), EdgeStats>, from: &str, to: &str, label: String) {
+    let edge = stats.entry((from.to_string(), to.to_string())).or_default();
+    edge.count += 1;
+    edge.labels.insert(label);
+}
+
+impl<E, T> Monitor<E, T> for Topology
+where
+    E: Event + Label,
+    T: Topic<E> + Label,
+{
+    fn on_actor_registered(&self, actor_id: &ActorId) {
+        let mut lock = self.inner.lock().unwrap();
+        lock.actors.insert(actor_id.name().to_string());
+    }
+
+    fn on_event_dispatched(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        let sender = envelope.meta().actor_name();
+        let topic = topic.label();
+        let label = envelope.event().label().into_owned();
+
+        let mut lock = self.inner.lock().unwrap();
+        if lock.last_published != Some(envelope.id()) {
+            lock.last_published = Some(envelope.id());
+            lock.actors.insert(sender.to_string());
+            record(&mut lock.published, sender, &topic, label.clone());
+        }
+        lock.actors.insert(receiver.name().to_string());
+        record(&mut lock.received, &topic, receiver.name(), label);
+    }
+
+    /// A replay was counted as published when first dispatched; only the
+    /// new receiving edge is recorded.
+    fn on_event_replayed(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
+        let topic = topic.label();
+        let label = envelope.event().label().into_owned();
+        let mut lock = self.inner.lock().unwrap();
+        lock.actors.insert(receiver.name().to_string());
+        record(&mut lock.received, &topic, receiver.name(), label);
+    }
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lock = self.inner.lock().unwrap();
+        f.debug_struct("Topology")
+            .field("actors", &lock.actors.len())
+            .field("published", &lock.published.len())
+            .field("received", &lock.received.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    enum Trade {
+        Order,
+        Fill,
+    }
+    impl Event for Trade {}
+    impl Label for Trade {
+        fn label(&self) -> Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    enum TradeTopic {
+        Orders,
+        Fills,
+    }
+    impl Topic<Trade> for TradeTopic {
+        fn from_event(event: &Trade) -> Self {
+            match event {
+                Trade::Order => TradeTopic::Orders,
+                Trade::Fill => TradeTopic::Fills,
+            }
+        }
+    }
+    impl Label for TradeTopic {
+        fn label(&self) -> Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    fn make_id(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    fn dispatch(topology: &Topology, envelope: &Envelope<Trade>, receivers: &[&ActorId]) {
+        let topic = TradeTopic::from_event(envelope.event());
+        for receiver in receivers {
+            topology.on_event_dispatched(envelope, &topic, receiver);
+        }
+    }
+
+    #[test]
+    fn counts_publications_once_per_event() {
+        let topology = Topology::new();
+        let (trader, exchange, audit) = (make_id("trader"), make_id("exchange"), make_id("audit"));
+        for _ in 0..3 {
+            let order = Envelope::new(Trade::Order, trader.clone());
+            dispatch(&topology, &order, &[&exchange, &audit]);
+        }
+
+        let published = topology.published();
+        assert_eq!(published.len(), 1);
+        assert_eq!(
+            (published[0].from(), published[0].to()),
+            ("trader", "Orders")
+        );
+        assert_eq!(published[0].count(), 3);
+        assert_eq!(published[0].labels(), ["Order"]);
+
+        let received = topology.received();
+        assert_eq!(received.len(), 2);
+        assert!(
+            received
+                .iter()
+                .all(|e| e.from() == "Orders" && e.count() == 3)
+        );
+        assert_eq!(topology.actors(), ["audit", "exchange", "trader"]);
+    }
+
+    #[test]
+    fn replays_are_not_publications() {
+        let topology = Topology::new();
+        let (trader, exchange, late) = (make_id("trader"), make_id("exchange"), make_id("late"));
+        let first = Envelope::new(Trade::Order, trader.clone());
+        let second = Envelope::new(Trade::Order, trader);
+        dispatch(&topology, &first, &[&exchange]);
+        dispatch(&topology, &second, &[&exchange]);
+        let m: &dyn Monitor<Trade, TradeTopic> = &topology;
+        m.on_event_replayed(&first, &TradeTopic::Orders, &late);
+
+        assert_eq!(topology.published()[0].count(), 2);
+        let received = topology.received();
+        assert_eq!(received.len(), 2);
+        assert!(received.iter().any(|e| e.to() == "late" && e.count() == 1));
+    }
+
+    #[test]
+    fn shows_registered_actors_without_traffic() {
+        let topology = Topology::new();
+        let m: &dyn Monitor<Trade, TradeTopic> = &topology;
+        m.on_actor_registered(&make_id("idle"));
+
+        assert!(topology.to_mermaid().contains("a_idle[\"idle\"]"));
+        assert!(
+            topology
+                .to_dot()
+                .contains("\"a:idle\" [label=\"idle\", shape=box];")
+        );
+        assert!(topology.published().is_empty());
+    }
+
+    #[test]
+    fn exports_mermaid_and_dot() {
+        let topology = Topology::new();
+        let (trader, exchange) = (make_id("trader"), make_id("exchange"));
+        dispatch(
+            &topology,
+            &Envelope::new(Trade::Order, trader.clone()),
+            &[&exchange],
+        );
+        dispatch(
+            &topology,
+            &Envelope::new(Trade::Fill, exchange.clone()),
+            &[&trader],
+        );
+
+        let mermaid = topology.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("t_Orders((\"Orders\"))"));
+        assert!(mermaid.contains("a_trader -->|\"Order (1)\"| t_Orders"));
+        assert!(mermaid.contains("t_Fills -->|\"Fill (1)\"| a_trader"));
+
+        let dot = topology.to_dot();
+        assert!(dot.starts_with("digraph topology {\n"));
+        assert!(dot.contains("\"a:exchange\" -> \"t:Fills\" [label=\"Fill (1)\"];"));
+        assert!(dot.contains("\"t:Orders\" -> \"a:exchange\" [label=\"Order (1)\"];"));
+        assert!(dot.ends_with('}'));
+
+        topology.clear();
+        assert!(topology.actors().is_empty());
+    }
+}
@@ -1,11 +1,17 @@
-use crate::{ActorId, Envelope, Event, OverflowPolicy, Topic, monitoring::Monitor};
+use std::time::Duration;
+
+use crate::{
+    ActorId, Envelope, Event, OverflowPolicy, StepAction, Topic,
+    monitoring::{Monitor, QueueDepth},
+};
 
 /// A monitor that logs event lifecycle to the `tracing` crate.
 ///
 /// Provides visibility into event flow without custom code. Log levels:
-/// - `trace` - event dispatched/delivered/overflow (high volume)
-/// - `debug` - event handled
-/// - `warn` - errors
+/// - `trace` - event dispatched/delivered/overflow, step entered/exited, queue depths (high volume)
+/// - `debug` - event handled, event dropped by an interceptor or for an
+///   undeclared topic, step failed
+/// - `warn` - errors, lost monitoring events
 /// - `info` - actor stopped
 ///
 /// # Example
@@ -52,6 +58,25 @@ where
         );
     }
 
+    fn on_event_dropped(&self, envelope: &Envelope<E>, interceptor: &str, reason: &str) {
+        tracing::debug!(
+            event_id = %envelope.id(),
+            sender = %envelope.meta().actor_name(),
+            interceptor = %interceptor,
+            reason = %reason,
+            "event dropped"
+        );
+    }
+
+    fn on_event_rejected(&self, envelope: &Envelope<E>, topic: &T) {
+        tracing::debug!(
+            event_id = %envelope.id(),
+            sender = %envelope.meta().actor_name(),
+            topic = ?topic,
+            "event sent to an undeclared topic dropped"
+        );
+    }
+
     fn on_error(&self, err: &str, actor_id: &ActorId) {
         tracing::warn!(
             actor = %actor_id.name(),
@@ -74,6 +99,49 @@ where
         )
     }
 
+    fn on_step_enter(&self, actor_id: &ActorId) {
+        tracing::trace!(actor = %actor_id.name(), "step entered");
+    }
+
+    fn on_step_exit_timed(&self, step_action: &StepAction, duration: Duration, actor_id: &ActorId) {
+        tracing::trace!(
+            actor = %actor_id.name(),
+            action = ?step_action,
+            duration = ?duration,
+            "step exited"
+        );
+    }
+
+    fn on_step_error(&self, err: &str, duration: Duration, actor_id: &ActorId) {
+        tracing::debug!(
+            actor = %actor_id.name(),
+            error = %err,
+            duration = ?duration,
+            "step failed"
+        );
+    }
+
+    fn on_step_cancel(&self, duration: Duration, actor_id: &ActorId) {
+        tracing::trace!(
+            actor = %actor_id.name(),
+            duration = ?duration,
+            "step cancelled"
+        );
+    }
+
+    fn on_lagged(&self, count: u64) {
+        tracing::warn!(count, "monitoring events lost, monitoring channel full");
+    }
+
+    fn on_queue_depth(&self, depth: &QueueDepth, actor_id: &ActorId) {
+        tracing::trace!(
+            actor = %actor_id.name(),
+            sender = ?depth.sender().map(|d| d.to_string()),
+            mailbox = ?depth.mailbox().map(|d| d.to_string()),
+            "queue depth"
+        );
+    }
+
     fn on_overflow(
         &self,
         envelope: &Envelope<E>,
@@ -0,0 +1,295 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::{Actor, Envelope, Error, Event, Result, StepAction};
+
+/// Adapter running an actor written against a narrower event type.
+///
+/// Every actor in a [`Supervisor<E, T>`](crate::Supervisor) processes the
+/// system event type `E`. Actor crates, however, can be written against
+/// their own small event enum `S`, as long as it converts both ways:
+///
+/// - `S: TryFrom<&E>` - incoming events are converted on delivery. Actors
+///   registered through the supervisor's narrow methods are never sent
+///   events that don't convert: the broker leaves them out on dispatch.
+///   An adapter registered by hand skips them without calling
+///   `handle_event`, although they still count as delivered and handled.
+/// - `S: Into<E>` - outgoing events are converted on send by the narrowed
+///   [`Context<S>`](crate::Context) the actor receives.
+///
+/// Metadata (id, sender, correlation) is preserved in both directions.
+///
+/// Register such actors with [`Supervisor::add_narrow_actor`] or
+/// [`Supervisor::build_narrow_actor`], which wrap the actor in this adapter.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Event, Clone, Debug)]
+/// enum LogEvent { Line(String) }
+///
+/// impl TryFrom<&AppEvent> for LogEvent { /* ... */ }
+/// impl From<LogEvent> for AppEvent { /* ... */ }
+///
+/// struct Logger { ctx: Context<LogEvent> }
+///
+/// impl Actor for Logger {
+///     type Event = LogEvent;
+///     async fn handle_event(&mut self, envelope: &Envelope<LogEvent>) -> Result<()> {
+///         let LogEvent::Line(line) = envelope.event();
+///         println!("{line}");
+///         Ok(())
+///     }
+/// }
+///
+/// sup.add_narrow_actor("logger", |ctx| Logger { ctx }, &[AppTopic::Log])?;
+/// ```
+///
+/// [`Supervisor::add_narrow_actor`]: crate::Supervisor::add_narrow_actor
+/// [`Supervisor::build_narrow_actor`]: crate::Supervisor::build_narrow_actor
+pub struct NarrowActor<A, E> {
+    actor: A,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<A, E> NarrowActor<A, E>
+where
+    E: Event,
+    A: Actor,
+    A::Event: Into<E> + for<'e> TryFrom<&'e E>,
+{
+    /// Wrap an actor so it can be registered in a system with event type `E`.
+    pub fn new(actor: A) -> Self {
+        Self {
+            actor,
+            _event: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the wrapped actor.
+    pub fn inner(&self) -> &A {
+        &self.actor
+    }
+}
+
+impl<A, E> Actor for NarrowActor<A, E>
+where
+    E: Event,
+    A: Actor,
+    A::Event: Into<E> + for<'e> TryFrom<&'e E>,
+{
+    type Event = E;
+
+    async fn handle_event(&mut self, envelope: &Envelope<E>) -> Result<()> {
+        let Ok(event) = A::Event::try_from(envelope.event()) else {
+            return Ok(());
+        };
+        let envelope = Envelope::with_meta(event, envelope.meta().clone());
+        self.actor.handle_event(&envelope).await
+    }
+
+    fn step(&mut self) -> impl Future<Output = Result<StepAction>> + Send {
+        self.actor.step()
+    }
+
+    fn on_start(&mut self) -> impl Future<Output = Result<()>> + Send {
+        self.actor.on_start()
+    }
+
+    fn on_shutdown(&mut self) -> impl Future<Output = Result<()>> + Send {
+        self.actor.on_shutdown()
+    }
+
+    fn on_error(&self, error: Error) -> Result<()> {
+        self.actor.on_error(error)
+    }
+}
+
+impl<A: fmt::Debug, E> fmt::Debug for NarrowActor<A, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NarrowActor").field(&self.actor).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+    use super::*;
+    use crate::{ActorId, Context, DefaultTopic, Supervisor};
+
+    #[derive(Clone, Debug)]
+    enum AppEvent {
+        Ping(u32),
+        Pong(u32),
+        Other,
+    }
+    impl Event for AppEvent {}
+
+    #[derive(Clone, Debug)]
+    enum PingPong {
+        Ping(u32),
+        Pong(u32),
+    }
+    impl Event for PingPong {}
+
+    impl TryFrom<&AppEvent> for PingPong {
+        type Error = ();
+        fn try_from(event: &AppEvent) -> std::result::Result<Self, ()> {
+            match event {
+                AppEvent::Ping(n) => Ok(PingPong::Ping(*n)),
+                AppEvent::Pong(n) => Ok(PingPong::Pong(*n)),
+                AppEvent::Other => Err(()),
+            }
+        }
+    }
+
+    impl From<PingPong> for AppEvent {
+        fn from(event: PingPong) -> Self {
+            match event {
+                PingPong::Ping(n) => AppEvent::Ping(n),
+                PingPong::Pong(n) => AppEvent::Pong(n),
+            }
+        }
+    }
+
+    struct Ponger {
+        ctx: Context<PingPong>,
+        handled: Arc<AtomicUsize>,
+    }
+
+    impl Actor for Ponger {
+        type Event = PingPong;
+
+        async fn handle_event(&mut self, envelope: &Envelope<PingPong>) -> Result<()> {
+            self.handled.fetch_add(1, Ordering::Relaxed);
+            if let PingPong::Ping(n) = envelope.event() {
+                self.ctx
+                    .send_child_event(PingPong::Pong(*n), envelope.meta())
+                    .await?;
+            }
+            Ok(())
+        }
+    }
+
+    struct Collector(UnboundedSender<Envelope<AppEvent>>);
+
+    impl Actor for Collector {
+        type Event = AppEvent;
+
+        async fn handle_event(&mut self, envelope: &Envelope<AppEvent>) -> Result<()> {
+            let _ = self.0.send(envelope.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn narrow_actor_converts_on_delivery_and_send() {
+        let handled = Arc::new(AtomicUsize::new(0));
+        let (tx, mut rx) = unbounded_channel();
+
+        let mut sup = Supervisor::<AppEvent>::default();
+        let counter = handled.clone();
+        let ponger = sup
+            .add_narrow_actor(
+                "ponger",
+                |ctx| Ponger {
+                    ctx,
+                    handled: counter,
+                },
+                &[DefaultTopic],
+            )
+            .unwrap();
+        sup.add_actor("collector", |_| Collector(tx), &[DefaultTopic])
+            .unwrap();
+        sup.start().await.unwrap();
+
+        let ping = Envelope::new(AppEvent::Ping(7), ActorId::new(Arc::from("test")));
+        let ping_id = ping.id();
+        sup.send(AppEvent::Other).await.unwrap();
+        sup.sender.send(Arc::new(ping)).await.unwrap();
+
+        let pong = loop {
+            let envelope = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .expect("pong not received")
+                .unwrap();
+            if matches!(envelope.event(), AppEvent::Pong(_)) {
+                break envelope;
+            }
+        };
+        sup.stop().await.unwrap();
+
+        assert!(matches!(pong.event(), AppEvent::Pong(7)));
+        assert_eq!(pong.meta().actor_id(), &ponger);
+        assert_eq!(pong.meta().correlation_id(), Some(ping_id));
+        assert_eq!(handled.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "monitoring")]
+    #[tokio::test(start_paused = true)]
+    async fn unconvertible_events_are_not_dispatched() {
+        use std::sync::Mutex;
+
+        use crate::monitoring::Monitor;
+
+        #[derive(Clone, Default)]
+        struct Handled(Arc<Mutex<Vec<(String, String)>>>);
+        impl Monitor<AppEvent> for Handled {
+            fn on_event_handled(&self, e: &Envelope<AppEvent>, _: &DefaultTopic, r: &ActorId) {
+                let entry = (r.name().to_string(), format!("{:?}", e.event()));
+                self.0.lock().unwrap().push(entry);
+            }
+        }
+
+        let mut sup = Supervisor::<AppEvent>::default();
+        let handled = Handled::default();
+        sup.monitors().add(handled.clone()).await;
+        sup.add_narrow_actor(
+            "ponger",
+            |ctx| Ponger {
+                ctx,
+                handled: Arc::new(AtomicUsize::new(0)),
+            },
+            &[DefaultTopic],
+        )
+        .unwrap();
+        sup.start().await.unwrap();
+
+        sup.send(AppEvent::Other).await.unwrap();
+        sup.send(AppEvent::Pong(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sup.stop().await.unwrap();
+
+        assert_eq!(
+            *handled.0.lock().unwrap(),
+            [("ponger".to_string(), "Pong(1)".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn narrow_context_shares_identity() {
+        let mut sup = Supervisor::<AppEvent>::default();
+        let id = sup
+            .build_narrow_actor("ponger", |ctx: Context<PingPong>| {
+                assert_eq!(ctx.actor_name(), "ponger");
+                assert!(ctx.is_alive());
+                Ponger {
+                    ctx,
+                    handled: Arc::new(AtomicUsize::new(0)),
+                }
+            })
+            .topics(&[DefaultTopic])
+            .channel_capacity(4)
+            .build()
+            .unwrap();
+        assert_eq!(id.name(), "ponger");
+    }
+}
@@ -0,0 +1,238 @@
+//! Replay of recorded events.
+//!
+//! A [`Replayer`] reads a recording made by the
+//! [`Recorder`](crate::monitors::Recorder) monitor and re-injects its events
+//! into a running supervisor, e.g. to reproduce an incident locally.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+    time::Duration,
+};
+
+use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+
+use crate::{
+    Envelope, Event, Result, Supervisor, Topic,
+    codec::{self, Codec, Json},
+    monitors::Record,
+};
+
+/// Re-injects a recording made by [`Recorder`](crate::monitors::Recorder) into a
+/// running supervisor.
+///
+/// A recording usually holds several records per event (one per stage and
+/// receiver). The replayer keeps only the first record of every event id
+/// and replays envelopes in recorded order, with
+/// their original ids, sender [`ActorId`](crate::ActorId)s and correlation
+/// ids. The broker routes them as usual, skipping the original sender if an
+/// actor of that name exists.
+///
+/// By default envelopes are sent as fast as the broker accepts them. With
+/// [`with_speed`](Self::with_speed) the original gaps between events are
+/// honoured, scaled by the given factor.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let replayer = Replayer::<MyEvent>::open("incident.jsonl")?.with_speed(10.0);
+/// sup.start().await?;
+/// replayer.replay(&sup).await?;
+/// ```
+pub struct Replayer<E> {
+    envelopes: Vec<Envelope<E>>,
+    speed: Option<f64>,
+}
+
+impl<E: Event + DeserializeOwned> Replayer<E> {
+    /// Load a JSON Lines recording.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::open_with_codec(path, Json)
+    }
+
+    /// Load a recording written with the given codec.
+    pub fn open_with_codec<P: AsRef<Path>, C: Codec>(path: P, codec: C) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut buf = Vec::new();
+        let mut seen = HashSet::new();
+        let mut envelopes = Vec::new();
+        let mut record = 0;
+        while codec::read_record(&codec, &mut reader, &mut buf)? {
+            record += 1;
+            if codec.is_line_delimited() && buf.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            // Recordings made before records carried a stage hold bare envelopes.
+            let envelope = match codec.decode::<Record<E>>(&buf) {
+                Ok(record) => record.into_envelope(),
+                Err(e) => Some(codec.decode::<Envelope<E>>(&buf).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("record {record}: {e}"))
+                })?),
+            };
+            if let Some(envelope) = envelope.filter(|envelope| seen.insert(envelope.id())) {
+                envelopes.push(envelope);
+            }
+        }
+        Ok(Self {
+            envelopes,
+            speed: None,
+        })
+    }
+
+    /// Honour the recorded timing, scaled by `factor`: `1.0` is real time,
+    /// `2.0` twice as fast. Non-positive factors replay as fast as possible.
+    pub fn with_speed(mut self, factor: f64) -> Self {
+        self.speed = (factor > 0.0).then_some(factor);
+        self
+    }
+
+    /// Replay as fast as possible (the default).
+    pub fn as_fast_as_possible(mut self) -> Self {
+        self.speed = None;
+        self
+    }
+
+    /// Unique envelopes of the recording, in replay order.
+    pub fn envelopes(&self) -> &[Envelope<E>] {
+        &self.envelopes
+    }
+
+    /// Number of unique envelopes to replay.
+    pub fn len(&self) -> usize {
+        self.envelopes.len()
+    }
+
+    /// Returns `true` if the recording has no envelope to replay.
+    pub fn is_empty(&self) -> bool {
+        self.envelopes.is_empty()
+    }
+
+    /// Send all envelopes into the supervisor's broker and return how many
+    /// were sent.
+    ///
+    /// Events that were recorded out of timestamp order are sent without
+    /// delay.
+    pub async fn replay<T: Topic<E>>(&self, supervisor: &Supervisor<E, T>) -> Result<usize> {
+        let Some(first) = self.envelopes.first() else {
+            return Ok(0);
+        };
+        let origin = first.meta().timestamp();
+        let start = Instant::now();
+        for envelope in &self.envelopes {
+            if let Some(speed) = self.speed {
+                let offset = envelope.meta().timestamp().saturating_sub(origin);
+                let delay = Duration::from_nanos(offset).div_f64(speed);
+                tokio::time::sleep_until(start + delay).await;
+            }
+            supervisor.send_envelope(envelope.clone()).await?;
+        }
+        Ok(self.envelopes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use futures_util::FutureExt;
+    use serde::{Deserialize, Serialize};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{Actor, ActorId, DefaultTopic, monitoring::Monitor, monitors::Recorder};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Tick(u64);
+    impl Event for Tick {}
+
+    struct Collector(mpsc::UnboundedSender<Envelope<Tick>>);
+
+    impl Actor for Collector {
+        type Event = Tick;
+
+        async fn handle_event(&mut self, envelope: &Envelope<Tick>) -> Result<()> {
+            let _ = self.0.send(envelope.clone());
+            Ok(())
+        }
+    }
+
+    fn record(path: &Path, envelopes: &[Envelope<Tick>]) {
+        let recorder = Recorder::new(path).unwrap();
+        for envelope in envelopes {
+            for receiver in ["a", "b"] {
+                Monitor::<Tick, DefaultTopic>::on_event_dispatched(
+                    &recorder,
+                    envelope,
+                    &DefaultTopic,
+                    &ActorId::new(Arc::from(receiver)),
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_unique_envelopes_with_original_metadata() {
+        let path =
+            std::env::temp_dir().join(format!("maiko_replay_{}.jsonl", uuid::Uuid::new_v4()));
+        let first = Envelope::new(Tick(1), ActorId::new(Arc::from("clock")));
+        let second =
+            Envelope::with_correlation(Tick(2), ActorId::new(Arc::from("clock")), first.id());
+        record(&path, &[first.clone(), second.clone()]);
+
+        let replayer = Replayer::<Tick>::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(replayer.len(), 2);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut sup = Supervisor::<Tick>::default();
+        sup.add_actor("collector", |_| Collector(tx), &[DefaultTopic])
+            .unwrap();
+        sup.start().await.unwrap();
+        assert_eq!(replayer.replay(&sup).await.unwrap(), 2);
+
+        for original in [first, second] {
+            let replayed = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(replayed.meta(), original.meta());
+            assert_eq!(replayed.event(), original.event());
+        }
+        sup.stop().await.unwrap();
+    }
+
+    /// An envelope sent `gap` after `previous`.
+    fn later(previous: &Envelope<Tick>, gap: Duration) -> Envelope<Tick> {
+        let envelope = Envelope::new(Tick(2), previous.meta().actor_id().clone());
+        let timestamp = previous.meta().timestamp() + gap.as_nanos() as u64;
+        let json = serde_json::to_string(&envelope).unwrap().replace(
+            &format!("\"timestamp\":{}", envelope.meta().timestamp()),
+            &format!("\"timestamp\":{timestamp}"),
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn speed_scales_recorded_gaps() {
+        let path =
+            std::env::temp_dir().join(format!("maiko_replay_{}.jsonl", uuid::Uuid::new_v4()));
+        let first = Envelope::new(Tick(1), ActorId::new(Arc::from("clock")));
+        let second = later(&first, Duration::from_millis(100));
+        record(&path, &[first, second]);
+
+        let replayer = Replayer::<Tick>::open(&path).unwrap().with_speed(4.0);
+        let _ = std::fs::remove_file(&path);
+        let sup = Supervisor::<Tick>::default();
+
+        let replay = replayer.replay(&sup);
+        tokio::pin!(replay);
+        assert!(replay.as_mut().now_or_never().is_none());
+        tokio::time::advance(Duration::from_millis(24)).await;
+        assert!(replay.as_mut().now_or_never().is_none());
+        tokio::time::advance(Duration::from_millis(1)).await;
+        assert_eq!(replay.await.unwrap(), 2);
+    }
+}
@@ -1,9 +1,13 @@
-use std::sync::{Arc, atomic::AtomicBool};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    marker::PhantomData,
+    sync::{Arc, OnceLock, atomic::AtomicBool},
+};
 
 use tokio::{
     sync::{
         Mutex, Notify,
-        mpsc::{self, Receiver, Sender, channel},
+        mpsc::{self, Receiver, Sender, UnboundedSender, channel},
     },
     task::JoinSet,
 };
@@ -11,8 +15,12 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{
     Actor, ActorBuilder, ActorConfig, ActorId, Config, Context, DefaultTopic, Envelope, Error,
-    Event, Label, Result, Subscribe, Topic,
-    internal::{ActorController, Broker, Subscriber, Subscription},
+    Event, Interceptor, Label, NarrowActor, OverflowPolicy, Result, Subscribe, Topic,
+    ValidationReport,
+    internal::{
+        ActorController, Bridge, BridgeOutlet, Broker, Registration, RetainedEvents, Routing,
+        SpanLabels, Subscriber, Subscription, dot_text, mermaid_id, mermaid_text,
+    },
 };
 
 #[cfg(feature = "monitoring")]
@@ -50,8 +58,16 @@ pub struct Supervisor<E: Event, T: Topic<E> = DefaultTopic> {
     cancel_token: Arc<CancellationToken>,
     broker_cancel_token: Arc<CancellationToken>,
     start_notifier: Arc<Notify>,
+    started: bool,
+    stopped: bool,
+    registrar: UnboundedSender<Registration<E, T>>,
     supervisor_id: ActorId,
     registrations: Vec<(ActorId, Subscription<T>)>,
+    publications: HashMap<ActorId, Subscription<T>>,
+    actor_configs: HashMap<ActorId, ActorConfig>,
+    retained: RetainedEvents<E, T>,
+    interceptors: Vec<Arc<str>>,
+    span_labels: Arc<OnceLock<SpanLabels<E, T>>>,
 
     #[cfg(feature = "monitoring")]
     monitoring: MonitorRegistry<E, T>,
@@ -80,7 +96,9 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
             #[cfg(feature = "monitoring")]
             monitoring.sink(),
         );
-        broker.add_sender(rx);
+        broker.add_sender(supervisor_id.clone(), rx);
+        let retained = broker.retained_events();
+        let registrar = broker.registrar();
 
         Self {
             broker: Arc::new(Mutex::new(broker)),
@@ -90,8 +108,16 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
             cancel_token,
             broker_cancel_token,
             start_notifier: Arc::new(Notify::new()),
+            started: false,
+            stopped: false,
+            registrar,
             supervisor_id,
             registrations: Vec::new(),
+            publications: HashMap::new(),
+            actor_configs: HashMap::new(),
+            retained,
+            interceptors: Vec::new(),
+            span_labels: Arc::new(OnceLock::new()),
 
             #[cfg(feature = "monitoring")]
             monitoring,
@@ -108,6 +134,12 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
     /// * `factory` - Closure that receives a Context and returns the actor
     /// * `topics` - Slice of topics the actor subscribes to
     ///
+    /// Actors can also be added after [`start()`](Self::start); they start
+    /// right away and receive the events retained for their topics (see
+    /// [`Topic::retention`]). Fails with [`Error::SubscriberAlreadyExists`]
+    /// if the name is taken, and with [`Error::SupervisorStopped`] once
+    /// [`stop()`](Self::stop) has been called.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -127,7 +159,8 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
         let ctx = self.create_context(name, tx);
         let actor = factory(ctx.clone());
         let topics = topics.into().0;
-        self.register_actor(ctx, actor, topics, ActorConfig::new(&self.config), rx)
+        let config = ActorConfig::new(&self.config);
+        self.register_actor(ctx, actor, Routing::new(topics), config, rx)
     }
 
     /// Start building an actor registration with custom configuration.
@@ -157,36 +190,170 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
         ActorBuilder::new(self, actor, ctx, rx)
     }
 
+    /// Register an actor whose event type is a subset of the system event type.
+    ///
+    /// The actor's factory receives a [`Context`] of the narrow event type.
+    /// Incoming events are converted with `TryFrom<&E>` and outgoing events
+    /// with `Into<E>`. Events on the subscribed topics that don't convert
+    /// are not dispatched to the actor at all, so monitors never see them
+    /// delivered or handled. See [`NarrowActor`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// supervisor.add_narrow_actor("logger", |ctx| Logger::new(ctx), &[AppTopic::Log])?;
+    /// ```
+    pub fn add_narrow_actor<A, F, S>(
+        &mut self,
+        name: &str,
+        factory: F,
+        topics: S,
+    ) -> Result<ActorId>
+    where
+        A: Actor,
+        A::Event: Into<E> + for<'e> TryFrom<&'e E>,
+        F: FnOnce(Context<A::Event>) -> A,
+        S: Into<Subscribe<E, T>>,
+    {
+        self.build_narrow_actor(name, factory)
+            .topics(topics)
+            .build()
+    }
+
+    /// Start building a registration for an actor with a narrower event type.
+    ///
+    /// Combines [`build_actor`](Self::build_actor) with the conversions
+    /// described in [`add_narrow_actor`](Self::add_narrow_actor).
+    pub fn build_narrow_actor<'a, A, F>(
+        &'a mut self,
+        name: &str,
+        factory: F,
+    ) -> ActorBuilder<'a, E, T, NarrowActor<A, E>>
+    where
+        A: Actor,
+        A::Event: Into<E> + for<'e> TryFrom<&'e E>,
+        F: FnOnce(Context<A::Event>) -> A,
+    {
+        self.build_actor(name, |ctx| NarrowActor::new(factory(ctx.narrow())))
+            .filter(Arc::new(|event: &E| A::Event::try_from(event).is_ok()))
+    }
+
+    /// Connect this supervisor to another one with a different event type.
+    ///
+    /// The bridge subscribes to `topics` here, maps every received event with
+    /// `map` and publishes the result into `target`. Events for which `map`
+    /// returns `None` are not forwarded. Forwarded envelopes keep their id,
+    /// timestamp and correlation id, so event chains can be followed across
+    /// both systems.
+    ///
+    /// The bridge is registered under `name` in both supervisors: as a
+    /// subscriber here and as a pure producer in `target`, so it shows up
+    /// in monitoring and [`to_mermaid`](Self::to_mermaid) on either side.
+    /// Returns the id of the bridge actor in this supervisor.
+    ///
+    /// Bridges are one-way. To connect both directions add a second bridge
+    /// (under a different name) and make sure the two `map` functions don't
+    /// send the same events back and forth.
+    ///
+    /// Both supervisors must not be started yet, and `name` must be free in
+    /// both; otherwise neither supervisor is changed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// billing.add_bridge("orders-to-shipping", &mut shipping, &[BillingTopic::Paid], |e| match e {
+    ///     BillingEvent::Paid(order) => Some(ShippingEvent::Ship(order.clone())),
+    ///     _ => None,
+    /// })?;
+    /// ```
+    pub fn add_bridge<E2, T2, F, S>(
+        &mut self,
+        name: &str,
+        target: &mut Supervisor<E2, T2>,
+        topics: S,
+        map: F,
+    ) -> Result<ActorId>
+    where
+        E2: Event,
+        T2: Topic<E2>,
+        F: FnMut(&E) -> Option<E2> + Send + 'static,
+        S: Into<Subscribe<E, T>>,
+    {
+        if self.started || target.started {
+            return Err(Error::BrokerAlreadyStarted);
+        }
+        // Checked before the outlet is added, so a failure leaves `target` untouched.
+        let bridge_id = ActorId::new(Arc::from(name));
+        if self.is_registered(&bridge_id) {
+            return Err(Error::SubscriberAlreadyExists(bridge_id));
+        }
+        let mut outlet = None;
+        target
+            .build_actor(name, |ctx| {
+                outlet = Some(ctx);
+                BridgeOutlet(PhantomData)
+            })
+            .publishes(Subscribe::all())
+            .build()?;
+        let outlet = outlet.expect("factory is called by build_actor");
+        self.build_actor(name, |_| Bridge {
+            outlet,
+            map,
+            _event: PhantomData,
+        })
+        .topics(topics)
+        .publishes(Subscribe::none())
+        .build()
+    }
+
     /// Internal method to register an actor with the supervisor.
     ///
     /// Called by `add_actor()` to perform the actual registration. It:
-    /// 1. Creates a Subscriber and registers it with the broker
+    /// 1. Creates a Subscriber and hands it to the broker, which adds it
+    ///    (replaying retained events) once running
     /// 2. Creates an ActorHandler wrapping the actor
-    /// 3. Spawns the actor task (which waits for start notification)
+    /// 3. Spawns the actor task (which waits for start notification, unless
+    ///    the supervisor is already running)
     pub(crate) fn register_actor<A>(
         &mut self,
         ctx: Context<E>,
         actor: A,
-        topics: Subscription<T>,
+        routing: Routing<E, T>,
         config: ActorConfig,
         receiver: Receiver<Arc<Envelope<E>>>,
     ) -> Result<ActorId>
     where
         A: Actor<Event = E>,
     {
+        if self.stopped {
+            return Err(Error::SupervisorStopped);
+        }
         let actor_id = ctx.actor_id().clone();
-
-        let mut broker = self
-            .broker
-            .try_lock()
-            .map_err(|_| Error::BrokerAlreadyStarted)?;
+        if self.is_registered(&actor_id) {
+            return Err(Error::SubscriberAlreadyExists(actor_id));
+        }
 
         let (tx, rx) = mpsc::channel::<Arc<Envelope<E>>>(config.channel_capacity());
 
-        let subscriber = Subscriber::<E, T>::new(actor_id.clone(), topics.clone(), tx);
-        broker.add_subscriber(subscriber)?;
-        broker.add_sender(receiver);
+        let Routing {
+            topics,
+            filter,
+            publications,
+        } = routing;
+        let subscriber =
+            Subscriber::<E, T>::new(actor_id.clone(), topics.clone(), tx).with_filter(filter);
+        self.registrar
+            .send(Registration {
+                subscriber,
+                receiver,
+                publications: publications.clone(),
+            })
+            .map_err(|_| Error::BrokerAlreadyStarted)?;
         self.registrations.push((actor_id.clone(), topics));
+        self.actor_configs.insert(actor_id.clone(), config);
+        if let Some(publications) = publications {
+            self.publications.insert(actor_id.clone(), publications);
+        }
 
         let mut controller = ActorController::<A, T> {
             actor,
@@ -194,22 +361,176 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
             ctx,
             max_events_per_tick: config.max_events_per_tick(),
             cancel_token: self.cancel_token.clone(),
+            span_labels: self.span_labels.clone(),
 
             #[cfg(feature = "monitoring")]
             monitoring: self.monitoring.sink(),
+            #[cfg(feature = "monitoring")]
+            step_state: None,
 
             _topic: std::marker::PhantomData,
         };
 
-        let notified = self.start_notifier.clone().notified_owned();
-        self.tasks.spawn(async move {
-            notified.await;
-            controller.run().await
-        });
+        if self.started {
+            self.tasks.spawn(async move { controller.run().await });
+        } else {
+            let notified = self.start_notifier.clone().notified_owned();
+            self.tasks.spawn(async move {
+                notified.await;
+                controller.run().await
+            });
+        }
 
         Ok(actor_id)
     }
 
+    /// Whether an actor with this id is already registered.
+    fn is_registered(&self, actor_id: &ActorId) -> bool {
+        self.registrations.iter().any(|(id, _)| id == actor_id)
+    }
+
+    /// Register an [`Interceptor`] that sees every event before it is routed.
+    ///
+    /// Interceptors run in the broker loop in registration order and can
+    /// forward, replace, fan out or drop envelopes. Dropped events are
+    /// reported to monitors along with the interceptor `name` and reason.
+    ///
+    /// Must be called before [`start()`](Self::start).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// supervisor.add_interceptor("tenant", |envelope: &Envelope<MyEvent>| {
+    ///     let stamped = envelope.event().clone().with_tenant("acme");
+    ///     InterceptAction::Replace(Envelope::from((&stamped, envelope.meta())))
+    /// })?;
+    /// ```
+    pub fn add_interceptor<I>(&mut self, name: &str, interceptor: I) -> Result<()>
+    where
+        I: Interceptor<E>,
+    {
+        let mut broker = self
+            .broker
+            .try_lock()
+            .map_err(|_| Error::BrokerAlreadyStarted)?;
+        let name = Arc::<str>::from(name);
+        broker.add_interceptor(name.clone(), Box::new(interceptor));
+        self.interceptors.push(name);
+        Ok(())
+    }
+
+    /// Returns the names of registered interceptors, in the order they run.
+    pub fn interceptors(&self) -> Vec<&str> {
+        self.interceptors.iter().map(|name| name.as_ref()).collect()
+    }
+
+    /// Record event and topic labels on the `handle_event` spans of all
+    /// actors, including those already registered or running.
+    ///
+    /// Every handled event gets a span with the actor name and event id,
+    /// nested under the span of the handler that sent it, so log tooling
+    /// shows causal trees across actors. Labels are opt-in because they
+    /// require [`Label`] on both the event and the topic type.
+    pub fn label_event_spans(&mut self)
+    where
+        E: Label,
+        T: Label,
+    {
+        let _ = self
+            .span_labels
+            .set(|event, topic| (event.label(), topic.label()));
+    }
+
+    /// Declare the topics the supervisor itself publishes to with
+    /// [`send`](Self::send).
+    ///
+    /// Like [`ActorBuilder::publishes`] for actors: the declaration feeds
+    /// [`validate`](Self::validate) and the broker checks the supervisor's
+    /// events against it. Must be called before [`start()`](Self::start).
+    pub fn publishes<S>(&mut self, topics: S) -> Result<()>
+    where
+        S: Into<Subscribe<E, T>>,
+    {
+        let mut broker = self
+            .broker
+            .try_lock()
+            .map_err(|_| Error::BrokerAlreadyStarted)?;
+        let topics = topics.into().0;
+        broker.add_publisher(self.supervisor_id.clone(), topics.clone());
+        self.publications.insert(self.supervisor_id.clone(), topics);
+        Ok(())
+    }
+
+    /// Check subscriptions against declared publications.
+    ///
+    /// Reports topics that are published but not subscribed to, topics that
+    /// are subscribed to but not published, and actors that do neither.
+    /// Only publications declared with [`ActorBuilder::publishes`] or
+    /// [`publishes`](Self::publishes) count, so the report is meaningful
+    /// once every producer declares what it sends.
+    ///
+    /// Enable [`Config::with_validation_on_start`] to have
+    /// [`start()`](Self::start) fail on problems.
+    ///
+    /// ```ignore
+    /// let report = sup.validate();
+    /// assert!(report.is_valid(), "{report}");
+    /// ```
+    pub fn validate(&self) -> ValidationReport<T> {
+        let publish_all = self
+            .publications
+            .values()
+            .any(|p| matches!(p, Subscription::All));
+        let subscribe_all = self
+            .registrations
+            .iter()
+            .any(|(_, s)| matches!(s, Subscription::All));
+
+        let mut seen = HashSet::new();
+        let mut unpublished = Vec::new();
+        for (_, subscription) in &self.registrations {
+            if let Subscription::Topics(topics) = subscription {
+                for topic in topics {
+                    if !publish_all
+                        && !self.publications.values().any(|p| p.contains(topic))
+                        && seen.insert(topic)
+                    {
+                        unpublished.push(topic.clone());
+                    }
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut unconsumed = Vec::new();
+        for publications in self.publications.values() {
+            if let Subscription::Topics(topics) = publications {
+                for topic in topics {
+                    if !subscribe_all
+                        && !self.registrations.iter().any(|(_, s)| s.contains(topic))
+                        && seen.insert(topic)
+                    {
+                        unconsumed.push(topic.clone());
+                    }
+                }
+            }
+        }
+
+        let idle = self
+            .registrations
+            .iter()
+            .filter(|(_, subscription)| subscription.is_empty())
+            .filter(|(actor_id, _)| {
+                self.publications
+                    .get(actor_id)
+                    .is_none_or(Subscription::is_empty)
+            })
+            .map(|(actor_id, _)| actor_id.clone())
+            .collect();
+
+        ValidationReport::new(unconsumed, unpublished, idle)
+    }
+
     /// Create a new Context for an actor.
     ///
     /// Internal helper used by `add_actor` to create actor contexts.
@@ -218,19 +539,27 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
         name: &str,
         sender: Sender<Arc<Envelope<E>>>,
     ) -> Context<E> {
-        Context::<E> {
-            actor_id: ActorId::new(Arc::<str>::from(name)),
+        Context::<E>::new(
+            ActorId::new(Arc::<str>::from(name)),
             sender,
-            alive: Arc::new(AtomicBool::new(true)),
-        }
+            Arc::new(AtomicBool::new(true)),
+        )
+        .with_shutdown(self.cancel_token.clone())
     }
 
     /// Start the broker loop in a background task. This returns immediately.
     pub async fn start(&mut self) -> Result<()> {
+        if self.config.validate_on_start() {
+            let report = self.validate();
+            if !report.is_valid() {
+                return Err(Error::InvalidTopology(report.summary().into()));
+            }
+        }
         let broker = self.broker.clone();
         self.tasks
             .spawn(async move { broker.lock().await.run().await });
         self.start_notifier.notify_waiters();
+        self.started = true;
         Ok(())
     }
 
@@ -262,6 +591,15 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
         Ok(())
     }
 
+    /// Emit an envelope into the broker as is, keeping its metadata
+    /// (id, sender and correlation id).
+    ///
+    /// Useful to re-inject events captured elsewhere, e.g. by a recording.
+    pub async fn send_envelope<M: Into<Envelope<E>>>(&self, envelope: M) -> Result<()> {
+        self.sender.send(Arc::new(envelope.into())).await?;
+        Ok(())
+    }
+
     /// Request a graceful shutdown, then await all actor tasks.
     ///
     /// # Shutdown Process
@@ -271,6 +609,7 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
     /// 3. Cancels all actors and waits for tasks t
     pub async fn stop(&mut self) -> Result<()> {
         use tokio::time::*;
+        self.stopped = true;
         let start = Instant::now();
         let timeout = Duration::from_millis(10);
         let max = self.sender.max_capacity();
@@ -303,6 +642,14 @@ impl<E: Event, T: Topic<E>> Supervisor<E, T> {
         self.config.as_ref()
     }
 
+    /// Returns the events currently retained for the topic, oldest first.
+    ///
+    /// Only topics that opt in via [`Topic::retention`] keep events.
+    /// The result is a snapshot and can be taken while the system is running.
+    pub fn retained(&self, topic: &T) -> Vec<Arc<Envelope<E>>> {
+        self.retained.get(topic)
+    }
+
     #[cfg(feature = "monitoring")]
     #[cfg_attr(docsrs, doc(cfg(feature = "monitoring")))]
     pub fn monitors(&mut self) -> &mut MonitorRegistry<E, T> {
@@ -365,12 +712,210 @@ impl<E: Event, T: Topic<E> + Label> Supervisor<E, T> {
         lines.join("\n")
     }
 
-    /// Collect all known topic labels from explicit subscriptions, sorted alphabetically.
-    fn all_topic_labels(&self) -> Vec<String> {
-        use std::collections::BTreeSet;
+    /// Generate a Mermaid flowchart of the declared topology.
+    ///
+    /// Extends [`to_mermaid`](Self::to_mermaid) with declared publishers
+    /// (actor → topic edges), each actor's mailbox capacity and the topic's
+    /// overflow policy on every subscription edge: thick for `Fail`, solid
+    /// for `Block` and dotted for `Drop`. Actors subscribing to or
+    /// publishing [`Subscribe::all()`] get a single edge to a `*` node
+    /// instead of one edge per topic.
+    ///
+    /// # Example output
+    ///
+    /// ```text
+    /// flowchart LR
+    ///     a_sensor["sensor<br/>capacity 128"]
+    ///     a_logger["logger<br/>capacity 128"]
+    ///     t_SensorData(("SensorData"))
+    ///     all_topics(("*"))
+    ///     a_sensor --> t_SensorData
+    ///     t_SensorData -.->|Drop| a_processor
+    ///     all_topics --> a_logger
+    /// ```
+    pub fn to_mermaid_detailed(&self) -> String {
+        let diagram = self.diagram();
+        let node = |end: &DiagramEnd| match end {
+            DiagramEnd::Actor(name) => mermaid_id("a", name),
+            DiagramEnd::Topic(label) => mermaid_id("t", label),
+            DiagramEnd::AllTopics => "all_topics".to_string(),
+        };
+
+        let mut lines = vec!["flowchart LR".to_string()];
+        for (name, capacity) in &diagram.actors {
+            let text = match capacity {
+                Some(capacity) => format!("{}<br/>capacity {capacity}", mermaid_text(name)),
+                None => mermaid_text(name),
+            };
+            lines.push(format!("    {}[\"{text}\"]", mermaid_id("a", name)));
+        }
+        for label in &diagram.topics {
+            lines.push(format!(
+                "    {}((\"{}\"))",
+                mermaid_id("t", label),
+                mermaid_text(label)
+            ));
+        }
+        if diagram.all_topics {
+            lines.push("    all_topics((\"*\"))".to_string());
+        }
+        for (from, to, policy) in &diagram.edges {
+            let arrow = match policy {
+                Some(OverflowPolicy::Fail) => "==>|Fail|",
+                Some(OverflowPolicy::Block) => "-->|Block|",
+                Some(OverflowPolicy::Drop) => "-.->|Drop|",
+                None => "-->",
+            };
+            lines.push(format!("    {} {arrow} {}", node(from), node(to)));
+        }
+        lines.join("\n")
+    }
+
+    /// Generate a Graphviz DOT graph of the declared topology.
+    ///
+    /// Shows the same as [`to_mermaid_detailed`](Self::to_mermaid_detailed):
+    /// actors as boxes with their mailbox capacity, topics as ellipses,
+    /// declared publishers, and subscription edges styled by overflow
+    /// policy (bold `Fail`, solid `Block`, dashed `Drop`).
+    ///
+    /// # Example output
+    ///
+    /// ```text
+    /// digraph supervisor {
+    ///     rankdir=LR;
+    ///     "a:processor" [label="processor\ncapacity 128", shape=box];
+    ///     "t:SensorData" [label="SensorData", shape=ellipse];
+    ///     "a:sensor" -> "t:SensorData";
+    ///     "t:SensorData" -> "a:processor" [label="Drop", style=dashed];
+    /// }
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let diagram = self.diagram();
+        let node = |end: &DiagramEnd| match end {
+            DiagramEnd::Actor(name) => format!("\"a:{}\"", dot_text(name)),
+            DiagramEnd::Topic(label) => format!("\"t:{}\"", dot_text(label)),
+            DiagramEnd::AllTopics => "\"*\"".to_string(),
+        };
 
+        let mut lines = vec![
+            "digraph supervisor {".to_string(),
+            "    rankdir=LR;".to_string(),
+        ];
+        for (name, capacity) in &diagram.actors {
+            let text = match capacity {
+                Some(capacity) => format!("{}\\ncapacity {capacity}", dot_text(name)),
+                None => dot_text(name),
+            };
+            lines.push(format!(
+                "    {} [label=\"{text}\", shape=box];",
+                node(&DiagramEnd::Actor(name.clone()))
+            ));
+        }
+        for label in &diagram.topics {
+            lines.push(format!(
+                "    {} [label=\"{}\", shape=ellipse];",
+                node(&DiagramEnd::Topic(label.clone())),
+                dot_text(label)
+            ));
+        }
+        if diagram.all_topics {
+            lines.push("    \"*\" [label=\"*\", shape=ellipse, style=dashed];".to_string());
+        }
+        for (from, to, policy) in &diagram.edges {
+            let attributes = match policy {
+                Some(OverflowPolicy::Fail) => " [label=\"Fail\", style=bold]",
+                Some(OverflowPolicy::Block) => " [label=\"Block\", style=solid]",
+                Some(OverflowPolicy::Drop) => " [label=\"Drop\", style=dashed]",
+                None => "",
+            };
+            lines.push(format!("    {} -> {}{attributes};", node(from), node(to)));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Nodes and edges of the declared topology, shared by the detailed
+    /// exports. Topics are sorted by label, edges by their ends.
+    fn diagram(&self) -> Diagram {
+        let mut actors = Vec::new();
+        let mut edges = Vec::new();
+        for (actor_id, subscription) in &self.registrations {
+            let name = actor_id.name().to_string();
+            let capacity = self
+                .actor_configs
+                .get(actor_id)
+                .map(|c| c.channel_capacity());
+            actors.push((name.clone(), capacity));
+            for (topic, policy) in Self::topic_ends(subscription) {
+                edges.push((topic, DiagramEnd::Actor(name.clone()), policy));
+            }
+        }
+        if self.publications.contains_key(&self.supervisor_id) {
+            actors.push((self.supervisor_id.name().to_string(), None));
+        }
+        let publishers = self
+            .registrations
+            .iter()
+            .map(|(actor_id, _)| actor_id)
+            .chain([&self.supervisor_id]);
+        for actor_id in publishers {
+            if let Some(publications) = self.publications.get(actor_id) {
+                for (topic, _) in Self::topic_ends(publications) {
+                    edges.push((DiagramEnd::Actor(actor_id.name().to_string()), topic, None));
+                }
+            }
+        }
+        edges.sort();
+
+        let mut topics = BTreeSet::new();
+        let mut all_topics = false;
+        for (from, to, _) in &edges {
+            for end in [from, to] {
+                match end {
+                    DiagramEnd::Topic(label) => {
+                        topics.insert(label.clone());
+                    }
+                    DiagramEnd::AllTopics => all_topics = true,
+                    DiagramEnd::Actor(_) => {}
+                }
+            }
+        }
+
+        Diagram {
+            actors,
+            topics,
+            all_topics,
+            edges,
+        }
+    }
+
+    /// Topic ends of a subscription or declared publication, with the
+    /// topic's overflow policy. `Subscribe::all()` is a single `*` end,
+    /// without a policy as topics may differ.
+    fn topic_ends(subscription: &Subscription<T>) -> Vec<(DiagramEnd, Option<OverflowPolicy>)> {
+        match subscription {
+            Subscription::All => vec![(DiagramEnd::AllTopics, None)],
+            Subscription::Topics(topics) => topics
+                .iter()
+                .map(|t| {
+                    let label = t.label().into_owned();
+                    (DiagramEnd::Topic(label), Some(t.overflow_policy()))
+                })
+                .collect(),
+            Subscription::None => Vec::new(),
+        }
+    }
+
+    /// Collect all known topic labels from explicit subscriptions and
+    /// declared publications, sorted alphabetically.
+    fn all_topic_labels(&self) -> Vec<String> {
         let mut labels = BTreeSet::new();
-        for (_, subscription) in &self.registrations {
+        let declared = self
+            .registrations
+            .iter()
+            .map(|(_, subscription)| subscription)
+            .chain(self.publications.values());
+        for subscription in declared {
             if let Subscription::Topics(topics) = subscription {
                 for topic in topics {
                     labels.insert(topic.label().into_owned());
@@ -381,6 +926,25 @@ impl<E: Event, T: Topic<E> + Label> Supervisor<E, T> {
     }
 }
 
+/// The declared topology, as drawn by the detailed diagram exports.
+struct Diagram {
+    /// Actor names with their mailbox capacity, in registration order.
+    actors: Vec<(String, Option<usize>)>,
+    /// Topic labels.
+    topics: BTreeSet<String>,
+    /// Whether any actor subscribes to or publishes all topics.
+    all_topics: bool,
+    /// Edges with the overflow policy of subscription edges.
+    edges: Vec<(DiagramEnd, DiagramEnd, Option<OverflowPolicy>)>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum DiagramEnd {
+    Actor(String),
+    Topic(String),
+    AllTopics,
+}
+
 impl<E: Event, T: Topic<E>> Drop for Supervisor<E, T> {
     fn drop(&mut self) {
         if !self.cancel_token.is_cancelled() {
@@ -411,7 +975,8 @@ impl<E: Event, T: Topic<E> + Label> Supervisor<E, T> {
     /// # Semantics
     ///
     /// - Actors registered with [`Subscribe::all()`] are expanded to include
-    ///   all known topics discovered from explicit subscriptions.
+    ///   all known topics discovered from explicit subscriptions and
+    ///   declared publications.
     /// - Actors registered with [`Subscribe::none()`] produce an empty list.
     /// - Topic names are obtained via [`Label::label()`].
     ///
@@ -487,6 +1052,13 @@ mod tests {
                 TestEvent::Alert(_) => TestTopic::Alerts,
             }
         }
+
+        fn retention(&self) -> usize {
+            match self {
+                TestTopic::SensorData => 1,
+                TestTopic::Alerts => 0,
+            }
+        }
     }
 
     impl Label for TestTopic {
@@ -542,6 +1114,329 @@ mod tests {
         assert_eq!(monitor_lines.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_to_mermaid_includes_published_topics() {
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.build_actor("sensor", |_| DummyActor)
+            .publishes(&[TestTopic::Alerts])
+            .build()
+            .unwrap();
+        sup.add_actor("monitor", |_| DummyActor, Subscribe::all())
+            .unwrap();
+
+        assert!(sup.to_mermaid().contains("Alerts((Alerts)) --> monitor"));
+    }
+
+    #[tokio::test]
+    async fn test_detailed_exports() {
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.build_actor("sensor", |_| DummyActor)
+            .publishes(&[TestTopic::SensorData])
+            .build()
+            .unwrap();
+        sup.build_actor("processor", |_| DummyActor)
+            .topics(&[TestTopic::SensorData, TestTopic::Alerts])
+            .channel_capacity(512)
+            .build()
+            .unwrap();
+        sup.add_actor("monitor", |_| DummyActor, Subscribe::all())
+            .unwrap();
+
+        let mermaid = sup.to_mermaid_detailed();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("a_processor[\"processor<br/>capacity 512\"]"));
+        assert!(mermaid.contains("a_sensor --> t_SensorData"));
+        assert!(mermaid.contains("t_SensorData ==>|Fail| a_processor"));
+        assert!(mermaid.contains("all_topics --> a_monitor"));
+        assert!(!mermaid.contains("t_Alerts ==>|Fail| a_monitor"));
+
+        let dot = sup.to_dot();
+        assert!(dot.starts_with("digraph supervisor {\n"));
+        assert!(dot.contains("\"a:processor\" [label=\"processor\\ncapacity 512\", shape=box];"));
+        assert!(dot.contains("\"a:sensor\" -> \"t:SensorData\";"));
+        assert!(dot.contains("\"t:Alerts\" -> \"a:processor\" [label=\"Fail\", style=bold];"));
+        assert!(dot.contains("\"*\" -> \"a:monitor\";"));
+        assert!(dot.ends_with('}'));
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_topology_problems() {
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.build_actor("sensor", |_| DummyActor)
+            .publishes(&[TestTopic::SensorData, TestTopic::Alerts])
+            .build()
+            .unwrap();
+        sup.add_actor("processor", |_| DummyActor, &[TestTopic::SensorData])
+            .unwrap();
+        sup.add_actor("idle", |_| DummyActor, Subscribe::none())
+            .unwrap();
+
+        let report = sup.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.unconsumed_topics(), [TestTopic::Alerts]);
+        assert!(report.unpublished_topics().is_empty());
+        assert_eq!(report.idle_actors().len(), 1);
+        assert_eq!(report.idle_actors()[0].name(), "idle");
+        assert_eq!(
+            report.to_string(),
+            "published but not subscribed: Alerts\nneither subscribing nor publishing: idle"
+        );
+
+        sup.add_actor("logger", |_| DummyActor, Subscribe::all())
+            .unwrap();
+        sup.add_actor("alerter", |_| DummyActor, &[TestTopic::Alerts])
+            .unwrap();
+        let report = sup.validate();
+        assert!(report.unconsumed_topics().is_empty());
+        assert!(report.unpublished_topics().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_start_fails_on_invalid_topology_when_enabled() {
+        let config = Config::default().with_validation_on_start(true);
+        let mut sup = Supervisor::<TestEvent, TestTopic>::new(config);
+        sup.add_actor("processor", |_| DummyActor, &[TestTopic::SensorData])
+            .unwrap();
+
+        let err = sup.start().await.unwrap_err();
+        assert!(matches!(err, Error::InvalidTopology(_)));
+
+        sup.publishes(&[TestTopic::SensorData]).unwrap();
+        assert!(sup.validate().is_valid());
+        sup.start().await.unwrap();
+        sup.stop().await.unwrap();
+    }
+
+    #[cfg(feature = "monitoring")]
+    #[tokio::test(start_paused = true)]
+    async fn test_undeclared_topic_drops_are_reported() {
+        use std::sync::Mutex;
+
+        use crate::{UndeclaredTopicPolicy, monitoring::Monitor};
+
+        #[derive(Clone, Default)]
+        struct Drops(Arc<Mutex<Vec<(String, TestTopic)>>>);
+        impl Monitor<TestEvent, TestTopic> for Drops {
+            fn on_event_dropped(&self, _: &Envelope<TestEvent>, interceptor: &str, _: &str) {
+                panic!("unexpected drop by interceptor {interceptor}");
+            }
+
+            fn on_event_rejected(&self, envelope: &Envelope<TestEvent>, topic: &TestTopic) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((envelope.meta().actor_name().to_string(), topic.clone()));
+            }
+        }
+
+        let config = Config::default().with_undeclared_topic_policy(UndeclaredTopicPolicy::Error);
+        let mut sup = Supervisor::<TestEvent, TestTopic>::new(config);
+        let drops = Drops::default();
+        sup.monitors().add(drops.clone()).await;
+        sup.publishes(&[TestTopic::SensorData]).unwrap();
+        sup.add_actor("processor", |_| DummyActor, Subscribe::all())
+            .unwrap();
+        sup.start().await.unwrap();
+
+        sup.send(TestEvent::Sensor(1.0)).await.unwrap();
+        sup.send(TestEvent::Alert("hot".into())).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        sup.stop().await.unwrap();
+
+        assert_eq!(
+            *drops.0.lock().unwrap(),
+            [(sup.supervisor_id.name().to_string(), TestTopic::Alerts)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retained_keeps_last_event_per_topic() {
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.add_actor("processor", |_| DummyActor, &[TestTopic::SensorData])
+            .unwrap();
+        sup.start().await.unwrap();
+
+        sup.send(TestEvent::Sensor(1.0)).await.unwrap();
+        sup.send(TestEvent::Sensor(2.0)).await.unwrap();
+        sup.send(TestEvent::Alert("hot".into())).await.unwrap();
+        sup.stop().await.unwrap();
+
+        let retained = sup.retained(&TestTopic::SensorData);
+        assert_eq!(retained.len(), 1);
+        assert!(matches!(retained[0].event(), TestEvent::Sensor(v) if *v == 2.0));
+        assert!(sup.retained(&TestTopic::Alerts).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_late_subscriber_receives_retained_events() {
+        struct Collector(mpsc::UnboundedSender<TestEvent>);
+        impl Actor for Collector {
+            type Event = TestEvent;
+
+            async fn handle_event(&mut self, envelope: &Envelope<TestEvent>) -> Result<()> {
+                let _ = self.0.send(envelope.event().clone());
+                Ok(())
+            }
+        }
+
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.start().await.unwrap();
+        sup.send(TestEvent::Sensor(1.0)).await.unwrap();
+        sup.send(TestEvent::Sensor(2.0)).await.unwrap();
+        while sup.retained(&TestTopic::SensorData).is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        sup.add_actor("late", |_| Collector(tx), &[TestTopic::SensorData])
+            .unwrap();
+        let replayed = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap();
+        assert!(matches!(replayed, Some(TestEvent::Sensor(v)) if v == 2.0));
+
+        sup.send(TestEvent::Sensor(3.0)).await.unwrap();
+        let live = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap();
+        assert!(matches!(live, Some(TestEvent::Sensor(v)) if v == 3.0));
+        assert!(matches!(
+            sup.add_actor("late", |_| DummyActor, &[TestTopic::Alerts]),
+            Err(Error::SubscriberAlreadyExists(_))
+        ));
+        sup.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_actor_after_stop_is_rejected() {
+        let mut sup = Supervisor::<TestEvent, TestTopic>::default();
+        sup.start().await.unwrap();
+        sup.stop().await.unwrap();
+        assert!(matches!(
+            sup.add_actor("late", |_| DummyActor, &[TestTopic::Alerts]),
+            Err(Error::SupervisorStopped)
+        ));
+        assert!(sup.tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_forwards_mapped_events() {
+        #[derive(Debug, Clone)]
+        struct Reading(f64);
+        impl Event for Reading {}
+
+        struct Collector(mpsc::UnboundedSender<Envelope<Reading>>);
+        impl Actor for Collector {
+            type Event = Reading;
+            async fn handle_event(&mut self, envelope: &Envelope<Reading>) -> Result<()> {
+                let _ = self.0.send(envelope.clone());
+                Ok(())
+            }
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut source = Supervisor::<TestEvent, TestTopic>::default();
+        let mut target = Supervisor::<Reading>::default();
+        // Named like the source sender: a name collision must not hide
+        // bridged events from it.
+        target
+            .add_actor("sensor", |_| Collector(tx), &[DefaultTopic])
+            .unwrap();
+        source
+            .add_bridge(
+                "bridge",
+                &mut target,
+                &[TestTopic::SensorData, TestTopic::Alerts],
+                |e| match e {
+                    TestEvent::Sensor(v) => Some(Reading(*v)),
+                    TestEvent::Alert(_) => None,
+                },
+            )
+            .unwrap();
+
+        assert!(source.to_mermaid().contains("--> bridge"));
+        assert!(
+            target
+                .registrations
+                .iter()
+                .any(|(id, sub)| id.name() == "bridge" && matches!(sub, Subscription::None))
+        );
+
+        source.start().await.unwrap();
+        target.start().await.unwrap();
+
+        let sensor = Envelope::with_correlation(
+            TestEvent::Sensor(21.5),
+            ActorId::new(Arc::from("sensor")),
+            42,
+        );
+        let sensor_id = sensor.id();
+        source
+            .send(TestEvent::Alert("ignored".into()))
+            .await
+            .unwrap();
+        source.sender.send(Arc::new(sensor)).await.unwrap();
+
+        let received = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .expect("bridged event not received")
+            .unwrap();
+        source.stop().await.unwrap();
+        target.stop().await.unwrap();
+
+        assert_eq!(received.event().0, 21.5);
+        assert_eq!(received.id(), sensor_id);
+        assert_eq!(received.meta().correlation_id(), Some(42));
+        assert_eq!(received.meta().actor_name(), "bridge");
+        assert_eq!(received.meta().origin().map(ActorId::name), Some("sensor"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_with_taken_name_changes_neither_supervisor() {
+        let mut source = Supervisor::<TestEvent, TestTopic>::default();
+        let mut target = Supervisor::<TestEvent>::default();
+        source
+            .add_actor("bridge", |_| DummyActor, Subscribe::none())
+            .unwrap();
+
+        let result =
+            source.add_bridge("bridge", &mut target, Subscribe::all(), |e| Some(e.clone()));
+        assert!(matches!(result, Err(Error::SubscriberAlreadyExists(_))));
+        assert!(target.registrations.is_empty());
+        assert_eq!(source.registrations.len(), 1);
+
+        let mut target = Supervisor::<TestEvent>::default();
+        target
+            .add_actor("bridge", |_| DummyActor, Subscribe::none())
+            .unwrap();
+        let mut source = Supervisor::<TestEvent, TestTopic>::default();
+        let result =
+            source.add_bridge("bridge", &mut target, Subscribe::all(), |e| Some(e.clone()));
+        assert!(matches!(result, Err(Error::SubscriberAlreadyExists(_))));
+        assert!(source.registrations.is_empty());
+        assert_eq!(target.registrations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_to_started_target_is_rejected() {
+        let mut source = Supervisor::<TestEvent, TestTopic>::default();
+        let mut target = Supervisor::<TestEvent>::default();
+        target.start().await.unwrap();
+
+        let result =
+            source.add_bridge("bridge", &mut target, Subscribe::all(), |e| Some(e.clone()));
+        assert!(matches!(result, Err(Error::BrokerAlreadyStarted)));
+        assert!(source.registrations.is_empty());
+        assert!(target.registrations.is_empty());
+
+        let result =
+            target.add_bridge("bridge", &mut source, Subscribe::all(), |e| Some(e.clone()));
+        assert!(matches!(result, Err(Error::BrokerAlreadyStarted)));
+        assert!(source.registrations.is_empty());
+        target.stop().await.unwrap();
+    }
+
     #[cfg(feature = "serde")]
     #[tokio::test]
     async fn test_to_json_basic() {
@@ -1,26 +1,64 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{ActorId, Envelope, Event, Topic, monitoring::Monitor, testing::EventEntry};
+use crate::{
+    ActorId, Envelope, Event, StepAction, Topic,
+    monitoring::Monitor,
+    testing::{EventEntry, StepEntry},
+};
 
 #[derive(Debug)]
 pub struct EventCollector<E: Event, T: Topic<E>> {
     events: UnboundedSender<EventEntry<E, T>>,
+    steps: Arc<Mutex<Vec<StepEntry>>>,
+    lagged: Arc<AtomicU64>,
 }
 
 impl<E: Event, T: Topic<E>> EventCollector<E, T> {
-    pub fn new(events: UnboundedSender<EventEntry<E, T>>) -> Self {
-        Self { events }
+    pub fn new(
+        events: UnboundedSender<EventEntry<E, T>>,
+        steps: Arc<Mutex<Vec<StepEntry>>>,
+        lagged: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            events,
+            steps,
+            lagged,
+        }
+    }
+
+    fn push_step(&self, step: StepEntry) {
+        if let Ok(mut steps) = self.steps.lock() {
+            steps.push(step);
+        }
     }
 }
 
 impl<E: Event, T: Topic<E>> Monitor<E, T> for EventCollector<E, T> {
     fn on_event_handled(&self, envelope: &Envelope<E>, topic: &T, receiver: &ActorId) {
-        let event = Arc::new(envelope.clone());
+        let event = Arc::new(envelope.without_span());
         let topic = Arc::new(topic.clone());
         let actor_id = receiver.clone();
         let entry = EventEntry::new(event, topic, actor_id);
         let _ = self.events.send(entry);
     }
+
+    fn on_step_exit_timed(&self, step_action: &StepAction, duration: Duration, actor_id: &ActorId) {
+        self.push_step(StepEntry::new(actor_id.clone(), Ok(*step_action), duration));
+    }
+
+    fn on_step_error(&self, err: &str, duration: Duration, actor_id: &ActorId) {
+        self.push_step(StepEntry::new(actor_id.clone(), Err(err.into()), duration));
+    }
+
+    fn on_lagged(&self, count: u64) {
+        self.lagged.fetch_add(count, Ordering::Relaxed);
+    }
 }
@@ -0,0 +1,434 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::{FlowSnapshot, FlowTree};
+
+/// How a part of the flow differs between two [`FlowSnapshot`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// Only in the new snapshot.
+    Added,
+    /// Only in the expected snapshot.
+    Missing,
+    /// The same events, in a different order.
+    Reordered,
+    /// Present in both, with different content.
+    Changed,
+}
+
+/// Structural difference between an expected and an actual [`FlowSnapshot`].
+///
+/// Created with [`FlowSnapshot::diff`]. Flows are compared as a multiset of
+/// correlation trees, so independent flows may interleave differently
+/// without being reported. The `Display` form is a readable, line-based
+/// report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlowDiff {
+    flows: Vec<FlowChange>,
+    actors: Vec<ActorChange>,
+    topics: Vec<TopicChange>,
+}
+
+/// A correlation tree that is missing, added or reordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowChange {
+    kind: ChangeKind,
+    before: Option<FlowTree>,
+    after: Option<FlowTree>,
+    count: usize,
+}
+
+/// A difference in the sequence of labels an actor received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActorChange {
+    kind: ChangeKind,
+    actor: String,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// A difference in the number of deliveries on a topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicChange {
+    topic: String,
+    before: usize,
+    after: usize,
+}
+
+impl FlowDiff {
+    pub(crate) fn new(before: &FlowSnapshot, after: &FlowSnapshot) -> Self {
+        Self {
+            flows: diff_flows(before.flows(), after.flows()),
+            actors: diff_actors(before.actors(), after.actors()),
+            topics: diff_topics(before.topic_counts(), after.topic_counts()),
+        }
+    }
+
+    /// Returns true if both snapshots have the same structure.
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty() && self.actors.is_empty() && self.topics.is_empty()
+    }
+
+    pub fn flows(&self) -> &[FlowChange] {
+        &self.flows
+    }
+
+    pub fn actors(&self) -> &[ActorChange] {
+        &self.actors
+    }
+
+    pub fn topics(&self) -> &[TopicChange] {
+        &self.topics
+    }
+}
+
+fn diff_flows(before: &[FlowTree], after: &[FlowTree]) -> Vec<FlowChange> {
+    let mut counts: BTreeMap<&FlowTree, isize> = BTreeMap::new();
+    for tree in before {
+        *counts.entry(tree).or_default() -= 1;
+    }
+    for tree in after {
+        *counts.entry(tree).or_default() += 1;
+    }
+
+    let mut missing: Vec<(&FlowTree, usize)> = Vec::new();
+    let mut added: Vec<(&FlowTree, usize)> = Vec::new();
+    for (tree, count) in counts {
+        if count < 0 {
+            missing.push((tree, count.unsigned_abs()));
+        } else if count > 0 {
+            added.push((tree, count as usize));
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (before, missing_count) in &mut missing {
+        let normalized = before.normalized();
+        for (after, added_count) in &mut added {
+            if *missing_count == 0 {
+                break;
+            }
+            if *added_count == 0 || after.normalized() != normalized {
+                continue;
+            }
+            let count = (*missing_count).min(*added_count);
+            *missing_count -= count;
+            *added_count -= count;
+            changes.push(FlowChange {
+                kind: ChangeKind::Reordered,
+                before: Some((*before).clone()),
+                after: Some((*after).clone()),
+                count,
+            });
+        }
+    }
+    for (tree, count) in missing.into_iter().filter(|(_, count)| *count > 0) {
+        changes.push(FlowChange {
+            kind: ChangeKind::Missing,
+            before: Some(tree.clone()),
+            after: None,
+            count,
+        });
+    }
+    for (tree, count) in added.into_iter().filter(|(_, count)| *count > 0) {
+        changes.push(FlowChange {
+            kind: ChangeKind::Added,
+            before: None,
+            after: Some(tree.clone()),
+            count,
+        });
+    }
+    changes
+}
+
+fn diff_actors(
+    before: &BTreeMap<String, Vec<String>>,
+    after: &BTreeMap<String, Vec<String>>,
+) -> Vec<ActorChange> {
+    let empty = Vec::new();
+    let mut actors: Vec<&String> = before.keys().chain(after.keys()).collect();
+    actors.sort();
+    actors.dedup();
+
+    actors
+        .into_iter()
+        .filter_map(|actor| {
+            let old = before.get(actor).unwrap_or(&empty);
+            let new = after.get(actor).unwrap_or(&empty);
+            let kind = if old == new {
+                return None;
+            } else if old.is_empty() {
+                ChangeKind::Added
+            } else if new.is_empty() {
+                ChangeKind::Missing
+            } else if sorted(old) == sorted(new) {
+                ChangeKind::Reordered
+            } else {
+                ChangeKind::Changed
+            };
+            Some(ActorChange {
+                kind,
+                actor: actor.clone(),
+                before: old.clone(),
+                after: new.clone(),
+            })
+        })
+        .collect()
+}
+
+fn sorted(labels: &[String]) -> Vec<&String> {
+    let mut labels: Vec<_> = labels.iter().collect();
+    labels.sort();
+    labels
+}
+
+fn diff_topics(
+    before: &BTreeMap<String, usize>,
+    after: &BTreeMap<String, usize>,
+) -> Vec<TopicChange> {
+    let mut topics: Vec<&String> = before.keys().chain(after.keys()).collect();
+    topics.sort();
+    topics.dedup();
+
+    topics
+        .into_iter()
+        .map(|topic| TopicChange {
+            topic: topic.clone(),
+            before: before.get(topic).copied().unwrap_or(0),
+            after: after.get(topic).copied().unwrap_or(0),
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
+}
+
+impl FlowChange {
+    /// `Missing`, `Added` or `Reordered`.
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// The tree in the expected snapshot, if any.
+    pub fn before(&self) -> Option<&FlowTree> {
+        self.before.as_ref()
+    }
+
+    /// The tree in the actual snapshot, if any.
+    pub fn after(&self) -> Option<&FlowTree> {
+        self.after.as_ref()
+    }
+
+    /// How many occurrences of the tree differ.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl ActorChange {
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Labels received in the expected snapshot.
+    pub fn before(&self) -> &[String] {
+        &self.before
+    }
+
+    /// Labels received in the actual snapshot.
+    pub fn after(&self) -> &[String] {
+        &self.after
+    }
+
+    /// Index of the first label that differs.
+    pub fn first_difference(&self) -> usize {
+        self.before
+            .iter()
+            .zip(&self.after)
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| self.before.len().min(self.after.len()))
+    }
+}
+
+impl TopicChange {
+    pub fn kind(&self) -> ChangeKind {
+        match (self.before, self.after) {
+            (0, _) => ChangeKind::Added,
+            (_, 0) => ChangeKind::Missing,
+            _ => ChangeKind::Changed,
+        }
+    }
+
+    /// Topic label.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Deliveries in the expected snapshot.
+    pub fn before(&self) -> usize {
+        self.before
+    }
+
+    /// Deliveries in the actual snapshot.
+    pub fn after(&self) -> usize {
+        self.after
+    }
+}
+
+impl fmt::Display for FlowDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "(no differences)");
+        }
+        if !self.flows.is_empty() {
+            writeln!(f, "flows:")?;
+            for change in &self.flows {
+                let times = match change.count {
+                    1 => String::new(),
+                    n => format!(" (x{n})"),
+                };
+                match (&change.before, &change.after) {
+                    (Some(before), Some(after)) => {
+                        writeln!(f, "  ~ {before}{times}")?;
+                        writeln!(f, "    reordered: {after}")?;
+                    }
+                    (Some(before), None) => writeln!(f, "  - {before}{times}")?,
+                    (None, Some(after)) => writeln!(f, "  + {after}{times}")?,
+                    (None, None) => {}
+                }
+            }
+        }
+        if !self.actors.is_empty() {
+            writeln!(f, "actors:")?;
+            for change in &self.actors {
+                let at = change.first_difference();
+                match change.kind {
+                    ChangeKind::Added => writeln!(
+                        f,
+                        "  + {}: received {} events",
+                        change.actor,
+                        change.after.len()
+                    )?,
+                    ChangeKind::Missing => writeln!(
+                        f,
+                        "  - {}: received no events, expected {}",
+                        change.actor,
+                        change.before.len()
+                    )?,
+                    ChangeKind::Reordered => {
+                        writeln!(f, "  ~ {}: same events, reordered from #{at}", change.actor)?
+                    }
+                    ChangeKind::Changed => writeln!(
+                        f,
+                        "  ~ {}: differs at #{at}: expected {}, got {} ({} -> {} events)",
+                        change.actor,
+                        change.before.get(at).map_or("nothing", String::as_str),
+                        change.after.get(at).map_or("nothing", String::as_str),
+                        change.before.len(),
+                        change.after.len()
+                    )?,
+                }
+            }
+        }
+        if !self.topics.is_empty() {
+            writeln!(f, "topics:")?;
+            for change in &self.topics {
+                let marker = match change.kind() {
+                    ChangeKind::Added => '+',
+                    ChangeKind::Missing => '-',
+                    _ => '~',
+                };
+                writeln!(
+                    f,
+                    "  {marker} {}: {} -> {} deliveries",
+                    change.topic, change.before, change.after
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic, Envelope, Event, Label, testing::EventEntry};
+
+    #[derive(Clone, Debug)]
+    enum Flow {
+        Order,
+        Fill,
+        Report,
+    }
+    impl Event for Flow {}
+    impl Label for Flow {
+        fn label(&self) -> std::borrow::Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    fn actor(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    /// An order, answered by a fill and a report, in the given order.
+    fn run(children: &[Flow]) -> FlowSnapshot {
+        let order = Arc::new(Envelope::new(Flow::Order, actor("trader")));
+        let mut records = vec![EventEntry::new(
+            order.clone(),
+            Arc::new(DefaultTopic),
+            actor("exchange"),
+        )];
+        for child in children {
+            let envelope = Arc::new(Envelope::with_correlation(
+                child.clone(),
+                actor("exchange"),
+                order.id(),
+            ));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            records.push(EventEntry::new(
+                envelope,
+                Arc::new(DefaultTopic),
+                actor("trader"),
+            ));
+        }
+        FlowSnapshot::new(&records)
+    }
+
+    #[test]
+    fn identical_runs_have_no_differences() {
+        let diff = run(&[Flow::Fill, Flow::Report]).diff(&run(&[Flow::Fill, Flow::Report]));
+        assert!(diff.is_empty(), "{diff}");
+        assert_eq!(diff.to_string(), "(no differences)\n");
+    }
+
+    #[test]
+    fn reordered_flows_are_reported() {
+        let diff = run(&[Flow::Fill, Flow::Report]).diff(&run(&[Flow::Report, Flow::Fill]));
+        assert_eq!(diff.flows().len(), 1);
+        assert_eq!(diff.flows()[0].kind(), ChangeKind::Reordered);
+        assert_eq!(diff.actors()[0].kind(), ChangeKind::Reordered);
+        assert_eq!(diff.actors()[0].first_difference(), 0);
+        assert!(diff.topics().is_empty());
+    }
+
+    #[test]
+    fn missing_and_added_flows_are_reported() {
+        let diff = run(&[Flow::Fill, Flow::Report]).diff(&run(&[Flow::Fill]));
+        assert_eq!(diff.flows().len(), 2);
+        assert_eq!(diff.flows()[0].kind(), ChangeKind::Missing);
+        assert_eq!(diff.flows()[1].kind(), ChangeKind::Added);
+        assert_eq!(diff.actors()[0].kind(), ChangeKind::Changed);
+        assert_eq!(diff.actors()[0].first_difference(), 1);
+        assert_eq!(diff.topics()[0].before(), 3);
+        assert_eq!(diff.topics()[0].after(), 2);
+
+        let report = diff.to_string();
+        assert!(report.contains("  - Order [trader -> exchange] { Fill"));
+        assert!(report.contains("  ~ trader: differs at #1: expected Report, got nothing"));
+        assert!(report.contains("  ~ default: 3 -> 2 deliveries"));
+    }
+}
@@ -0,0 +1,272 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+use crate::{Event, EventId, Label, Topic};
+
+use super::{EventEntry, FlowDiff};
+
+/// The structure of recorded event flow, free of event ids and timestamps.
+///
+/// A snapshot captures:
+/// - the correlation tree of every root event, as [`FlowTree`]s
+/// - the sequence of event labels each actor received
+/// - the number of deliveries per topic
+///
+/// Two runs of the same system produce equal snapshots even though all their
+/// ids differ, so snapshots can be [diffed](Self::diff) to check that a
+/// refactoring kept the event flow unchanged. With the `serde` feature
+/// snapshots can be stored and used as a baseline.
+///
+/// # Example
+///
+/// ```ignore
+/// let before = baseline.flow_snapshot();
+/// let after = test.flow_snapshot();
+/// let diff = before.diff(&after);
+/// assert!(diff.is_empty(), "{diff}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowSnapshot {
+    flows: Vec<FlowTree>,
+    actors: BTreeMap<String, Vec<String>>,
+    topics: BTreeMap<String, usize>,
+}
+
+/// A correlation tree: an event, who sent it to whom, and the events
+/// correlated to it, in order of creation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowTree {
+    label: String,
+    sender: String,
+    receivers: Vec<String>,
+    children: Vec<FlowTree>,
+}
+
+impl FlowSnapshot {
+    pub(crate) fn new<E, T>(records: &[EventEntry<E, T>]) -> Self
+    where
+        E: Event + Label,
+        T: Topic<E> + Label,
+    {
+        let mut events: HashMap<EventId, FlowEvent<'_, E, T>> = HashMap::new();
+        let mut order = Vec::new();
+        let mut actors: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut topics: BTreeMap<String, usize> = BTreeMap::new();
+
+        for entry in records {
+            let event = events.entry(entry.id()).or_insert_with(|| {
+                order.push(entry.id());
+                FlowEvent {
+                    entry,
+                    receivers: BTreeSet::new(),
+                }
+            });
+            event.receivers.insert(entry.receiver().name());
+            actors
+                .entry(entry.receiver().name().to_string())
+                .or_default()
+                .push(entry.payload().label().into_owned());
+            *topics
+                .entry(entry.topic().label().into_owned())
+                .or_default() += 1;
+        }
+
+        let mut children: HashMap<EventId, Vec<EventId>> = HashMap::new();
+        let mut roots = Vec::new();
+        for id in &order {
+            match events[id]
+                .entry
+                .meta()
+                .correlation_id()
+                .filter(|parent| events.contains_key(parent) && parent != id)
+            {
+                Some(parent) => children.entry(parent).or_default().push(*id),
+                None => roots.push(*id),
+            }
+        }
+        let timestamp = |id: &EventId| events[id].entry.meta().timestamp();
+        roots.sort_by_key(timestamp);
+        for ids in children.values_mut() {
+            ids.sort_by_key(timestamp);
+        }
+
+        Self {
+            flows: roots
+                .into_iter()
+                .map(|id| FlowTree::build(id, &events, &children))
+                .collect(),
+            actors,
+            topics,
+        }
+    }
+
+    /// Correlation trees of all root events, in order of creation.
+    pub fn flows(&self) -> &[FlowTree] {
+        &self.flows
+    }
+
+    /// Labels of the events an actor received, in order.
+    pub fn received_by(&self, actor: &str) -> &[String] {
+        self.actors
+            .get(actor)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Number of deliveries per topic label.
+    pub fn topic_counts(&self) -> &BTreeMap<String, usize> {
+        &self.topics
+    }
+
+    /// Compare with another snapshot, taking `self` as the expected flow.
+    pub fn diff(&self, other: &FlowSnapshot) -> FlowDiff {
+        FlowDiff::new(self, other)
+    }
+
+    pub(super) fn actors(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.actors
+    }
+}
+
+struct FlowEvent<'a, E: Event, T: Topic<E>> {
+    entry: &'a EventEntry<E, T>,
+    receivers: BTreeSet<&'a str>,
+}
+
+impl FlowTree {
+    fn build<E: Event + Label, T: Topic<E>>(
+        id: EventId,
+        events: &HashMap<EventId, FlowEvent<'_, E, T>>,
+        children: &HashMap<EventId, Vec<EventId>>,
+    ) -> Self {
+        let event = &events[&id];
+        Self {
+            label: event.entry.payload().label().into_owned(),
+            sender: event.entry.sender().to_string(),
+            receivers: event.receivers.iter().map(|r| r.to_string()).collect(),
+            children: children
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .map(|child| Self::build(*child, events, children))
+                .collect(),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn sender(&self) -> &str {
+        &self.sender
+    }
+
+    /// Actors the event was delivered to, sorted by name.
+    pub fn receivers(&self) -> &[String] {
+        &self.receivers
+    }
+
+    /// Correlated events, in order of creation.
+    pub fn children(&self) -> &[FlowTree] {
+        &self.children
+    }
+
+    /// The same tree with children sorted, so trees differing only in the
+    /// order of correlated events compare equal.
+    pub fn normalized(&self) -> FlowTree {
+        let mut children: Vec<_> = self.children.iter().map(FlowTree::normalized).collect();
+        children.sort();
+        FlowTree {
+            children,
+            ..self.clone()
+        }
+    }
+}
+
+/// Single line form: `Order [trader -> audit, exchange] { Fill [exchange -> trader] }`.
+impl fmt::Display for FlowTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{} -> {}]",
+            self.label,
+            self.sender,
+            self.receivers.join(", ")
+        )?;
+        if !self.children.is_empty() {
+            f.write_str(" { ")?;
+            for (i, child) in self.children.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{child}")?;
+            }
+            f.write_str(" }")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{ActorId, DefaultTopic, Envelope};
+
+    #[derive(Clone, Debug)]
+    enum Flow {
+        Order,
+        Fill,
+        Report,
+    }
+    impl Event for Flow {}
+    impl Label for Flow {
+        fn label(&self) -> std::borrow::Cow<'static, str> {
+            format!("{self:?}").into()
+        }
+    }
+
+    fn actor(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    fn entry(envelope: &Arc<Envelope<Flow>>, receiver: &str) -> EventEntry<Flow, DefaultTopic> {
+        EventEntry::new(envelope.clone(), Arc::new(DefaultTopic), actor(receiver))
+    }
+
+    #[test]
+    fn snapshot_builds_correlation_trees() {
+        let order = Arc::new(Envelope::new(Flow::Order, actor("trader")));
+        let fill = Arc::new(Envelope::with_correlation(
+            Flow::Fill,
+            actor("exchange"),
+            order.id(),
+        ));
+        let report = Arc::new(Envelope::with_correlation(
+            Flow::Report,
+            actor("audit"),
+            order.id(),
+        ));
+        let records = vec![
+            entry(&order, "exchange"),
+            entry(&order, "audit"),
+            entry(&fill, "trader"),
+            entry(&report, "trader"),
+        ];
+
+        let snapshot = FlowSnapshot::new(&records);
+        assert_eq!(snapshot.flows().len(), 1);
+        assert_eq!(
+            snapshot.flows()[0].to_string(),
+            "Order [trader -> audit, exchange] { Fill [exchange -> trader], Report [audit -> trader] }"
+        );
+        assert_eq!(snapshot.received_by("trader"), ["Fill", "Report"]);
+        assert!(snapshot.received_by("nobody").is_empty());
+        assert_eq!(snapshot.topic_counts()["default"], 4);
+    }
+}
@@ -1,17 +1,20 @@
 use std::{
     fmt,
-    sync::Arc,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
     time::{Duration, Instant},
 };
 
 use tokio::sync::mpsc::{Sender, UnboundedReceiver, unbounded_channel};
 
 use crate::{
-    ActorId, Envelope, Event, EventId, Supervisor, Topic,
+    ActorId, Envelope, Event, EventId, Label, Supervisor, Topic,
     monitoring::MonitorHandle,
     testing::{
         ActorSpy, EventChain, EventCollector, EventEntry, EventMatcher, EventQuery, EventRecords,
-        EventSpy, TopicSpy, expectation::Expectation,
+        EventSpy, FlowSnapshot, StepEntry, TopicSpy, expectation::Expectation,
     },
 };
 
@@ -48,6 +51,8 @@ pub struct Harness<E: Event, T: Topic<E>> {
     records: EventRecords<E, T>,
     monitor_handle: MonitorHandle<E, T>,
     pub(super) receiver: UnboundedReceiver<EventEntry<E, T>>,
+    steps: Arc<Mutex<Vec<StepEntry>>>,
+    lagged: Arc<AtomicU64>,
     actor_sender: Sender<Arc<Envelope<E>>>,
 }
 
@@ -63,13 +68,17 @@ impl<E: Event, T: Topic<E>> fmt::Debug for Harness<E, T> {
 impl<E: Event, T: Topic<E>> Harness<E, T> {
     pub async fn new(supervisor: &mut Supervisor<E, T>) -> Self {
         let (tx, rx) = unbounded_channel();
-        let monitor = EventCollector::new(tx);
+        let steps = Arc::new(Mutex::new(Vec::new()));
+        let lagged = Arc::new(AtomicU64::new(0));
+        let monitor = EventCollector::new(tx, steps.clone(), lagged.clone());
         let monitor_handle = supervisor.monitors().add(monitor).await;
         Self {
             snapshot: Vec::new(),
             records: Arc::new(Vec::new()),
             monitor_handle,
             receiver: rx,
+            steps,
+            lagged,
             actor_sender: supervisor.sender.clone(),
         }
     }
@@ -163,6 +172,10 @@ impl<E: Event, T: Topic<E>> Harness<E, T> {
         self.snapshot.clear();
         self.records = Arc::new(Vec::new());
         while let Ok(_entry) = self.receiver.try_recv() {}
+        if let Ok(mut steps) = self.steps.lock() {
+            steps.clear();
+        }
+        self.lagged.store(0, Ordering::Relaxed);
     }
 
     /// Default settle window: wait 1ms for quiet before considering settled.
@@ -280,6 +293,40 @@ impl<E: Event, T: Topic<E>> Harness<E, T> {
         EventChain::new(self.records.clone(), id)
     }
 
+    /// Returns the steps an actor completed while recording, in order.
+    ///
+    /// Steps are only reported when the supervisor is configured with
+    /// [`Config::with_step_monitoring`](crate::Config::with_step_monitoring).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let steps = test.steps(&poller);
+    /// assert!(steps.iter().all(|s| s.action() == Some(StepAction::Backoff(POLL_INTERVAL))));
+    /// ```
+    pub fn steps(&self, actor: &ActorId) -> Vec<StepEntry> {
+        self.steps
+            .lock()
+            .map(|steps| {
+                steps
+                    .iter()
+                    .filter(|step| step.actor_id() == actor)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of monitoring events the harness missed because
+    /// the monitoring channel was full.
+    ///
+    /// When non-zero, queries may be missing deliveries. Enlarge the channel
+    /// or configure the supervisor with
+    /// [`Config::with_blocking_monitoring`](crate::Config::with_blocking_monitoring).
+    pub fn lagged(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
     // ==================== Debugging ====================
 
     /// Print all recorded events to stdout for debugging.
@@ -307,3 +354,17 @@ impl<E: Event, T: Topic<E>> Harness<E, T> {
         self.records.len()
     }
 }
+
+impl<E: Event + Label, T: Topic<E> + Label> Harness<E, T> {
+    /// Returns the structure of the recorded event flow, for comparing runs.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let diff = expected.diff(&test.flow_snapshot());
+    /// assert!(diff.is_empty(), "event flow changed:\n{diff}");
+    /// ```
+    pub fn flow_snapshot(&self) -> FlowSnapshot {
+        FlowSnapshot::new(&self.records)
+    }
+}
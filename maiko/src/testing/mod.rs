@@ -38,6 +38,20 @@
 //! assert!(chain.events().segment(&["Input", "Processed", "Output"]));
 //! ```
 //!
+//! # Comparing Runs
+//!
+//! [`Harness::flow_snapshot`] captures the structure of the recorded flow
+//! (correlation trees, labels received per actor, deliveries per topic)
+//! without ids, and [`FlowSnapshot::diff`] reports what was added, is missing
+//! or got reordered between two runs.
+//!
+//! # Offline Analysis
+//!
+//! With the `recorder` feature, [`RecordingReader`] loads files written by the
+//! [`Recorder`](crate::monitors::Recorder) into a [`Recording`], which offers
+//! the same queries (`events()`, `actor()`, `chain()`, ...) over production
+//! traffic.
+//!
 //! # Warning
 //!
 //! **Do not use in production.** See [`Harness`] documentation for details.
@@ -52,7 +66,12 @@ mod event_query;
 mod event_spy;
 mod event_trace;
 pub(crate) mod expectation;
+mod flow_diff;
+mod flow_snapshot;
 mod harness;
+#[cfg(feature = "recorder")]
+mod recording;
+mod step_entry;
 mod topic_spy;
 
 pub use actor_spy::ActorSpy;
@@ -65,7 +84,13 @@ pub use event_query::EventQuery;
 pub use event_spy::EventSpy;
 pub use event_trace::EventTrace;
 pub use expectation::Expectation;
+pub use flow_diff::{ActorChange, ChangeKind, FlowChange, FlowDiff, TopicChange};
+pub use flow_snapshot::{FlowSnapshot, FlowTree};
 pub use harness::Harness;
+#[cfg(feature = "recorder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+pub use recording::{Recording, RecordingReader};
+pub use step_entry::StepEntry;
 pub use topic_spy::TopicSpy;
 
 pub(crate) type EventRecords<E, T> = std::sync::Arc<Vec<EventEntry<E, T>>>;
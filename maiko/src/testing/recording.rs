@@ -0,0 +1,327 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    marker::PhantomData,
+    path::Path,
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    ActorId, Event, EventId, Label, Topic,
+    codec::{self, Codec, Json},
+    monitoring::Stage,
+    monitors::Record,
+    testing::{
+        ActorSpy, EventChain, EventEntry, EventQuery, EventRecords, EventSpy, FlowSnapshot,
+        TopicSpy,
+    },
+};
+
+/// Streams the deliveries of a [`Recorder`](crate::monitors::Recorder) file
+/// as [`EventEntry`] records, the same ones the [`Harness`](super::Harness)
+/// collects.
+///
+/// By default an entry is built from every `handled` record, matching what
+/// the harness observes in a live system; [`with_stage`](Self::with_stage)
+/// picks another stage, e.g. [`Stage::Dispatched`] for recordings limited
+/// to it. Records without a receiver (from
+/// [`RecordMode::PerEvent`](crate::monitors::RecordMode::PerEvent)) can't
+/// form a delivery and are skipped. Topics are derived from the events with
+/// [`Topic::from_event`], unless [`with_topics`](Self::with_topics) builds
+/// them from the recorded topic labels.
+///
+/// Iterate the reader to process huge recordings as a stream, or collect
+/// it with [`read_all`](Self::read_all) to run queries.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let recording = RecordingReader::<MyEvent, MyTopic>::open("incident.jsonl")?.read_all()?;
+/// let chain = recording.chain(order_id);
+/// println!("{}", chain.to_mermaid());
+/// assert!(recording.actor(&ActorId::new("risk".into())).events_received() > 0);
+/// ```
+pub struct RecordingReader<E, T, C = Json> {
+    reader: BufReader<File>,
+    codec: C,
+    stage: Stage,
+    topic: fn(&E, Option<&str>) -> T,
+    buf: Vec<u8>,
+    record_no: u64,
+    _types: PhantomData<fn() -> (E, T)>,
+}
+
+impl<E, T> RecordingReader<E, T>
+where
+    E: Event + DeserializeOwned,
+    T: Topic<E>,
+{
+    /// Open a JSON Lines recording.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::open_with_codec(path, Json)
+    }
+}
+
+impl<E, T, C> RecordingReader<E, T, C>
+where
+    E: Event + DeserializeOwned,
+    T: Topic<E>,
+    C: Codec,
+{
+    /// Open a recording written with the given codec.
+    pub fn open_with_codec<P: AsRef<Path>>(path: P, codec: C) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+            codec,
+            stage: Stage::Handled,
+            topic: |event, _| T::from_event(event),
+            buf: Vec::new(),
+            record_no: 0,
+            _types: PhantomData,
+        })
+    }
+
+    /// Build entries from records of the given stage instead of `handled`.
+    pub fn with_stage(mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// Build topics with `f` from the event and the recorded topic label.
+    ///
+    /// Useful when the event type can't route itself, e.g. when events are
+    /// read as untyped JSON.
+    pub fn with_topics(mut self, f: fn(&E, Option<&str>) -> T) -> Self {
+        self.topic = f;
+        self
+    }
+
+    /// Read the next raw record, whatever its stage.
+    pub fn next_record(&mut self) -> io::Result<Option<Record<E>>> {
+        loop {
+            if !codec::read_record(&self.codec, &mut self.reader, &mut self.buf)? {
+                return Ok(None);
+            }
+            self.record_no += 1;
+            if self.codec.is_line_delimited() && self.buf.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return self.codec.decode(&self.buf).map(Some).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("record {}: {e}", self.record_no),
+                )
+            });
+        }
+    }
+
+    /// Read the whole recording into a queryable [`Recording`].
+    pub fn read_all(self) -> io::Result<Recording<E, T>> {
+        self.collect()
+    }
+
+    fn entry(&self, record: Record<E>) -> Option<EventEntry<E, T>> {
+        if record.stage() != self.stage {
+            return None;
+        }
+        let receiver = record.actor().cloned()?;
+        let label = record.topic().map(str::to_owned);
+        let envelope = record.into_envelope()?;
+        let topic = (self.topic)(envelope.event(), label.as_deref());
+        Some(EventEntry::new(
+            Arc::new(envelope),
+            Arc::new(topic),
+            receiver,
+        ))
+    }
+}
+
+impl<E, T, C> Iterator for RecordingReader<E, T, C>
+where
+    E: Event + DeserializeOwned,
+    T: Topic<E>,
+    C: Codec,
+{
+    type Item = io::Result<EventEntry<E, T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_record() {
+                Ok(Some(record)) => {
+                    if let Some(entry) = self.entry(record) {
+                        return Some(Ok(entry));
+                    }
+                }
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Deliveries loaded from a recording, with the same queries as the
+/// [`Harness`](super::Harness).
+///
+/// Built with [`RecordingReader::read_all`], or collected from the entries
+/// of several readers, e.g. the rotated files of one recording.
+#[derive(Debug, Clone)]
+pub struct Recording<E: Event, T: Topic<E>> {
+    records: EventRecords<E, T>,
+}
+
+impl<E: Event, T: Topic<E>> Recording<E, T> {
+    /// Returns a query over all recorded deliveries.
+    pub fn events(&self) -> EventQuery<E, T> {
+        EventQuery::new(self.records.clone())
+    }
+
+    /// Returns a spy for a specific event by ID.
+    pub fn event(&self, id: EventId) -> EventSpy<E, T> {
+        EventSpy::new(self.records.clone(), id)
+    }
+
+    /// Returns a spy for events from a specific actor's perspective.
+    pub fn actor(&self, actor: &ActorId) -> ActorSpy<E, T> {
+        ActorSpy::new(self.records.clone(), actor.clone())
+    }
+
+    /// Returns a spy for events on a specific topic.
+    pub fn topic(&self, topic: T) -> TopicSpy<E, T> {
+        TopicSpy::new(self.records.clone(), topic)
+    }
+
+    /// Returns the chain of events correlated to the given root event.
+    pub fn chain(&self, id: EventId) -> EventChain<E, T> {
+        EventChain::new(self.records.clone(), id)
+    }
+
+    /// Number of deliveries in the recording.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl<E: Event + Label, T: Topic<E> + Label> Recording<E, T> {
+    /// Returns the structure of the recorded event flow, for comparing
+    /// recordings with [`FlowSnapshot::diff`].
+    pub fn flow_snapshot(&self) -> FlowSnapshot {
+        FlowSnapshot::new(&self.records)
+    }
+}
+
+impl<E: Event, T: Topic<E>> FromIterator<EventEntry<E, T>> for Recording<E, T> {
+    fn from_iter<I: IntoIterator<Item = EventEntry<E, T>>>(iter: I) -> Self {
+        Self {
+            records: Arc::new(iter.into_iter().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{
+        DefaultTopic, Envelope, Label,
+        monitoring::Monitor,
+        monitors::{RecordMode, Recorder, RecorderConfig},
+    };
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum Flow {
+        Order(u32),
+        Fill(u32),
+    }
+    impl Event for Flow {}
+
+    impl Label for Flow {
+        fn label(&self) -> std::borrow::Cow<'static, str> {
+            match self {
+                Flow::Order(_) => "Order".into(),
+                Flow::Fill(_) => "Fill".into(),
+            }
+        }
+    }
+
+    fn actor(name: &str) -> ActorId {
+        ActorId::new(Arc::from(name))
+    }
+
+    fn record(recorder: &Recorder, envelope: &Envelope<Flow>, receiver: &ActorId) {
+        let monitor: &dyn Monitor<Flow, DefaultTopic> = recorder;
+        monitor.on_event_dispatched(envelope, &DefaultTopic, receiver);
+        monitor.on_event_delivered(envelope, &DefaultTopic, receiver);
+        monitor.on_event_handled(envelope, &DefaultTopic, receiver);
+    }
+
+    #[test]
+    fn recording_supports_harness_queries() {
+        let path =
+            std::env::temp_dir().join(format!("maiko_recording_{}.jsonl", uuid::Uuid::new_v4()));
+        let order = Envelope::new(Flow::Order(1), actor("trader"));
+        let fill = Envelope::with_correlation(Flow::Fill(1), actor("exchange"), order.id());
+        {
+            let recorder = Recorder::new(&path).unwrap();
+            record(&recorder, &order, &actor("exchange"));
+            record(&recorder, &order, &actor("audit"));
+            record(&recorder, &fill, &actor("trader"));
+        }
+
+        let recording = RecordingReader::<Flow, DefaultTopic>::open(&path)
+            .unwrap()
+            .read_all()
+            .unwrap();
+        assert_eq!(recording.len(), 3);
+        assert_eq!(recording.events().sent_by(&actor("trader")).count(), 2);
+        assert!(
+            recording
+                .event(order.id())
+                .was_delivered_to(&actor("audit"))
+        );
+        assert_eq!(recording.actor(&actor("trader")).events_received(), 1);
+
+        let chain = recording.chain(order.id());
+        assert!(chain.events().segment(&["Order", "Fill"]));
+        assert!(chain.to_mermaid().contains("exchange"));
+
+        let dispatched = RecordingReader::<Flow, DefaultTopic>::open(&path)
+            .unwrap()
+            .with_stage(Stage::Dispatched)
+            .count();
+        assert_eq!(dispatched, 3);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn per_event_records_have_no_deliveries() {
+        let path =
+            std::env::temp_dir().join(format!("maiko_recording_{}.jsonl", uuid::Uuid::new_v4()));
+        {
+            let config = RecorderConfig::default().with_mode(RecordMode::PerEvent);
+            let recorder = Recorder::open(&path, config).unwrap();
+            record(
+                &recorder,
+                &Envelope::new(Flow::Order(1), actor("trader")),
+                &actor("x"),
+            );
+        }
+
+        let mut reader = RecordingReader::<Flow, DefaultTopic>::open(&path)
+            .unwrap()
+            .with_stage(Stage::Dispatched);
+        assert!(reader.next().is_none());
+
+        let mut raw = RecordingReader::<Flow, DefaultTopic>::open(&path).unwrap();
+        let record = raw.next_record().unwrap().unwrap();
+        assert_eq!(record.stage(), Stage::Dispatched);
+        assert!(raw.next_record().unwrap().is_none());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,115 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{ActorId, StepAction};
+
+/// A record of a single completed actor `step()`.
+///
+/// Collected by the [`Harness`](super::Harness) when step monitoring is
+/// enabled with [`Config::with_step_monitoring`](crate::Config::with_step_monitoring).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepEntry {
+    actor_id: ActorId,
+    result: Result<StepAction, Arc<str>>,
+    duration: Duration,
+}
+
+impl StepEntry {
+    pub(crate) fn new(
+        actor_id: ActorId,
+        result: Result<StepAction, Arc<str>>,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            actor_id,
+            result,
+            duration,
+        }
+    }
+
+    /// Returns the actor that stepped.
+    #[inline]
+    pub fn actor_id(&self) -> &ActorId {
+        &self.actor_id
+    }
+
+    /// Returns the action the step returned, or `None` if it failed.
+    #[inline]
+    pub fn action(&self) -> Option<StepAction> {
+        self.result.as_ref().ok().copied()
+    }
+
+    /// Returns the error message if the step failed.
+    #[inline]
+    pub fn error(&self) -> Option<&str> {
+        self.result.as_ref().err().map(|e| e.as_ref())
+    }
+
+    /// Returns how long the step took.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{Actor, Config, Error, Event, StepAction, Subscribe, Supervisor, testing::Harness};
+
+    #[derive(Clone, Debug)]
+    struct Tick;
+    impl Event for Tick {}
+
+    /// Fails its first step, then backs off.
+    struct Poller {
+        steps: usize,
+    }
+
+    impl Actor for Poller {
+        type Event = Tick;
+
+        async fn step(&mut self) -> crate::Result<StepAction> {
+            self.steps += 1;
+            if self.steps == 1 {
+                return Err(Error::External("poll failed".into()));
+            }
+            Ok(StepAction::Backoff(Duration::from_millis(1)))
+        }
+
+        fn on_error(&self, _error: Error) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn steps(config: Config) -> Vec<super::StepEntry> {
+        let mut sup = Supervisor::<Tick>::new(config);
+        let mut test = Harness::new(&mut sup).await;
+        test.record().await;
+        let poller = sup
+            .add_actor("poller", |_| Poller { steps: 0 }, Subscribe::none())
+            .unwrap();
+        sup.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        test.settle().await;
+        sup.stop().await.unwrap();
+        test.steps(&poller)
+    }
+
+    #[tokio::test]
+    async fn harness_records_steps() {
+        let steps = steps(Config::default().with_step_monitoring(true)).await;
+        assert!(steps.len() > 2, "{steps:?}");
+        assert!(steps[0].error().unwrap().contains("poll failed"));
+        assert_eq!(steps[0].action(), None);
+        assert_eq!(
+            steps[1].action(),
+            Some(StepAction::Backoff(Duration::from_millis(1)))
+        );
+    }
+
+    #[tokio::test]
+    async fn steps_are_not_reported_by_default() {
+        assert!(steps(Config::default()).await.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
-use std::hash::Hash;
+use std::{borrow::Cow, hash::Hash};
 
-use crate::{OverflowPolicy, event::Event};
+use crate::{Label, OverflowPolicy, event::Event};
 
 /// Maps events to routing topics.
 ///
@@ -43,6 +43,27 @@ pub trait Topic<E: Event>: Hash + PartialEq + Eq + Clone + Send + Sync + 'static
     fn overflow_policy(&self) -> OverflowPolicy {
         OverflowPolicy::Fail
     }
+
+    /// Returns how many of the most recent events the broker retains for this topic.
+    ///
+    /// Retained events are replayed to a subscriber when it is added to the
+    /// broker, similar to MQTT retained messages, so actors joining late still
+    /// observe the current state (e.g. the last `Config` event). Inspect
+    /// retained events with [`Supervisor::retained`](crate::Supervisor::retained).
+    ///
+    /// The default is `0` (no retention). Override this method to opt in:
+    ///
+    /// ```rust,ignore
+    /// fn retention(&self) -> usize {
+    ///     match self {
+    ///         MyTopic::Config => 1,
+    ///         _ => 0,
+    ///     }
+    /// }
+    /// ```
+    fn retention(&self) -> usize {
+        0
+    }
 }
 
 /// Unit topic for systems that don't need topic-based routing.
@@ -68,6 +89,12 @@ impl<E: Event> Topic<E> for DefaultTopic {
     }
 }
 
+impl Label for DefaultTopic {
+    fn label(&self) -> Cow<'static, str> {
+        Cow::Borrowed("default")
+    }
+}
+
 impl std::fmt::Display for DefaultTopic {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "default")
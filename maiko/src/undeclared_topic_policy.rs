@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// Controls what the broker does when an actor sends an event to a topic it
+/// didn't declare with [`ActorBuilder::publishes`](crate::ActorBuilder::publishes).
+///
+/// Only actors that declare their publications are checked; events from
+/// actors without a declaration are always routed.
+///
+/// # Example
+///
+/// ```rust
+/// use maiko::{Config, UndeclaredTopicPolicy};
+///
+/// let config = Config::default().with_undeclared_topic_policy(UndeclaredTopicPolicy::Error);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UndeclaredTopicPolicy {
+    /// Route the event without checking.
+    Ignore,
+
+    /// Log a warning and route the event.
+    #[default]
+    Warn,
+
+    /// Log an error and drop the event.
+    Error,
+}
+
+impl fmt::Display for UndeclaredTopicPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UndeclaredTopicPolicy::Ignore => write!(f, "Ignore"),
+            UndeclaredTopicPolicy::Warn => write!(f, "Warn"),
+            UndeclaredTopicPolicy::Error => write!(f, "Error"),
+        }
+    }
+}
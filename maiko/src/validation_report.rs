@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::{ActorId, Label};
+
+/// Problems in the declared topology, found by
+/// [`Supervisor::validate`](crate::Supervisor::validate).
+///
+/// Compares the topics actors subscribe to with the topics they declare
+/// with [`ActorBuilder::publishes`](crate::ActorBuilder::publishes).
+/// Subscribing to or publishing [`Subscribe::all()`](crate::Subscribe::all)
+/// matches every topic on the other side.
+///
+/// Display lists the problems by topic label:
+///
+/// ```text
+/// published but not subscribed: Audit
+/// subscribed but not published: Fills, Quotes
+/// neither subscribing nor publishing: idle
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport<T> {
+    unconsumed: Vec<T>,
+    unpublished: Vec<T>,
+    idle: Vec<ActorId>,
+}
+
+impl<T> ValidationReport<T> {
+    pub(crate) fn new(unconsumed: Vec<T>, unpublished: Vec<T>, idle: Vec<ActorId>) -> Self {
+        Self {
+            unconsumed,
+            unpublished,
+            idle,
+        }
+    }
+
+    /// Returns `true` if no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.unconsumed.is_empty() && self.unpublished.is_empty() && self.idle.is_empty()
+    }
+
+    /// Topics some actor publishes to but no actor subscribes to.
+    pub fn unconsumed_topics(&self) -> &[T] {
+        &self.unconsumed
+    }
+
+    /// Topics some actor subscribes to but no actor declares publishing to.
+    pub fn unpublished_topics(&self) -> &[T] {
+        &self.unpublished
+    }
+
+    /// Actors that neither subscribe to nor declare publishing to any topic.
+    pub fn idle_actors(&self) -> &[ActorId] {
+        &self.idle
+    }
+
+    /// One-line summary that doesn't need topic labels.
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.unconsumed.is_empty() {
+            parts.push(format!(
+                "{} published topic(s) without subscribers",
+                self.unconsumed.len()
+            ));
+        }
+        if !self.unpublished.is_empty() {
+            parts.push(format!(
+                "{} subscribed topic(s) without publishers",
+                self.unpublished.len()
+            ));
+        }
+        if !self.idle.is_empty() {
+            let names: Vec<_> = self.idle.iter().map(|id| id.name()).collect();
+            parts.push(format!("idle actor(s): {}", names.join(", ")));
+        }
+        parts.join("; ")
+    }
+}
+
+impl<T: Label> fmt::Display for ValidationReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = |topics: &[T]| {
+            let mut labels: Vec<_> = topics.iter().map(|t| t.label()).collect();
+            labels.sort();
+            labels.join(", ")
+        };
+        let mut lines = Vec::new();
+        if !self.unconsumed.is_empty() {
+            lines.push(format!(
+                "published but not subscribed: {}",
+                labels(&self.unconsumed)
+            ));
+        }
+        if !self.unpublished.is_empty() {
+            lines.push(format!(
+                "subscribed but not published: {}",
+                labels(&self.unpublished)
+            ));
+        }
+        if !self.idle.is_empty() {
+            let names: Vec<_> = self.idle.iter().map(|id| id.name()).collect();
+            lines.push(format!(
+                "neither subscribing nor publishing: {}",
+                names.join(", ")
+            ));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}